@@ -1,4 +1,8 @@
+use astrape::processor::MS_PER_SLOT;
+
 // Constants
-pub const PROGRAM_ID: &str = "5oDdrYxYbeABKyNyZHsgsJBREZjwZurzHcRPNGxtYPXn";
 pub const INTEREST_MINT: &str = "Gh9ZwEmdLJ8DscKNTkTqPbNwLNNBjuSzaG9Vp2KGtKJr"; // USDC Devnet
 pub const COLLATERAL_MINT: &str = "91AgzqSfXnCq6AJm5CPPHL3paB25difEJ1TfSnrFKrf"; // zBTC Devnet
+pub const BTC_USD_FEED_ID: &str =
+    "0xe62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43";
+pub const SLOTS_PER_MONTH: u64 = 30 * 24 * 60 * 60 * 1000 / MS_PER_SLOT;
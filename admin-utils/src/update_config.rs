@@ -1,22 +1,16 @@
 use anyhow::{anyhow, Result};
 use astrape::{
-    instructions::TokenLockInstruction,
-    processor::{CONFIG_SEED, SLOTS_PER_MONTH},
+    instruction::{self as astrape_instruction, config_address},
+    state::{ConfigUpdate, DepositPeriod},
 };
-use astrape_admin_utils::PROGRAM_ID;
-use borsh::BorshSerialize;
+use astrape_admin_utils::SLOTS_PER_MONTH;
 use clap::Parser;
 use solana_client::rpc_client::RpcClient;
-use solana_program::{
-    instruction::{AccountMeta, Instruction},
-    pubkey::Pubkey,
-};
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     signature::{read_keypair_file, Signer},
     transaction::Transaction,
 };
-use std::str::FromStr;
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 struct Args {
@@ -32,9 +26,6 @@ struct Args {
 fn main() -> Result<()> {
     let args = Args::parse();
 
-    // Parse Solana program ID
-    let program_id = Pubkey::from_str(PROGRAM_ID)?;
-
     // Initialize RPC client with the specified URL
     let rpc_client = RpcClient::new_with_commitment(&args.url, CommitmentConfig::confirmed());
     println!("Connected to Solana cluster at {}", args.url);
@@ -43,40 +34,40 @@ fn main() -> Result<()> {
     let admin_keypair = read_keypair_file(&args.keypair)
         .map_err(|_| anyhow!("Failed to read keypair file: {}", args.keypair))?;
     println!("Admin pubkey: {}", admin_keypair.pubkey());
-    // Find PDAs
-    let (config_pda, _) = Pubkey::find_program_address(&[CONFIG_SEED], &program_id);
-    println!("Config PDA: {}", config_pda);
+    println!("Config PDA: {}", config_address());
 
-    let instructions = {
-        let mut instructions = vec![];
-        for i in 0..=6 {
-            let instruction = Instruction {
-                program_id,
-                accounts: vec![
-                    AccountMeta::new(admin_keypair.pubkey(), true),
-                    AccountMeta::new(config_pda, false),
-                ],
-                data: TokenLockInstruction::AdminUpdateConfig {
-                    param: i,
-                    base_interest_rate: Some(213),
-                    price_factor: Some(100_000 / 10_u64.pow(8 - 6)),
+    // Annual interest rates per mille
+    let tenor = |months: u64, interest_rate: u64| DepositPeriod {
+        period: months * SLOTS_PER_MONTH,
+        interest_rate,
+        enabled: true,
+        capacity: None,
+    };
+
+    // Each update only applies the value of its param
+    let instructions = [2, 3, 4, 5, 6]
+        .into_iter()
+        .map(|param| {
+            astrape_instruction::admin_update_config(
+                &admin_keypair.pubkey(),
+                ConfigUpdate {
+                    param,
+                    deposit_period: None,
+                    pyth_price_max_age: None,
                     min_commission_rate: Some(200),
                     max_commission_rate: Some(500),
                     min_deposit_amount: Some(10_000_000),
                     max_deposit_amount: Some(1_000_000_000),
-                    deposit_periods: Some(vec![
-                        1 * SLOTS_PER_MONTH as u64,
-                        3 * SLOTS_PER_MONTH as u64,
-                        6 * SLOTS_PER_MONTH as u64,
-                    ]),
-                }
-                .try_to_vec()
-                .unwrap(),
-            };
-            instructions.push(instruction);
-        }
-        instructions
-    };
+                    deposit_periods: Some(vec![tenor(1, 213), tenor(3, 213), tenor(6, 213)]),
+                    pyth_feed_id: None,
+                    early_exit_policy: None,
+                    pool_capacity: None,
+                    withdrawal_sla: None,
+                    late_payment_rate: None,
+                },
+            )
+        })
+        .collect::<Vec<_>>();
 
     let mut transaction = Transaction::new_with_payer(&instructions, Some(&admin_keypair.pubkey()));
 
@@ -14,6 +14,25 @@ pub enum AstrapeError {
     #[error("Signer required")]
     SignerRequired,
 
+    #[error("Signer is not the pending admin")]
+    InvalidPendingAdmin,
+
+    #[error("Signer is not a multisig signer")]
+    InvalidMultisigSigner,
+
+    // Multisig errors
+    #[error("Action requires multisig approval")]
+    MultisigRequired,
+
+    #[error("Proposal already approved by signer")]
+    ProposalAlreadyApproved,
+
+    #[error("Proposal already executed")]
+    ProposalAlreadyExecuted,
+
+    #[error("Insufficient approvals: approvals={0}, threshold={1}")]
+    InsufficientApprovals(u8, u8),
+
     // Account validation errors
     #[error("Invalid pool account: {0}")]
     InvalidPoolAccount(u8),
@@ -39,6 +58,12 @@ pub enum AstrapeError {
     #[error("User deposit already exists")]
     UserDepositAlreadyExists,
 
+    #[error("Signer does not own the deposit position")]
+    NotPositionOwner,
+
+    #[error("Signer does not hold the receipt of the deposit position")]
+    ReceiptNotHeld,
+
     // Operation errors
     #[error("Invalid lock period: {0}")]
     InvalidLockPeriod(u64),
@@ -64,6 +89,9 @@ pub enum AstrapeError {
     #[error("Insufficient interest balance: {0}")]
     InsufficientInterestBalance(u64),
 
+    #[error("Deposit capacity exceeded, remaining capacity: {0}")]
+    DepositCapacityExceeded(u64),
+
     // State errors
     #[error("No deposit found for user")]
     NoDepositFound,
@@ -77,6 +105,21 @@ pub enum AstrapeError {
     #[error("Lock period not yet expired")]
     LockPeriodNotExpired,
 
+    #[error("Operation is paused")]
+    Paused,
+
+    #[error("Accounts do not match the withdrawal queue")]
+    WithdrawalQueueMismatch,
+
+    #[error("Deposit position is not next in the withdrawal queue")]
+    NotNextInWithdrawalQueue,
+
+    #[error("Withdrawal is not overdue, deadline: {0}")]
+    WithdrawalNotOverdue(u64),
+
+    #[error("Collateral pool can pay the withdrawal, balance: {0}")]
+    PoolLiquidityAvailable(u64),
+
     // Mathematical errors
     #[error("Arithmetic overflow")]
     ArithmeticOverflow,
@@ -138,6 +181,20 @@ impl From<AstrapeError> for ProgramError {
             AstrapeError::Unexpected => 26,
             AstrapeError::InvalidPythPriceFeed => 27,
             AstrapeError::GetPriceError => 28,
+            AstrapeError::InvalidPendingAdmin => 29,
+            AstrapeError::InvalidMultisigSigner => 30,
+            AstrapeError::MultisigRequired => 31,
+            AstrapeError::ProposalAlreadyApproved => 32,
+            AstrapeError::ProposalAlreadyExecuted => 33,
+            AstrapeError::InsufficientApprovals(_, _) => 34,
+            AstrapeError::Paused => 35,
+            AstrapeError::NotPositionOwner => 36,
+            AstrapeError::ReceiptNotHeld => 37,
+            AstrapeError::DepositCapacityExceeded(_) => 38,
+            AstrapeError::WithdrawalQueueMismatch => 39,
+            AstrapeError::NotNextInWithdrawalQueue => 40,
+            AstrapeError::WithdrawalNotOverdue(_) => 41,
+            AstrapeError::PoolLiquidityAvailable(_) => 42,
         };
 
         ProgramError::Custom(TOKEN_LOCK_ERROR_CODE_BASE + error_code)
@@ -0,0 +1,183 @@
+use borsh::{BorshDeserialize, BorshSerialize};
+use solana_program::{log::sol_log_data, pubkey::Pubkey};
+
+use crate::state::{DepositPeriod, EarlyExitPolicy, PauseFlags};
+
+// Events are logged with `sol_log_data` as a Borsh-encoded `AstrapeEvent`, so they show up
+// as "Program data: <base64>" in the transaction logs. The first byte is the event variant.
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct DepositEvent {
+    pub deposit: Pubkey, // Deposit account of the position
+    pub owner: Pubkey,
+    pub amount: u64,
+    pub deposit_period: u64,
+    pub unlock_slot: u64,
+    pub interest: u64,   // Upfront interest paid to the user
+    pub commission: u64, // Commission moved to the treasury
+}
+
+// The lock of a position is extended by `ExtendDeposit` or renewed by `Rollover`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct RelockEvent {
+    pub deposit: Pubkey,
+    pub amount: u64,
+    pub deposit_period: u64,
+    pub unlock_slot: u64,
+    pub interest: u64, // Upfront interest paid for the new lock
+    pub commission: u64,
+    pub rollover: bool,
+}
+
+// Upfront interest returned to the pool on an early exit
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct RefundEvent {
+    pub deposit: Pubkey,
+    pub amount: u64, // Collateral leaving the lock
+    pub refund: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct WithdrawalRequestedEvent {
+    pub deposit: Pubkey,
+    pub amount: u64,
+    pub early: bool,
+    pub total_pending: u64, // Collateral of all queued positions, this one included
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct WithdrawalPreparedEvent {
+    pub deposit: Pubkey,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct WithdrawalCompletedEvent {
+    pub deposit: Pubkey,
+    pub recipient: Pubkey, // Holder of the receipt
+    pub amount: u64,
+    pub overdue: bool, // Paid from the collateral pool after the withdrawal SLA
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct LatePaymentEvent {
+    pub deposit: Pubkey,
+    pub recipient: Pubkey,
+    pub compensation: u64,
+}
+
+// Owner of a position changed with `TransferPosition`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct PositionTransferredEvent {
+    pub deposit: Pubkey,
+    pub from: Pubkey,
+    pub to: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct RolloverUpdatedEvent {
+    pub deposit: Pubkey,
+    pub rollover: bool,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct DepositClosedEvent {
+    pub deposit: Pubkey,
+    pub recipient: Pubkey,
+    pub refund: u64, // Rent refunded in lamports
+}
+
+// New value of the config field set by each `ConfigUpdate` param, in param order
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum ConfigValue {
+    DepositPeriod(DepositPeriod),
+    PythPriceMaxAge(u64),
+    MinCommissionRate(u64),
+    MaxCommissionRate(u64),
+    MinDepositAmount(u64),
+    MaxDepositAmount(u64),
+    DepositPeriods(Vec<DepositPeriod>),
+    PythFeedId([u8; 32]),
+    EarlyExitPolicy(EarlyExitPolicy),
+    PoolCapacity(Option<u64>),
+    WithdrawalSla(Option<u64>),
+    LatePaymentRate(u64),
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ConfigUpdatedEvent {
+    pub param: u8, // `ConfigUpdate` param which was applied
+    pub value: ConfigValue,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct PauseUpdatedEvent {
+    pub paused: PauseFlags,
+}
+
+// The admin proposed a successor, who takes over with `AcceptAdmin`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct AdminProposedEvent {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct AdminChangedEvent {
+    pub admin: Pubkey,
+}
+
+// The multisig was enabled or its signers replaced
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct MultisigUpdatedEvent {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub enum FundMovement {
+    CollateralInvested { strategy: u64 },
+    CollateralReturned { strategy: u64, principal: u64 },
+    InterestDeposited,
+    InterestWithdrawn,
+    TreasuryWithdrawn,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct AdminFundsEvent {
+    pub movement: FundMovement,
+    pub amount: u64,
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum AstrapeEvent {
+    Deposit(DepositEvent),
+    Relock(RelockEvent),
+    Refund(RefundEvent),
+    WithdrawalRequested(WithdrawalRequestedEvent),
+    WithdrawalPrepared(WithdrawalPreparedEvent),
+    WithdrawalCompleted(WithdrawalCompletedEvent),
+    LatePayment(LatePaymentEvent),
+    ConfigUpdated(ConfigUpdatedEvent),
+    PauseUpdated(PauseUpdatedEvent),
+    AdminChanged(AdminChangedEvent),
+    AdminFunds(AdminFundsEvent),
+    PositionTransferred(PositionTransferredEvent),
+    RolloverUpdated(RolloverUpdatedEvent),
+    DepositClosed(DepositClosedEvent),
+    AdminProposed(AdminProposedEvent),
+    MultisigUpdated(MultisigUpdatedEvent),
+}
+
+impl AstrapeEvent {
+    pub fn emit(&self) {
+        let data = self
+            .try_to_vec()
+            .expect("Serializing an event into a Vec cannot fail");
+        sol_log_data(&[&data]);
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        Self::try_from_slice(data)
+    }
+}
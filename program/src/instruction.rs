@@ -0,0 +1,766 @@
+use solana_program::{
+    bpf_loader_upgradeable::get_program_data_address,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    system_program, sysvar,
+};
+use spl_associated_token_account::get_associated_token_address;
+
+use crate::{
+    instructions::AstrapeInstruction,
+    processor::{
+        AUTHORITY_SEED, CONFIG_SEED, INVESTMENT_LEDGER_SEED, MULTISIG_PROPOSAL_SEED, MULTISIG_SEED,
+        POOL_STATE_SEED, RECEIPT_MINT_SEED, TREASURY_SEED, USER_DEPOSIT_COUNTER_SEED,
+        USER_DEPOSIT_SEED, WITHDRAWAL_POOL_SEED, WITHDRAWAL_QUEUE_SEED,
+    },
+    state::{ConfigUpdate, DepositPeriod, MultisigAction, PauseFlags},
+};
+
+// Builders for every `AstrapeInstruction`, in the account order documented on the variant.
+// PDAs and token accounts are derived from the essential pubkeys, token accounts being the
+// associated token accounts of their owner. A deposit position is addressed by the user who
+// opened it and its deposit index, since its account stays derived from them after a transfer.
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &crate::id()).0
+}
+
+pub fn config_address() -> Pubkey {
+    pda(&[CONFIG_SEED])
+}
+
+pub fn authority_address() -> Pubkey {
+    pda(&[AUTHORITY_SEED])
+}
+
+pub fn withdrawal_pool_address() -> Pubkey {
+    pda(&[WITHDRAWAL_POOL_SEED])
+}
+
+pub fn treasury_address() -> Pubkey {
+    pda(&[TREASURY_SEED])
+}
+
+pub fn pool_state_address() -> Pubkey {
+    pda(&[POOL_STATE_SEED])
+}
+
+pub fn investment_ledger_address() -> Pubkey {
+    pda(&[INVESTMENT_LEDGER_SEED])
+}
+
+pub fn withdrawal_queue_address() -> Pubkey {
+    pda(&[WITHDRAWAL_QUEUE_SEED])
+}
+
+// The tail account is ignored while the queue is empty, the queue itself stands in for it
+fn queue_tail_address(queue_tail: Option<&Pubkey>) -> Pubkey {
+    queue_tail.copied().unwrap_or_else(withdrawal_queue_address)
+}
+
+pub fn user_deposit_counter_address(user: &Pubkey) -> Pubkey {
+    pda(&[USER_DEPOSIT_COUNTER_SEED, user.as_ref()])
+}
+
+pub fn user_deposit_address(user: &Pubkey, deposit_index: u64) -> Pubkey {
+    pda(&[
+        USER_DEPOSIT_SEED,
+        user.as_ref(),
+        &deposit_index.to_le_bytes(),
+    ])
+}
+
+pub fn receipt_mint_address(deposit: &Pubkey) -> Pubkey {
+    pda(&[RECEIPT_MINT_SEED, deposit.as_ref()])
+}
+
+pub fn multisig_address() -> Pubkey {
+    pda(&[MULTISIG_SEED])
+}
+
+pub fn proposal_address(proposal_index: u64) -> Pubkey {
+    pda(&[MULTISIG_PROPOSAL_SEED, &proposal_index.to_le_bytes()])
+}
+
+// Pool's token account of `mint`, owned by the authority PDA
+fn pool_address(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&authority_address(), mint)
+}
+
+// Receipt token account of `holder` for a position
+fn receipt_address(holder: &Pubkey, deposit: &Pubkey) -> Pubkey {
+    get_associated_token_address(holder, &receipt_mint_address(deposit))
+}
+
+fn build(accounts: Vec<AccountMeta>, instruction: AstrapeInstruction) -> Instruction {
+    Instruction {
+        program_id: crate::id(),
+        accounts,
+        data: instruction
+            .pack()
+            .expect("Serializing an instruction into a Vec cannot fail"),
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn initialize(
+    admin: &Pubkey,
+    interest_mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    pyth_feed_id: [u8; 32],
+    pyth_price_max_age: u64,
+    min_commission_rate: u64,
+    max_commission_rate: u64,
+    min_deposit_amount: u64,
+    max_deposit_amount: u64,
+    deposit_periods: Vec<DepositPeriod>,
+) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new(authority_address(), false),
+            AccountMeta::new(pool_address(interest_mint), false),
+            AccountMeta::new(pool_address(collateral_mint), false),
+            AccountMeta::new(withdrawal_pool_address(), false),
+            AccountMeta::new(treasury_address(), false),
+            AccountMeta::new_readonly(*interest_mint, false),
+            AccountMeta::new_readonly(*collateral_mint, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new_readonly(sysvar::rent::id(), false),
+            AccountMeta::new(pool_state_address(), false),
+            AccountMeta::new(investment_ledger_address(), false),
+            AccountMeta::new(withdrawal_queue_address(), false),
+            AccountMeta::new_readonly(get_program_data_address(&crate::id()), false),
+        ],
+        AstrapeInstruction::Initialize {
+            interest_mint: *interest_mint,
+            collateral_mint: *collateral_mint,
+            pyth_feed_id,
+            pyth_price_max_age,
+            min_commission_rate,
+            max_commission_rate,
+            min_deposit_amount,
+            max_deposit_amount,
+            deposit_periods,
+        },
+    )
+}
+
+pub fn admin_update_config(admin: &Pubkey, update: ConfigUpdate) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new_readonly(pool_state_address(), false),
+        ],
+        AstrapeInstruction::AdminUpdateConfig { update },
+    )
+}
+
+pub fn admin_withdraw_collateral_for_investment(
+    admin: &Pubkey,
+    collateral_mint: &Pubkey,
+    amount: u64,
+    strategy: u64,
+) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(get_associated_token_address(admin, collateral_mint), false),
+            AccountMeta::new(pool_address(collateral_mint), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new(investment_ledger_address(), false),
+        ],
+        AstrapeInstruction::AdminWithdrawCollateralForInvestment { amount, strategy },
+    )
+}
+
+/// `owner` is the current owner of the position opened by `user` at `deposit_index`
+pub fn admin_prepare_withdrawal(
+    admin: &Pubkey,
+    collateral_mint: &Pubkey,
+    owner: &Pubkey,
+    user: &Pubkey,
+    deposit_index: u64,
+) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new(get_associated_token_address(admin, collateral_mint), false),
+            AccountMeta::new(withdrawal_pool_address(), false),
+            AccountMeta::new_readonly(*owner, false),
+            AccountMeta::new(user_deposit_address(user, deposit_index), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(pool_state_address(), false),
+            AccountMeta::new(withdrawal_queue_address(), false),
+        ],
+        AstrapeInstruction::AdminPrepareWithdrawal { deposit_index },
+    )
+}
+
+pub fn admin_deposit_interest(admin: &Pubkey, interest_mint: &Pubkey, amount: u64) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(get_associated_token_address(admin, interest_mint), false),
+            AccountMeta::new(pool_address(interest_mint), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+        AstrapeInstruction::AdminDepositInterest { amount },
+    )
+}
+
+pub fn admin_withdraw_interest(admin: &Pubkey, interest_mint: &Pubkey, amount: u64) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(get_associated_token_address(admin, interest_mint), false),
+            AccountMeta::new(pool_address(interest_mint), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+        AstrapeInstruction::AdminWithdrawInterest { amount },
+    )
+}
+
+/// `deposit_index` must be the current deposit count of `user`, read from its deposit counter
+#[allow(clippy::too_many_arguments)]
+pub fn deposit_collateral(
+    user: &Pubkey,
+    interest_mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    pyth_price_feed: &Pubkey,
+    deposit_index: u64,
+    amount: u64,
+    deposit_period: u64,
+    commission_rate: u64,
+    rollover: bool,
+) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new(*user, true),
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(get_associated_token_address(user, collateral_mint), false),
+            AccountMeta::new(user_deposit_counter_address(user), false),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new(pool_address(collateral_mint), false),
+            AccountMeta::new(get_associated_token_address(user, interest_mint), false),
+            AccountMeta::new(pool_address(interest_mint), false),
+            AccountMeta::new(treasury_address(), false),
+            AccountMeta::new_readonly(*pyth_price_feed, false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(receipt_mint_address(&deposit), false),
+            AccountMeta::new(receipt_address(user, &deposit), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+            AccountMeta::new(pool_state_address(), false),
+        ],
+        AstrapeInstruction::DepositCollateral {
+            amount,
+            deposit_period,
+            commission_rate,
+            rollover,
+        },
+    )
+}
+
+/// `queue_tail` is the `tail` of the withdrawal queue, the deposit account of its newest
+/// position, and `None` when the queue is empty
+pub fn request_withdrawal_early(
+    holder: &Pubkey,
+    interest_mint: &Pubkey,
+    user: &Pubkey,
+    deposit_index: u64,
+    queue_tail: Option<&Pubkey>,
+) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new(get_associated_token_address(holder, interest_mint), false),
+            AccountMeta::new(pool_address(interest_mint), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(pool_state_address(), false),
+            AccountMeta::new_readonly(receipt_address(holder, &deposit), false),
+            AccountMeta::new(withdrawal_queue_address(), false),
+            AccountMeta::new(queue_tail_address(queue_tail), false),
+        ],
+        AstrapeInstruction::RequestWithdrawalEarly { deposit_index },
+    )
+}
+
+/// `queue_tail` is the deposit account of the newest position in the withdrawal queue, if any
+pub fn request_withdrawal(
+    holder: &Pubkey,
+    user: &Pubkey,
+    deposit_index: u64,
+    queue_tail: Option<&Pubkey>,
+) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new_readonly(receipt_address(holder, &deposit), false),
+            AccountMeta::new(pool_state_address(), false),
+            AccountMeta::new(withdrawal_queue_address(), false),
+            AccountMeta::new(queue_tail_address(queue_tail), false),
+        ],
+        AstrapeInstruction::RequestWithdrawal { deposit_index },
+    )
+}
+
+pub fn withdraw_collateral(
+    holder: &Pubkey,
+    collateral_mint: &Pubkey,
+    user: &Pubkey,
+    deposit_index: u64,
+) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new(get_associated_token_address(holder, collateral_mint), false),
+            AccountMeta::new(withdrawal_pool_address(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(receipt_mint_address(&deposit), false),
+            AccountMeta::new(receipt_address(holder, &deposit), false),
+            AccountMeta::new(pool_state_address(), false),
+        ],
+        AstrapeInstruction::WithdrawCollateral { deposit_index },
+    )
+}
+
+/// The rent of the deposit account goes back to `user`, who opened the position
+pub fn close_deposit(owner: &Pubkey, user: &Pubkey, deposit_index: u64) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new(*owner, true),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new(*user, false),
+            AccountMeta::new(receipt_address(owner, &deposit), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(pool_state_address(), false),
+        ],
+        AstrapeInstruction::CloseDeposit { deposit_index },
+    )
+}
+
+pub fn propose_admin(admin: &Pubkey, new_admin: &Pubkey) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(config_address(), false),
+        ],
+        AstrapeInstruction::ProposeAdmin {
+            new_admin: *new_admin,
+        },
+    )
+}
+
+pub fn accept_admin(pending_admin: &Pubkey) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*pending_admin, true),
+            AccountMeta::new(config_address(), false),
+        ],
+        AstrapeInstruction::AcceptAdmin,
+    )
+}
+
+pub fn admin_initialize_multisig(
+    admin: &Pubkey,
+    signers: Vec<Pubkey>,
+    threshold: u8,
+) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new(multisig_address(), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        AstrapeInstruction::AdminInitializeMultisig { signers, threshold },
+    )
+}
+
+/// `proposal_index` must be the current proposal count of the multisig
+pub fn create_proposal(
+    signer: &Pubkey,
+    proposal_index: u64,
+    action: MultisigAction,
+) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new(multisig_address(), false),
+            AccountMeta::new(proposal_address(proposal_index), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+        ],
+        AstrapeInstruction::CreateProposal { action },
+    )
+}
+
+pub fn approve_proposal(signer: &Pubkey, proposal_index: u64) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*signer, true),
+            AccountMeta::new_readonly(multisig_address(), false),
+            AccountMeta::new(proposal_address(proposal_index), false),
+        ],
+        AstrapeInstruction::ApproveProposal { proposal_index },
+    )
+}
+
+/// The accounts of the fund movements are derived from `action`, which must be the action
+/// of the proposal
+pub fn execute_proposal(
+    signer: &Pubkey,
+    proposal_index: u64,
+    action: &MultisigAction,
+    interest_mint: &Pubkey,
+    collateral_mint: &Pubkey,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*signer, true),
+        AccountMeta::new(multisig_address(), false),
+        AccountMeta::new(proposal_address(proposal_index), false),
+        AccountMeta::new(config_address(), false),
+    ];
+    match action {
+        MultisigAction::WithdrawCollateralForInvestment { destination, .. } => {
+            accounts.extend([
+                AccountMeta::new_readonly(authority_address(), false),
+                AccountMeta::new(*destination, false),
+                AccountMeta::new(pool_address(collateral_mint), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(investment_ledger_address(), false),
+            ]);
+        }
+        MultisigAction::WithdrawInterest { destination, .. } => {
+            accounts.extend([
+                AccountMeta::new_readonly(authority_address(), false),
+                AccountMeta::new(*destination, false),
+                AccountMeta::new(pool_address(interest_mint), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ]);
+        }
+        MultisigAction::WithdrawTreasury { destination, .. } => {
+            accounts.extend([
+                AccountMeta::new_readonly(authority_address(), false),
+                AccountMeta::new(*destination, false),
+                AccountMeta::new(treasury_address(), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+            ]);
+        }
+        MultisigAction::UpdateConfig(_) => {
+            accounts.push(AccountMeta::new_readonly(pool_state_address(), false));
+        }
+        MultisigAction::SetSigners { .. } => {}
+        MultisigAction::ReturnCollateral { source, .. } => {
+            accounts.extend([
+                AccountMeta::new_readonly(authority_address(), false),
+                AccountMeta::new(*source, false),
+                AccountMeta::new(pool_address(collateral_mint), false),
+                AccountMeta::new_readonly(spl_token::id(), false),
+                AccountMeta::new(investment_ledger_address(), false),
+            ]);
+        }
+    }
+    build(
+        accounts,
+        AstrapeInstruction::ExecuteProposal { proposal_index },
+    )
+}
+
+pub fn admin_set_pause(admin: &Pubkey, paused: PauseFlags) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new(config_address(), false),
+        ],
+        AstrapeInstruction::AdminSetPause { paused },
+    )
+}
+
+pub fn admin_withdraw_treasury(admin: &Pubkey, interest_mint: &Pubkey, amount: u64) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(get_associated_token_address(admin, interest_mint), false),
+            AccountMeta::new(treasury_address(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        AstrapeInstruction::AdminWithdrawTreasury { amount },
+    )
+}
+
+pub fn request_partial_withdrawal_early(
+    holder: &Pubkey,
+    interest_mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    user: &Pubkey,
+    deposit_index: u64,
+    amount: u64,
+) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new(get_associated_token_address(holder, interest_mint), false),
+            AccountMeta::new(pool_address(interest_mint), false),
+            AccountMeta::new(get_associated_token_address(holder, collateral_mint), false),
+            AccountMeta::new(pool_address(collateral_mint), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(pool_state_address(), false),
+            AccountMeta::new_readonly(receipt_address(holder, &deposit), false),
+        ],
+        AstrapeInstruction::RequestPartialWithdrawalEarly {
+            deposit_index,
+            amount,
+        },
+    )
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn extend_deposit(
+    holder: &Pubkey,
+    interest_mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    pyth_price_feed: &Pubkey,
+    user: &Pubkey,
+    deposit_index: u64,
+    amount: u64,
+    deposit_period: u64,
+) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(get_associated_token_address(holder, collateral_mint), false),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new(pool_address(collateral_mint), false),
+            AccountMeta::new(get_associated_token_address(holder, interest_mint), false),
+            AccountMeta::new(pool_address(interest_mint), false),
+            AccountMeta::new(treasury_address(), false),
+            AccountMeta::new_readonly(*pyth_price_feed, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(pool_state_address(), false),
+            AccountMeta::new_readonly(receipt_address(holder, &deposit), false),
+        ],
+        AstrapeInstruction::ExtendDeposit {
+            deposit_index,
+            amount,
+            deposit_period,
+        },
+    )
+}
+
+pub fn set_rollover(
+    holder: &Pubkey,
+    user: &Pubkey,
+    deposit_index: u64,
+    rollover: bool,
+) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new_readonly(receipt_address(holder, &deposit), false),
+        ],
+        AstrapeInstruction::SetRollover {
+            deposit_index,
+            rollover,
+        },
+    )
+}
+
+/// Anyone can send it, `holder` only holds the receipt and receives the upfront interest
+pub fn rollover(
+    holder: &Pubkey,
+    interest_mint: &Pubkey,
+    pyth_price_feed: &Pubkey,
+    user: &Pubkey,
+    deposit_index: u64,
+) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new_readonly(*holder, false),
+            AccountMeta::new(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new(get_associated_token_address(holder, interest_mint), false),
+            AccountMeta::new(pool_address(interest_mint), false),
+            AccountMeta::new(treasury_address(), false),
+            AccountMeta::new_readonly(*pyth_price_feed, false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(pool_state_address(), false),
+            AccountMeta::new_readonly(receipt_address(holder, &deposit), false),
+        ],
+        AstrapeInstruction::Rollover { deposit_index },
+    )
+}
+
+pub fn transfer_position(
+    holder: &Pubkey,
+    user: &Pubkey,
+    deposit_index: u64,
+    new_owner: &Pubkey,
+) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new_readonly(*new_owner, false),
+            AccountMeta::new_readonly(receipt_mint_address(&deposit), false),
+            AccountMeta::new(receipt_address(holder, &deposit), false),
+            AccountMeta::new(receipt_address(new_owner, &deposit), false),
+            AccountMeta::new_readonly(system_program::id(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new_readonly(spl_associated_token_account::id(), false),
+        ],
+        AstrapeInstruction::TransferPosition {
+            deposit_index,
+            new_owner: *new_owner,
+        },
+    )
+}
+
+pub fn admin_return_collateral(
+    admin: &Pubkey,
+    collateral_mint: &Pubkey,
+    strategy: u64,
+    principal: u64,
+    amount: u64,
+) -> Instruction {
+    build(
+        vec![
+            AccountMeta::new(*admin, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(get_associated_token_address(admin, collateral_mint), false),
+            AccountMeta::new(pool_address(collateral_mint), false),
+            AccountMeta::new(investment_ledger_address(), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        AstrapeInstruction::AdminReturnCollateral {
+            strategy,
+            principal,
+            amount,
+        },
+    )
+}
+
+/// `positions` are the (owner, user, deposit index) of each position, in the order of the
+/// withdrawal queue
+pub fn admin_prepare_withdrawal_batch(
+    admin: &Pubkey,
+    collateral_mint: &Pubkey,
+    positions: &[(Pubkey, Pubkey, u64)],
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(*admin, true),
+        AccountMeta::new_readonly(config_address(), false),
+        AccountMeta::new(get_associated_token_address(admin, collateral_mint), false),
+        AccountMeta::new(withdrawal_pool_address(), false),
+        AccountMeta::new_readonly(spl_token::id(), false),
+        AccountMeta::new(pool_state_address(), false),
+        AccountMeta::new(withdrawal_queue_address(), false),
+    ];
+    for (owner, user, deposit_index) in positions {
+        accounts.push(AccountMeta::new_readonly(*owner, false));
+        accounts.push(AccountMeta::new(
+            user_deposit_address(user, *deposit_index),
+            false,
+        ));
+    }
+    build(accounts, AstrapeInstruction::AdminPrepareWithdrawalBatch)
+}
+
+/// `queue_previous` is the deposit account of the position queued right before this one, and
+/// `None` when it is the oldest
+pub fn withdraw_overdue_collateral(
+    holder: &Pubkey,
+    collateral_mint: &Pubkey,
+    user: &Pubkey,
+    deposit_index: u64,
+    queue_previous: Option<&Pubkey>,
+) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new(get_associated_token_address(holder, collateral_mint), false),
+            AccountMeta::new(pool_address(collateral_mint), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+            AccountMeta::new(receipt_mint_address(&deposit), false),
+            AccountMeta::new(receipt_address(holder, &deposit), false),
+            AccountMeta::new(pool_state_address(), false),
+            AccountMeta::new(withdrawal_queue_address(), false),
+            AccountMeta::new(*queue_previous.unwrap_or(&deposit), false),
+        ],
+        AstrapeInstruction::WithdrawOverdueCollateral { deposit_index },
+    )
+}
+
+pub fn claim_late_payment(
+    holder: &Pubkey,
+    interest_mint: &Pubkey,
+    collateral_mint: &Pubkey,
+    user: &Pubkey,
+    deposit_index: u64,
+) -> Instruction {
+    let deposit = user_deposit_address(user, deposit_index);
+    build(
+        vec![
+            AccountMeta::new(*holder, true),
+            AccountMeta::new_readonly(config_address(), false),
+            AccountMeta::new_readonly(authority_address(), false),
+            AccountMeta::new(deposit, false),
+            AccountMeta::new_readonly(receipt_address(holder, &deposit), false),
+            AccountMeta::new(get_associated_token_address(holder, interest_mint), false),
+            AccountMeta::new(pool_address(interest_mint), false),
+            AccountMeta::new_readonly(pool_address(collateral_mint), false),
+            AccountMeta::new_readonly(spl_token::id(), false),
+        ],
+        AstrapeInstruction::ClaimLatePayment { deposit_index },
+    )
+}
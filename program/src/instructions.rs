@@ -1,9 +1,12 @@
 use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::{program_error::ProgramError, pubkey::Pubkey};
 
+use crate::state::{ConfigUpdate, DepositPeriod, MultisigAction, PauseFlags};
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub enum AstrapeInstruction {
-    /// Initialize the pool with configuration and create necessary PDAs
+    /// Initialize the pool with configuration and create necessary PDAs. The signer must be the
+    /// upgrade authority of the program and becomes the admin.
     ///
     /// Accounts expected:
     /// 0. `[signer]` Admin account
@@ -12,41 +15,39 @@ pub enum AstrapeInstruction {
     /// 3. `[writable]` Interest pool ATA account
     /// 4. `[writable]` Collateral pool ATA account
     /// 5. `[writable]` Withdrawal pool account
-    /// 6. `[]` Interest mint account
-    /// 7. `[]` Collateral mint account
-    /// 8. `[]` System program
-    /// 9. `[]` Token program
-    /// 10. `[]` Associated Token Account program
-    /// 11. `[]` Rent sysvar
+    /// 6. `[writable]` Treasury account
+    /// 7. `[]` Interest mint account
+    /// 8. `[]` Collateral mint account
+    /// 9. `[]` System program
+    /// 10. `[]` Token program
+    /// 11. `[]` Associated Token Account program
+    /// 12. `[]` Rent sysvar
+    /// 13. `[writable]` Pool state PDA account
+    /// 14. `[writable]` Investment ledger PDA account
+    /// 15. `[writable]` Withdrawal queue PDA account
+    /// 16. `[]` Program data account of the program
     Initialize {
         interest_mint: Pubkey,
         collateral_mint: Pubkey,
-        base_interest_rate: u64,
+        pyth_feed_id: [u8; 32],
         pyth_price_max_age: u64,
         min_commission_rate: u64,
         max_commission_rate: u64,
         min_deposit_amount: u64,
         max_deposit_amount: u64,
-        deposit_periods: Vec<u64>,
+        deposit_periods: Vec<DepositPeriod>,
     },
 
-    /// Update pool configuration parameters
+    /// Update pool configuration parameters, only while multisig is not enabled
     ///
     /// Accounts expected:
     /// 0. `[signer]` Admin account
     /// 1. `[writable]` Config PDA account
-    AdminUpdateConfig {
-        param: u8,
-        base_interest_rate: Option<u64>,
-        pyth_price_max_age: Option<u64>,
-        min_commission_rate: Option<u64>,
-        max_commission_rate: Option<u64>,
-        min_deposit_amount: Option<u64>,
-        max_deposit_amount: Option<u64>,
-        deposit_periods: Option<Vec<u64>>,
-    },
+    /// 2. `[]` Pool state PDA account
+    AdminUpdateConfig { update: ConfigUpdate },
 
-    /// Admin withdraws collateral for investment
+    /// Admin withdraws `amount` of collateral for investment in `strategy`, only while
+    /// multisig is not enabled. The withdrawal is recorded in the investment ledger.
     ///
     /// Accounts expected:
     /// 0. `[signer]` Admin account
@@ -57,9 +58,11 @@ pub enum AstrapeInstruction {
     /// 5. `[]` System program
     /// 6. `[]` Token program
     /// 7. `[]` Associated Token Account program
-    AdminWithdrawCollateralForInvestment,
+    /// 8. `[writable]` Investment ledger PDA account
+    AdminWithdrawCollateralForInvestment { amount: u64, strategy: u64 },
 
-    /// Admin prepares withdrawal by depositing collateral
+    /// Admin prepares withdrawal by depositing collateral. Withdrawals are prepared in the order
+    /// of the withdrawal queue, and the position is removed from it.
     ///
     /// Accounts expected:
     /// 0. `[signer]` Admin account
@@ -69,7 +72,9 @@ pub enum AstrapeInstruction {
     /// 4. `[]` User account
     /// 5. `[writable]` User deposit account
     /// 6. `[]` Token program
-    AdminPrepareWithdrawal,
+    /// 7. `[writable]` Pool state PDA account
+    /// 8. `[writable]` Withdrawal queue PDA account
+    AdminPrepareWithdrawal { deposit_index: u64 },
 
     /// Admin deposits interest tokens to the pool
     ///
@@ -84,7 +89,7 @@ pub enum AstrapeInstruction {
     /// 7. `[]` Associated Token Account program
     AdminDepositInterest { amount: u64 },
 
-    /// Admin withdraws interest tokens from the pool
+    /// Admin withdraws interest tokens from the pool, only while multisig is not enabled
     ///
     /// Accounts expected:
     /// 0. `[signer]` Admin account
@@ -97,56 +102,350 @@ pub enum AstrapeInstruction {
     /// 7. `[]` Associated Token Account program
     AdminWithdrawInterest { amount: u64 },
 
-    /// Deposit collateral tokens into the pool
+    /// Deposit collateral tokens into the pool. The commission part of the interest
+    /// goes from the interest pool to the treasury, and the user gets the receipt token
+    /// of the new position.
     ///
     /// Accounts expected:
     /// 0. `[signer]` User account
-    /// 1. `[]` Config PDA account
+    /// 1. `[writable]` Config PDA account
     /// 2. `[]` Authority PDA account
     /// 3. `[writable]` User's collateral token account
-    /// 4. `[writable]` User's deposit account
-    /// 5. `[writable]` Pool's collateral token account
-    /// 6. `[writable]` User's interest token account
-    /// 7. `[writable]` Pool's interest token account
-    /// 8. `[]` Pyth price feed account
-    /// 9. `[]` System program
-    /// 10. `[]` Token program
+    /// 4. `[writable]` User's deposit counter account
+    /// 5. `[writable]` User's deposit account for the next deposit index
+    /// 6. `[writable]` Pool's collateral token account
+    /// 7. `[writable]` User's interest token account
+    /// 8. `[writable]` Pool's interest token account
+    /// 9. `[writable]` Treasury account
+    /// 10. `[]` Pyth price feed account
+    /// 11. `[]` System program
+    /// 12. `[]` Token program
+    /// 13. `[writable]` Receipt mint PDA account of the new position
+    /// 14. `[writable]` User's receipt token account
+    /// 15. `[]` Associated Token Account program
+    /// 16. `[writable]` Pool state PDA account
     DepositCollateral {
         amount: u64,
         deposit_period: u64,
         commission_rate: u64,
+        rollover: bool,
     },
 
-    /// Request early withdrawal of collateral (before unlock time)
+    /// Request early withdrawal of collateral (before unlock time). The signer must hold the
+    /// receipt token of the position. The position joins the withdrawal queue.
     ///
     /// Accounts expected:
-    /// 0. `[signer]` User account
+    /// 0. `[signer]` Receipt holder account
     /// 1. `[]` Config PDA account
     /// 2. `[]` Authority PDA account
     /// 3. `[writable]` User's deposit account
-    /// 4. `[writable]` User's interest token account
+    /// 4. `[writable]` Receipt holder's interest token account
     /// 5. `[writable]` Pool's interest token account
     /// 6. `[]` Token program
-    RequestWithdrawalEarly,
+    /// 7. `[writable]` Pool state PDA account
+    /// 8. `[]` Receipt holder's receipt token account
+    /// 9. `[writable]` Withdrawal queue PDA account
+    /// 10. `[writable]` Newest position's deposit account in the withdrawal queue, if any
+    RequestWithdrawalEarly { deposit_index: u64 },
 
-    /// Request withdrawal of collateral (after unlock time)
+    /// Request withdrawal of collateral (after unlock time). The signer must hold the
+    /// receipt token of the position. The position joins the withdrawal queue.
     ///
     /// Accounts expected:
-    /// 0. `[signer]` User account
-    /// 1. `[writable]` User's deposit account
-    RequestWithdrawal,
+    /// 0. `[signer]` Receipt holder account
+    /// 1. `[]` Config PDA account
+    /// 2. `[writable]` User's deposit account
+    /// 3. `[]` Receipt holder's receipt token account
+    /// 4. `[writable]` Pool state PDA account
+    /// 5. `[writable]` Withdrawal queue PDA account
+    /// 6. `[writable]` Newest position's deposit account in the withdrawal queue, if any
+    RequestWithdrawal { deposit_index: u64 },
 
-    /// Withdraw collateral after admin preparation
+    /// Withdraw collateral after admin preparation. The receipt token of the position is
+    /// burned from the signer.
     ///
     /// Accounts expected:
-    /// 0. `[signer]` User account
+    /// 0. `[signer]` Receipt holder account
     /// 1. `[]` Config PDA account
     /// 2. `[]` Authority PDA account
     /// 3. `[writable]` User's deposit account
-    /// 4. `[writable]` User's collateral token account
+    /// 4. `[writable]` Receipt holder's collateral token account
     /// 5. `[writable]` Withdrawal pool account
     /// 6. `[]` Token program
-    WithdrawCollateral,
+    /// 7. `[writable]` Receipt mint PDA account
+    /// 8. `[writable]` Receipt holder's receipt token account
+    /// 9. `[writable]` Pool state PDA account
+    WithdrawCollateral { deposit_index: u64 },
+
+    /// Close a completed deposit account and refund its rent to the depositor, who opened it.
+    /// The owner, which is the receipt holder who redeemed it, also gets back the rent of their
+    /// emptied receipt token account.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Owner account
+    /// 1. `[writable]` Deposit account
+    /// 2. `[writable]` Depositor account
+    /// 3. `[writable]` Owner's receipt token account
+    /// 4. `[]` Token program
+    /// 5. `[writable]` Pool state PDA account
+    CloseDeposit { deposit_index: u64 },
+
+    /// Propose a new admin, who has to accept the role to take over
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Admin account
+    /// 1. `[writable]` Config PDA account
+    ProposeAdmin { new_admin: Pubkey },
+
+    /// Accept the admin role proposed by the current admin
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Pending admin account
+    /// 1. `[writable]` Config PDA account
+    AcceptAdmin,
+
+    /// Create the multisig. From then on collateral withdrawals for investment, interest
+    /// withdrawals and config updates go through multisig proposals.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Admin account
+    /// 1. `[writable]` Config PDA account
+    /// 2. `[writable]` Multisig PDA account
+    /// 3. `[]` System program
+    AdminInitializeMultisig { signers: Vec<Pubkey>, threshold: u8 },
+
+    /// Propose a multisig action, approved by the proposer
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Multisig signer account
+    /// 1. `[writable]` Multisig PDA account
+    /// 2. `[writable]` Proposal PDA account for the next proposal index
+    /// 3. `[]` System program
+    CreateProposal { action: MultisigAction },
+
+    /// Approve a multisig proposal
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Multisig signer account
+    /// 1. `[]` Multisig PDA account
+    /// 2. `[writable]` Proposal PDA account
+    ApproveProposal { proposal_index: u64 },
+
+    /// Execute a multisig proposal which reached the threshold
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Multisig signer account
+    /// 1. `[writable]` Multisig PDA account
+    /// 2. `[writable]` Proposal PDA account
+    /// 3. `[writable]` Config PDA account
+    ///
+    /// For `WithdrawCollateralForInvestment`:
+    /// 4. `[]` Authority PDA account
+    /// 5. `[writable]` Destination collateral token account
+    /// 6. `[writable]` Pool's collateral token account
+    /// 7. `[]` Token program
+    /// 8. `[writable]` Investment ledger PDA account
+    ///
+    /// For `WithdrawInterest`:
+    /// 4. `[]` Authority PDA account
+    /// 5. `[writable]` Destination interest token account
+    /// 6. `[writable]` Pool's interest token account
+    /// 7. `[]` Token program
+    ///
+    /// For `WithdrawTreasury`:
+    /// 4. `[]` Authority PDA account
+    /// 5. `[writable]` Destination interest token account
+    /// 6. `[writable]` Treasury account
+    /// 7. `[]` Token program
+    ///
+    /// For `UpdateConfig`:
+    /// 4. `[]` Pool state PDA account
+    ///
+    /// For `ReturnCollateral`, sent by the multisig signer which owns the source account:
+    /// 4. `[]` Authority PDA account
+    /// 5. `[writable]` Source collateral token account
+    /// 6. `[writable]` Pool's collateral token account
+    /// 7. `[]` Token program
+    /// 8. `[writable]` Investment ledger PDA account
+    ExecuteProposal { proposal_index: u64 },
+
+    /// Pause or resume instruction categories
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Admin account
+    /// 1. `[writable]` Config PDA account
+    AdminSetPause { paused: PauseFlags },
+
+    /// Admin withdraws collected commission from the treasury, only while multisig is
+    /// not enabled
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Admin account
+    /// 1. `[]` Config PDA account
+    /// 2. `[]` Authority PDA account
+    /// 3. `[writable]` Admin's interest token account
+    /// 4. `[writable]` Treasury account
+    /// 5. `[]` Token program
+    AdminWithdrawTreasury { amount: u64 },
+
+    /// Withdraw part of a deposit early. The position keeps its lock with `amount` less
+    /// collateral, which is paid out of the collateral pool right away, and the interest of
+    /// the withdrawn part is refunded. The signer must hold the receipt token of the position,
+    /// which stays with it.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Receipt holder account
+    /// 1. `[]` Config PDA account
+    /// 2. `[]` Authority PDA account
+    /// 3. `[writable]` User's deposit account
+    /// 4. `[writable]` Receipt holder's interest token account
+    /// 5. `[writable]` Pool's interest token account
+    /// 6. `[writable]` Receipt holder's collateral token account
+    /// 7. `[writable]` Collateral pool ATA account
+    /// 8. `[]` Token program
+    /// 9. `[writable]` Pool state PDA account
+    /// 10. `[]` Receipt holder's receipt token account
+    RequestPartialWithdrawalEarly { deposit_index: u64, amount: u64 },
+
+    /// Add `amount` of collateral to an active deposit and/or lock it until `deposit_period`
+    /// slots from now. Upfront interest is paid for the increment only. The signer must hold
+    /// the receipt token of the position.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Receipt holder account
+    /// 1. `[writable]` Config PDA account
+    /// 2. `[]` Authority PDA account
+    /// 3. `[writable]` Receipt holder's collateral token account
+    /// 4. `[writable]` User's deposit account
+    /// 5. `[writable]` Pool's collateral token account
+    /// 6. `[writable]` Receipt holder's interest token account
+    /// 7. `[writable]` Pool's interest token account
+    /// 8. `[writable]` Treasury account
+    /// 9. `[]` Pyth price feed account
+    /// 10. `[]` Token program
+    /// 11. `[writable]` Pool state PDA account
+    /// 12. `[]` Receipt holder's receipt token account
+    ExtendDeposit {
+        deposit_index: u64,
+        amount: u64,
+        deposit_period: u64,
+    },
+
+    /// Opt a deposit in or out of automatic rollover at maturity. The signer must hold the
+    /// receipt token of the position.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Receipt holder account
+    /// 1. `[writable]` User's deposit account
+    /// 2. `[]` Receipt holder's receipt token account
+    SetRollover { deposit_index: u64, rollover: bool },
+
+    /// Re-lock a matured deposit which opted in to rollover for the same tenor, at the
+    /// current rate and price. Anyone can send it, and the interest goes to the receipt holder.
+    ///
+    /// Accounts expected:
+    /// 0. `[]` Receipt holder account
+    /// 1. `[writable]` Config PDA account
+    /// 2. `[]` Authority PDA account
+    /// 3. `[writable]` User's deposit account
+    /// 4. `[writable]` Receipt holder's interest token account
+    /// 5. `[writable]` Pool's interest token account
+    /// 6. `[writable]` Treasury account
+    /// 7. `[]` Pyth price feed account
+    /// 8. `[]` Token program
+    /// 9. `[writable]` Pool state PDA account
+    /// 10. `[]` Receipt holder's receipt token account
+    Rollover { deposit_index: u64 },
+
+    /// Transfer a deposit position to `new_owner`. The position keeps its account, which
+    /// stays derived from the original depositor. The signer must hold the receipt token, which
+    /// moves to the new owner.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer, writable]` Receipt holder account
+    /// 1. `[writable]` Deposit account
+    /// 2. `[]` New owner account
+    /// 3. `[]` Receipt mint PDA account
+    /// 4. `[writable]` Receipt holder's receipt token account
+    /// 5. `[writable]` New owner's receipt token account
+    /// 6. `[]` System program
+    /// 7. `[]` Token program
+    /// 8. `[]` Associated Token Account program
+    TransferPosition {
+        deposit_index: u64,
+        new_owner: Pubkey,
+    },
+
+    /// Admin returns `amount` of collateral to the pool from `strategy`, settling `principal` of
+    /// the collateral deployed in it. The difference is recorded as realized yield or loss in the
+    /// investment ledger. Only while multisig is not enabled.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Admin account
+    /// 1. `[]` Config PDA account
+    /// 2. `[]` Authority PDA account
+    /// 3. `[writable]` Admin's collateral token account
+    /// 4. `[writable]` Pool's collateral token account
+    /// 5. `[writable]` Investment ledger PDA account
+    /// 6. `[]` Token program
+    AdminReturnCollateral {
+        strategy: u64,
+        principal: u64,
+        amount: u64,
+    },
+
+    /// Admin prepares the withdrawal of several deposit positions with a single transfer of
+    /// their summed collateral. The positions must be passed in the order of the withdrawal
+    /// queue.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Admin account
+    /// 1. `[]` Config PDA account
+    /// 2. `[writable]` Admin's collateral token account
+    /// 3. `[writable]` Withdrawal pool account
+    /// 4. `[]` Token program
+    /// 5. `[writable]` Pool state PDA account
+    /// 6. `[writable]` Withdrawal queue PDA account
+    /// 7. `[]` User account of the first position
+    /// 8. `[writable]` User deposit account of the first position
+    /// 9. The same pair of accounts for each following position
+    AdminPrepareWithdrawalBatch,
+
+    /// Withdraw the collateral of a requested withdrawal directly from the collateral pool once
+    /// the withdrawal SLA has passed without the admin preparing it. The signer must hold the
+    /// receipt token of the position, which is burned.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Receipt holder account
+    /// 1. `[]` Config PDA account
+    /// 2. `[]` Authority PDA account
+    /// 3. `[writable]` User's deposit account
+    /// 4. `[writable]` Receipt holder's collateral token account
+    /// 5. `[writable]` Pool's collateral token account
+    /// 6. `[]` Token program
+    /// 7. `[writable]` Receipt mint PDA account
+    /// 8. `[writable]` Receipt holder's receipt token account
+    /// 9. `[writable]` Pool state PDA account
+    /// 10. `[writable]` Withdrawal queue PDA account
+    /// 11. `[writable]` Deposit account of the position queued right before this one, if any
+    WithdrawOverdueCollateral { deposit_index: u64 },
+
+    /// Claim the late payment compensation of a requested withdrawal which is past the
+    /// withdrawal SLA while the collateral pool cannot pay it: the late payment rate of the
+    /// interest of the position. The deadline restarts from the current slot. The signer must
+    /// hold the receipt token of the position.
+    ///
+    /// Accounts expected:
+    /// 0. `[signer]` Receipt holder account
+    /// 1. `[]` Config PDA account
+    /// 2. `[]` Authority PDA account
+    /// 3. `[writable]` User's deposit account
+    /// 4. `[]` Receipt holder's receipt token account
+    /// 5. `[writable]` Receipt holder's interest token account
+    /// 6. `[writable]` Pool's interest token account
+    /// 7. `[]` Pool's collateral token account
+    /// 8. `[]` Token program
+    ClaimLatePayment { deposit_index: u64 },
 }
 
 impl AstrapeInstruction {
@@ -155,129 +454,6 @@ impl AstrapeInstruction {
         Ok(instruction)
     }
     pub fn pack(&self) -> Result<Vec<u8>, ProgramError> {
-        let mut buffer = vec![];
-        match self {
-            Self::Initialize {
-                interest_mint,
-                collateral_mint,
-                base_interest_rate,
-                pyth_price_max_age,
-                min_commission_rate,
-                max_commission_rate,
-                min_deposit_amount,
-                max_deposit_amount,
-                deposit_periods,
-            } => {
-                buffer.push(0);
-                buffer.extend_from_slice(&interest_mint.to_bytes());
-                buffer.extend_from_slice(&collateral_mint.to_bytes());
-                buffer.extend_from_slice(&base_interest_rate.to_le_bytes());
-                buffer.extend_from_slice(&pyth_price_max_age.to_le_bytes());
-                buffer.extend_from_slice(&min_commission_rate.to_le_bytes());
-                buffer.extend_from_slice(&max_commission_rate.to_le_bytes());
-                buffer.extend_from_slice(&min_deposit_amount.to_le_bytes());
-                buffer.extend_from_slice(&max_deposit_amount.to_le_bytes());
-                buffer.extend_from_slice(
-                    &deposit_periods
-                        .iter()
-                        .flat_map(|&x| x.to_le_bytes())
-                        .collect::<Vec<u8>>(),
-                );
-            }
-            Self::AdminUpdateConfig {
-                param,
-                base_interest_rate,
-                pyth_price_max_age,
-                min_commission_rate,
-                max_commission_rate,
-                min_deposit_amount,
-                max_deposit_amount,
-                deposit_periods,
-            } => {
-                buffer.push(1);
-                buffer.push(*param);
-                if let Some(rate) = base_interest_rate {
-                    buffer.push(1);
-                    buffer.extend_from_slice(&rate.to_le_bytes());
-                } else {
-                    buffer.push(0);
-                }
-                if let Some(pyth_price_max_age) = pyth_price_max_age {
-                    buffer.push(1);
-                    buffer.extend_from_slice(&pyth_price_max_age.to_le_bytes());
-                } else {
-                    buffer.push(0);
-                }
-                if let Some(min_commission_rate) = min_commission_rate {
-                    buffer.push(1);
-                    buffer.extend_from_slice(&min_commission_rate.to_le_bytes());
-                } else {
-                    buffer.push(0);
-                }
-                if let Some(max_commission_rate) = max_commission_rate {
-                    buffer.push(1);
-                    buffer.extend_from_slice(&max_commission_rate.to_le_bytes());
-                } else {
-                    buffer.push(0);
-                }
-                if let Some(min_deposit_amount) = min_deposit_amount {
-                    buffer.push(1);
-                    buffer.extend_from_slice(&min_deposit_amount.to_le_bytes());
-                } else {
-                    buffer.push(0);
-                }
-                if let Some(max_deposit_amount) = max_deposit_amount {
-                    buffer.push(1);
-                    buffer.extend_from_slice(&max_deposit_amount.to_le_bytes());
-                } else {
-                    buffer.push(0);
-                }
-                if let Some(deposit_periods) = deposit_periods {
-                    buffer.push(1);
-                    buffer.extend_from_slice(
-                        &deposit_periods
-                            .iter()
-                            .flat_map(|&x| x.to_le_bytes())
-                            .collect::<Vec<u8>>(),
-                    );
-                } else {
-                    buffer.push(0);
-                }
-            }
-            Self::AdminWithdrawCollateralForInvestment => {
-                buffer.push(2);
-            }
-            Self::AdminPrepareWithdrawal => {
-                buffer.push(3);
-            }
-            Self::AdminDepositInterest { amount } => {
-                buffer.push(4);
-                buffer.extend_from_slice(&amount.to_le_bytes());
-            }
-            Self::AdminWithdrawInterest { amount } => {
-                buffer.push(5);
-                buffer.extend_from_slice(&amount.to_le_bytes());
-            }
-            Self::DepositCollateral {
-                amount,
-                deposit_period,
-                commission_rate: comminsion_rate,
-            } => {
-                buffer.push(6);
-                buffer.extend_from_slice(&amount.to_le_bytes());
-                buffer.extend_from_slice(&deposit_period.to_le_bytes());
-                buffer.extend_from_slice(&comminsion_rate.to_le_bytes());
-            }
-            Self::RequestWithdrawalEarly => {
-                buffer.push(7);
-            }
-            Self::RequestWithdrawal => {
-                buffer.push(8);
-            }
-            Self::WithdrawCollateral => {
-                buffer.push(9);
-            }
-        }
-        Ok(buffer)
+        Ok(self.try_to_vec()?)
     }
 }
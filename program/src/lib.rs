@@ -1,5 +1,7 @@
 pub mod entrypoint;
 pub mod errors;
+pub mod events;
+pub mod instruction;
 pub mod instructions;
 pub mod processor;
 pub mod state;
@@ -1,55 +1,68 @@
 use borsh::{BorshDeserialize, BorshSerialize};
-use pyth_solana_receiver_sdk::price_update::{get_feed_id_from_hex, PriceUpdateV2};
+use pyth_solana_receiver_sdk::price_update::PriceUpdateV2;
 use solana_program::{
     account_info::{next_account_info, AccountInfo},
+    bpf_loader_upgradeable::{self, UpgradeableLoaderState},
     clock::Clock,
     entrypoint::ProgramResult,
     msg,
     program::{invoke, invoke_signed},
+    program_error::ProgramError,
     program_pack::Pack,
+    program_utils::limited_deserialize,
     pubkey::Pubkey,
     system_instruction,
     sysvar::{rent::Rent, Sysvar},
 };
 use spl_associated_token_account::instruction as ata_instruction;
-use spl_token::{instruction as token_instruction, state::Account as TokenAccount};
+use spl_token::{
+    instruction as token_instruction,
+    state::{Account as TokenAccount, Mint},
+};
 
 use crate::{
     errors::{AstrapeError, AstrapeResult},
+    events::{
+        AdminChangedEvent, AdminFundsEvent, AdminProposedEvent, AstrapeEvent, ConfigUpdatedEvent,
+        ConfigValue, DepositClosedEvent, DepositEvent, FundMovement, LatePaymentEvent,
+        MultisigUpdatedEvent, PauseUpdatedEvent, PositionTransferredEvent, RefundEvent,
+        RelockEvent, RolloverUpdatedEvent, WithdrawalCompletedEvent, WithdrawalPreparedEvent,
+        WithdrawalRequestedEvent,
+    },
     instructions::AstrapeInstruction,
-    state::{AstrapeConfig, UserDeposit, UserDepositState},
+    state::{
+        AstrapeConfig, ConfigUpdate, DepositPeriod, EarlyExitPolicy, InvestmentLedger, Multisig,
+        MultisigAction, MultisigProposal, PauseFlags, PoolState, QueuedWithdrawal, UserDeposit,
+        UserDepositCounter, UserDepositState, WithdrawalQueue, MAX_DEPOSIT_PERIODS,
+        MAX_MULTISIG_SIGNERS, MAX_REFUND_STEPS,
+    },
 };
 
 // PDA seeds
 pub const CONFIG_SEED: &[u8] = b"pool_config";
 pub const AUTHORITY_SEED: &[u8] = b"authority";
 pub const WITHDRAWAL_POOL_SEED: &[u8] = b"withdrawal_pool";
+pub const TREASURY_SEED: &[u8] = b"treasury";
+pub const RECEIPT_MINT_SEED: &[u8] = b"receipt";
+pub const POOL_STATE_SEED: &[u8] = b"pool_state";
+pub const INVESTMENT_LEDGER_SEED: &[u8] = b"investment_ledger";
+pub const WITHDRAWAL_QUEUE_SEED: &[u8] = b"withdrawal_queue";
+pub const USER_DEPOSIT_SEED: &[u8] = b"deposit";
+pub const USER_DEPOSIT_COUNTER_SEED: &[u8] = b"deposit_counter";
+pub const MULTISIG_SEED: &[u8] = b"multisig";
+pub const MULTISIG_PROPOSAL_SEED: &[u8] = b"proposal";
 
 pub const MS_PER_SLOT: u64 = 440;
-pub const SLOTS_PER_SEC: f64 = 1000.0 / MS_PER_SLOT as f64;
-pub const SLOTS_PER_MONTH: f64 = SLOTS_PER_SEC * 30.0 * 24.0 * 60.0 * 60.0;
-pub const SLOTS_PER_YEAR: f64 = SLOTS_PER_SEC * 365.0 * 24.0 * 60.0 * 60.0;
+pub const MS_PER_YEAR: u64 = 365 * 24 * 60 * 60 * 1000;
 
-const PYTH_PRICE_UPDATE_DISCRIMINATOR: &'static [u8] = &[34, 241, 35, 99, 157, 126, 244, 205];
+// Interest and commission rates are fixed decimals at the first decimal place
+pub const RATE_DENOMINATOR: u64 = 1000;
+// Early exit penalties and refund steps are in basis points
+pub const BPS_DENOMINATOR: u64 = 10_000;
+// Oracle prices are normalized to fixed decimals with this many decimal places
+pub const PRICE_DECIMALS: u32 = 8;
 
-#[cfg(feature = "testnet")]
-pub mod config_feature {
-    pub mod admin {
-        solana_program::declare_id!("EjYMbwtvCjAdMB2RPu45QKPBEE5gTPSJBktzTro5VigV");
-    }
-}
-#[cfg(feature = "devnet")]
-pub mod config_feature {
-    pub mod admin {
-        solana_program::declare_id!("EjYMbwtvCjAdMB2RPu45QKPBEE5gTPSJBktzTro5VigV");
-    }
-}
-#[cfg(not(any(feature = "testnet", feature = "devnet")))]
-pub mod config_feature {
-    pub mod admin {
-        solana_program::declare_id!("EjYMbwtvCjAdMB2RPu45QKPBEE5gTPSJBktzTro5VigV");
-    }
-}
+const PYTH_PRICE_UPDATE_DISCRIMINATOR: &[u8] = &[34, 241, 35, 99, 157, 126, 244, 205];
 
 pub struct Processor;
 
@@ -61,11 +74,13 @@ impl Processor {
     ) -> ProgramResult {
         let instruction = AstrapeInstruction::unpack(instruction_data)?;
 
+        Self::check_not_paused(program_id, accounts, &instruction)?;
+
         match instruction {
             AstrapeInstruction::Initialize {
                 interest_mint,
                 collateral_mint,
-                base_interest_rate,
+                pyth_feed_id,
                 pyth_price_max_age,
                 min_commission_rate,
                 max_commission_rate,
@@ -79,7 +94,7 @@ impl Processor {
                     accounts,
                     interest_mint,
                     collateral_mint,
-                    base_interest_rate,
+                    pyth_feed_id,
                     pyth_price_max_age,
                     min_commission_rate,
                     max_commission_rate,
@@ -88,37 +103,19 @@ impl Processor {
                     deposit_periods,
                 )
             }
-            AstrapeInstruction::AdminUpdateConfig {
-                param,
-                base_interest_rate,
-                pyth_price_max_age,
-                min_commission_rate,
-                max_commission_rate,
-                min_deposit_amount,
-                max_deposit_amount,
-                deposit_periods,
-            } => {
+            AstrapeInstruction::AdminUpdateConfig { update } => {
                 msg!("Instruction: AdminUpdateConfig");
-                Self::process_update_config(
-                    program_id,
-                    accounts,
-                    param,
-                    base_interest_rate,
-                    pyth_price_max_age,
-                    min_commission_rate,
-                    max_commission_rate,
-                    min_deposit_amount,
-                    max_deposit_amount,
-                    deposit_periods,
-                )
+                Self::process_update_config(program_id, accounts, update)
             }
-            AstrapeInstruction::AdminWithdrawCollateralForInvestment => {
+            AstrapeInstruction::AdminWithdrawCollateralForInvestment { amount, strategy } => {
                 msg!("Instruction: AdminWithdrawCollateralForInvestment");
-                Self::process_admin_withdraw_collateral_for_investment(program_id, accounts)
+                Self::process_admin_withdraw_collateral_for_investment(
+                    program_id, accounts, amount, strategy,
+                )
             }
-            AstrapeInstruction::AdminPrepareWithdrawal => {
+            AstrapeInstruction::AdminPrepareWithdrawal { deposit_index } => {
                 msg!("Instruction: AdminPrepareWithdrawal");
-                Self::process_admin_prepare_withdrawal(program_id, accounts)
+                Self::process_admin_prepare_withdrawal(program_id, accounts, deposit_index)
             }
             AstrapeInstruction::AdminDepositInterest { amount } => {
                 msg!("Instruction: AdminDepositInterest");
@@ -132,6 +129,7 @@ impl Processor {
                 amount,
                 deposit_period,
                 commission_rate,
+                rollover,
             } => {
                 msg!("Instruction: DepositCollateral");
                 Self::process_deposit_collateral(
@@ -140,19 +138,122 @@ impl Processor {
                     amount,
                     deposit_period,
                     commission_rate,
+                    rollover,
                 )
             }
-            AstrapeInstruction::RequestWithdrawalEarly => {
+            AstrapeInstruction::RequestWithdrawalEarly { deposit_index } => {
                 msg!("Instruction: RequestWithdrawalEarly");
-                Self::process_request_withdrawal_early(program_id, accounts)
+                Self::process_request_withdrawal_early(program_id, accounts, deposit_index)
             }
-            AstrapeInstruction::RequestWithdrawal => {
+            AstrapeInstruction::RequestWithdrawal { deposit_index } => {
                 msg!("Instruction: RequestWithdrawal");
-                Self::process_request_withdrawal(program_id, accounts)
+                Self::process_request_withdrawal(program_id, accounts, deposit_index)
             }
-            AstrapeInstruction::WithdrawCollateral => {
+            AstrapeInstruction::WithdrawCollateral { deposit_index } => {
                 msg!("Instruction: WithdrawCollateral");
-                Self::process_withdraw_collateral(program_id, accounts)
+                Self::process_withdraw_collateral(program_id, accounts, deposit_index)
+            }
+            AstrapeInstruction::CloseDeposit { deposit_index } => {
+                msg!("Instruction: CloseDeposit");
+                Self::process_close_deposit(program_id, accounts, deposit_index)
+            }
+            AstrapeInstruction::ProposeAdmin { new_admin } => {
+                msg!("Instruction: ProposeAdmin");
+                Self::process_propose_admin(program_id, accounts, new_admin)
+            }
+            AstrapeInstruction::AcceptAdmin => {
+                msg!("Instruction: AcceptAdmin");
+                Self::process_accept_admin(program_id, accounts)
+            }
+            AstrapeInstruction::AdminInitializeMultisig { signers, threshold } => {
+                msg!("Instruction: AdminInitializeMultisig");
+                Self::process_admin_initialize_multisig(program_id, accounts, signers, threshold)
+            }
+            AstrapeInstruction::CreateProposal { action } => {
+                msg!("Instruction: CreateProposal");
+                Self::process_create_proposal(program_id, accounts, action)
+            }
+            AstrapeInstruction::ApproveProposal { proposal_index } => {
+                msg!("Instruction: ApproveProposal");
+                Self::process_approve_proposal(program_id, accounts, proposal_index)
+            }
+            AstrapeInstruction::ExecuteProposal { proposal_index } => {
+                msg!("Instruction: ExecuteProposal");
+                Self::process_execute_proposal(program_id, accounts, proposal_index)
+            }
+            AstrapeInstruction::AdminSetPause { paused } => {
+                msg!("Instruction: AdminSetPause");
+                Self::process_admin_set_pause(program_id, accounts, paused)
+            }
+            AstrapeInstruction::AdminWithdrawTreasury { amount } => {
+                msg!("Instruction: AdminWithdrawTreasury");
+                Self::process_admin_withdraw_treasury(program_id, accounts, amount)
+            }
+            AstrapeInstruction::RequestPartialWithdrawalEarly {
+                deposit_index,
+                amount,
+            } => {
+                msg!("Instruction: RequestPartialWithdrawalEarly");
+                Self::process_request_partial_withdrawal_early(
+                    program_id,
+                    accounts,
+                    deposit_index,
+                    amount,
+                )
+            }
+            AstrapeInstruction::ExtendDeposit {
+                deposit_index,
+                amount,
+                deposit_period,
+            } => {
+                msg!("Instruction: ExtendDeposit");
+                Self::process_extend_deposit(
+                    program_id,
+                    accounts,
+                    deposit_index,
+                    amount,
+                    deposit_period,
+                )
+            }
+            AstrapeInstruction::SetRollover {
+                deposit_index,
+                rollover,
+            } => {
+                msg!("Instruction: SetRollover");
+                Self::process_set_rollover(program_id, accounts, deposit_index, rollover)
+            }
+            AstrapeInstruction::Rollover { deposit_index } => {
+                msg!("Instruction: Rollover");
+                Self::process_rollover(program_id, accounts, deposit_index)
+            }
+            AstrapeInstruction::TransferPosition {
+                deposit_index,
+                new_owner,
+            } => {
+                msg!("Instruction: TransferPosition");
+                Self::process_transfer_position(program_id, accounts, deposit_index, new_owner)
+            }
+            AstrapeInstruction::AdminReturnCollateral {
+                strategy,
+                principal,
+                amount,
+            } => {
+                msg!("Instruction: AdminReturnCollateral");
+                Self::process_admin_return_collateral(
+                    program_id, accounts, strategy, principal, amount,
+                )
+            }
+            AstrapeInstruction::AdminPrepareWithdrawalBatch => {
+                msg!("Instruction: AdminPrepareWithdrawalBatch");
+                Self::process_admin_prepare_withdrawal_batch(program_id, accounts)
+            }
+            AstrapeInstruction::WithdrawOverdueCollateral { deposit_index } => {
+                msg!("Instruction: WithdrawOverdueCollateral");
+                Self::process_withdraw_overdue_collateral(program_id, accounts, deposit_index)
+            }
+            AstrapeInstruction::ClaimLatePayment { deposit_index } => {
+                msg!("Instruction: ClaimLatePayment");
+                Self::process_claim_late_payment(program_id, accounts, deposit_index)
             }
         }
     }
@@ -171,486 +272,3313 @@ impl Processor {
                 expected_pda,
                 pda
             );
-            return Err(AstrapeError::InvalidPDA(1).into());
+            return Err(AstrapeError::InvalidPDA(1));
         }
         Ok(bump)
     }
 
-    fn check_ata(
-        name: &str,
-        ata: &Pubkey,
-        wallet: &Pubkey,
-        mint: &Pubkey,
-    ) -> Result<(), AstrapeError> {
-        let expected_ata = spl_associated_token_account::get_associated_token_address(wallet, mint);
-        if expected_ata != *ata {
+    fn check_admin(admin_info: &AccountInfo, config: &AstrapeConfig) -> Result<(), AstrapeError> {
+        if !admin_info.is_signer {
+            msg!("Admin must be a signer");
+            return Err(AstrapeError::SignerRequired);
+        }
+        if config.admin != *admin_info.key {
             msg!(
-                "Invalid ATA for {}: expected={}, actual={}",
-                name,
-                expected_ata,
-                ata
+                "Invalid admin: expected={}, actual={}",
+                config.admin,
+                admin_info.key
             );
-            return Err(AstrapeError::InvalidPDA(1).into());
+            return Err(AstrapeError::InvalidAdmin(0));
         }
         Ok(())
     }
 
-    fn deserialize_price_update(
-        pyth_price_feed_account: &AccountInfo,
-    ) -> Result<PriceUpdateV2, AstrapeError> {
-        let buf = pyth_price_feed_account.data.borrow();
-        if buf.len() < PYTH_PRICE_UPDATE_DISCRIMINATOR.len() {
-            return Err(AstrapeError::InvalidPythPriceFeed);
-        }
-        let given_disc = &buf[..PYTH_PRICE_UPDATE_DISCRIMINATOR.len()];
-        if PYTH_PRICE_UPDATE_DISCRIMINATOR != given_disc {
-            return Err(AstrapeError::InvalidPythPriceFeed);
-        }
-        let mut data: &[u8] = &buf[PYTH_PRICE_UPDATE_DISCRIMINATOR.len()..];
-        let price_update = PriceUpdateV2::deserialize(&mut data)
-            .map_err(|_| AstrapeError::InvalidPythPriceFeed)?;
-        Ok(price_update)
-    }
-
-    pub fn calculate_interest_amount(
-        amount: u64,
-        price: u64,
-        commission_rate: u64,
-        deposit_period: u64,
-        config: &AstrapeConfig,
-    ) -> u64 {
-        let collateral_value_in_interest = amount * price;
-        let base_interest_rate = config.base_interest_rate as f64 / 1000.0;
-        let deposit_period_in_years = deposit_period as f64 / SLOTS_PER_YEAR;
-        let ratio_without_commission = (1000.0 - commission_rate as f64) / 1000.0;
-
-        let interest = collateral_value_in_interest as f64
-            * base_interest_rate
-            * deposit_period_in_years
-            * ratio_without_commission;
-
-        interest as u64
-    }
-
-    pub fn calculate_interest_to_return(
-        user_deposit: &UserDeposit,
-        current_slot: u64,
-    ) -> Result<u64, AstrapeError> {
-        // Calculate remaining interest to be returned based on actual lock duration
-        let actual_lock_duration = current_slot - user_deposit.deposit_slot;
-        let total_lock_duration = user_deposit.unlock_slot - user_deposit.deposit_slot;
-
-        let interest_to_return = match (user_deposit.interest_received as u128)
-            .checked_mul(actual_lock_duration as u128)
+    fn check_upgrade_authority(
+        admin_info: &AccountInfo,
+        program_data_info: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        let expected_program_data = bpf_loader_upgradeable::get_program_data_address(program_id);
+        if *program_data_info.key != expected_program_data
+            || *program_data_info.owner != bpf_loader_upgradeable::id()
         {
-            Some(v) => v,
-            None => return Err(AstrapeError::ArithmeticOverflow.into()),
-        };
-
-        let interest_to_return = match interest_to_return.checked_div(total_lock_duration as u128) {
-            Some(v) => v,
-            None => {
-                return Err(AstrapeError::DivisionByZero.into());
+            msg!(
+                "Invalid program data account: expected={}, actual={}",
+                expected_program_data,
+                program_data_info.key
+            );
+            return Err(AstrapeError::InvalidAccountOwner.into());
+        }
+        let upgrade_authority = match limited_deserialize(
+            &program_data_info.data.borrow(),
+            UpgradeableLoaderState::size_of_programdata_metadata() as u64,
+        ) {
+            Ok(UpgradeableLoaderState::ProgramData {
+                upgrade_authority_address,
+                ..
+            }) => upgrade_authority_address,
+            _ => {
+                msg!("Invalid program data account: {}", program_data_info.key);
+                return Err(AstrapeError::InvalidAccountOwner.into());
             }
-        } as u64;
-
-        Ok(interest_to_return)
+        };
+        if upgrade_authority != Some(*admin_info.key) {
+            msg!(
+                "Signer is not the upgrade authority: expected={:?}, actual={}",
+                upgrade_authority,
+                admin_info.key
+            );
+            return Err(AstrapeError::InvalidAdmin(0).into());
+        }
+        Ok(())
     }
 
-    fn process_initialize(
+    // Pausable instructions take the config PDA as their second account
+    fn check_not_paused(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        interest_mint: Pubkey,
-        collateral_mint: Pubkey,
-        base_interest_rate: u64,
-        pyth_price_max_age: u64,
-        min_commission_rate: u64,
-        max_commission_rate: u64,
-        min_deposit_amount: u64,
-        max_deposit_amount: u64,
-        deposit_periods: Vec<u64>,
+        instruction: &AstrapeInstruction,
     ) -> ProgramResult {
-        let account_info_iter = &mut accounts.iter();
-        let admin_info = next_account_info(account_info_iter)?;
-        let config_info = next_account_info(account_info_iter)?;
-        let authority_account = next_account_info(account_info_iter)?;
-        let interest_pool_account = next_account_info(account_info_iter)?;
-        let collateral_pool_account = next_account_info(account_info_iter)?;
-        let withdrawal_pool_account = next_account_info(account_info_iter)?;
-        let interest_mint_account = next_account_info(account_info_iter)?;
-        let collateral_mint_account = next_account_info(account_info_iter)?;
-        let system_program_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
-        let ata_program_info = next_account_info(account_info_iter)?;
-        let rent_account_info = next_account_info(account_info_iter)?;
-
-        // Verify admin
-        if !admin_info.is_signer || config_feature::admin::id() != *admin_info.key {
-            msg!("Admin must be a signer");
-            return Err(AstrapeError::SignerRequired.into());
+        if !matches!(
+            instruction,
+            AstrapeInstruction::DepositCollateral { .. }
+                | AstrapeInstruction::ExtendDeposit { .. }
+                | AstrapeInstruction::Rollover { .. }
+                | AstrapeInstruction::RequestWithdrawalEarly { .. }
+                | AstrapeInstruction::RequestPartialWithdrawalEarly { .. }
+                | AstrapeInstruction::RequestWithdrawal { .. }
+                | AstrapeInstruction::WithdrawCollateral { .. }
+                | AstrapeInstruction::WithdrawOverdueCollateral { .. }
+        ) {
+            return Ok(());
         }
 
-        // Verify PDAs
-        // 1. authority PDA
-        let authority_bump = Self::check_pda(
-            "authority",
-            authority_account.key,
-            &[AUTHORITY_SEED],
-            program_id,
-        )?;
-
-        // 2. config PDA
-        let config_bump = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
-
-        // 3. interest pool PDA
-        Self::check_ata(
-            "interest pool",
-            interest_pool_account.key,
-            authority_account.key,
-            &interest_mint,
-        )?;
+        let config_info = accounts.get(1).ok_or(ProgramError::NotEnoughAccountKeys)?;
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
 
-        // 4. collateral pool PDA
-        Self::check_ata(
-            "collateral pool",
-            collateral_pool_account.key,
-            authority_account.key,
-            &collateral_mint,
-        )?;
+        let paused = match instruction {
+            AstrapeInstruction::DepositCollateral { .. }
+            | AstrapeInstruction::ExtendDeposit { .. }
+            | AstrapeInstruction::Rollover { .. } => config.paused.deposits,
+            AstrapeInstruction::RequestWithdrawalEarly { .. }
+            | AstrapeInstruction::RequestPartialWithdrawalEarly { .. } => {
+                config.paused.early_withdrawals
+            }
+            AstrapeInstruction::RequestWithdrawal { .. } => config.paused.withdrawal_requests,
+            AstrapeInstruction::WithdrawCollateral { .. }
+            | AstrapeInstruction::WithdrawOverdueCollateral { .. } => {
+                config.paused.collateral_withdrawals
+            }
+            _ => false,
+        };
+        if paused {
+            msg!("Instruction is paused: {:?}", config.paused);
+            return Err(AstrapeError::Paused.into());
+        }
+        Ok(())
+    }
 
-        // 5. withdrawal pool PDA
-        let withdrawal_pool_bump = Self::check_pda(
-            "withdrawal pool",
-            withdrawal_pool_account.key,
-            &[WITHDRAWAL_POOL_SEED],
-            program_id,
-        )?;
+    fn check_multisig_disabled(config: &AstrapeConfig) -> Result<(), AstrapeError> {
+        if config.multisig_enabled {
+            msg!("Multisig is enabled, the action has to be proposed to the multisig");
+            return Err(AstrapeError::MultisigRequired);
+        }
+        Ok(())
+    }
 
-        // Verify programs
-        // 1. system program
-        if *system_program_info.key != solana_program::system_program::id() {
+    fn check_multisig_signers(signers: &[Pubkey], threshold: u8) -> Result<(), AstrapeError> {
+        if signers.is_empty() || signers.len() > MAX_MULTISIG_SIGNERS {
             msg!(
-                "Invalid system program: expected={}, actual={}",
-                solana_program::system_program::id(),
-                system_program_info.key
+                "Invalid number of multisig signers: {}, max={}",
+                signers.len(),
+                MAX_MULTISIG_SIGNERS
             );
-            return Err(AstrapeError::InvalidAccountOwner.into());
+            return Err(AstrapeError::InvalidInput);
         }
-
-        // 2. token program
-        if *token_program_info.key != spl_token::id() {
+        if signers
+            .iter()
+            .enumerate()
+            .any(|(i, signer)| signers[..i].contains(signer))
+        {
+            msg!("Duplicate multisig signer");
+            return Err(AstrapeError::InvalidInput);
+        }
+        if threshold == 0 || threshold as usize > signers.len() {
             msg!(
-                "Invalid token program: expected={}, actual={}",
-                spl_token::id(),
-                token_program_info.key
+                "Invalid multisig threshold: threshold={}, signers={}",
+                threshold,
+                signers.len()
             );
-            return Err(AstrapeError::InvalidAccountOwner.into());
+            return Err(AstrapeError::InvalidInput);
         }
+        Ok(())
+    }
 
-        // 3. ATA program
-        if *ata_program_info.key != spl_associated_token_account::id() {
+    fn check_deposit_periods(deposit_periods: &[DepositPeriod]) -> Result<(), AstrapeError> {
+        if deposit_periods.is_empty() || deposit_periods.len() > MAX_DEPOSIT_PERIODS {
             msg!(
-                "Invalid ATA program: expected={}, actual={}",
-                spl_associated_token_account::id(),
-                ata_program_info.key
+                "Invalid number of deposit periods: {}, max={}",
+                deposit_periods.len(),
+                MAX_DEPOSIT_PERIODS
             );
-            return Err(AstrapeError::InvalidAccountOwner.into());
+            return Err(AstrapeError::InvalidInput);
         }
-
-        // Check if accounts are already initialized
-        if config_info.owner != system_program_info.key {
-            return Err(AstrapeError::AccountAlreadyInitialized.into());
+        for (i, deposit_period) in deposit_periods.iter().enumerate() {
+            Self::check_deposit_period(deposit_period)?;
+            if deposit_periods[..i]
+                .iter()
+                .any(|other| other.period == deposit_period.period)
+            {
+                msg!("Duplicate deposit period: {}", deposit_period.period);
+                return Err(AstrapeError::InvalidInput);
+            }
         }
+        Ok(())
+    }
 
-        // Validate configuration parameters
-        if min_commission_rate > max_commission_rate {
-            return Err(AstrapeError::InvalidInput.into());
+    fn check_deposit_period(deposit_period: &DepositPeriod) -> Result<(), AstrapeError> {
+        if deposit_period.period == 0 {
+            msg!("Deposit period cannot be zero");
+            return Err(AstrapeError::InvalidLockPeriod(0));
         }
-        if min_deposit_amount > max_deposit_amount {
-            return Err(AstrapeError::InvalidInput.into());
+        if deposit_period.interest_rate > RATE_DENOMINATOR {
+            msg!(
+                "Interest rate {} of deposit period {} exceeds 100%",
+                deposit_period.interest_rate,
+                deposit_period.period
+            );
+            return Err(AstrapeError::InvalidInput);
         }
-        if deposit_periods.is_empty() {
-            return Err(AstrapeError::InvalidInput.into());
+        Ok(())
+    }
+
+    // A cap below the collateral already locked would leave the pool over capacity
+    fn check_capacity_covers(
+        name: &str,
+        capacity: Option<u64>,
+        locked: u64,
+    ) -> Result<(), AstrapeError> {
+        if let Some(capacity) = capacity {
+            if capacity < locked {
+                msg!(
+                    "Capacity of {} below its locked collateral: capacity={}, locked={}",
+                    name,
+                    capacity,
+                    locked
+                );
+                return Err(AstrapeError::InvalidInput);
+            }
         }
-        if pyth_price_max_age == 0 {
-            return Err(AstrapeError::ValueOutOfRange(0).into());
+        Ok(())
+    }
+
+    fn check_tenor_capacity(
+        deposit_period: &DepositPeriod,
+        pool_state: &PoolState,
+    ) -> Result<(), AstrapeError> {
+        let locked = pool_state
+            .tenor_totals(deposit_period.period)
+            .map_or(0, |totals| totals.amount);
+        Self::check_capacity_covers("tenor", deposit_period.capacity, locked)
+    }
+
+    fn check_early_exit_policy(policy: &EarlyExitPolicy) -> Result<(), AstrapeError> {
+        match policy {
+            EarlyExitPolicy::LinearUnearned => {}
+            EarlyExitPolicy::FlatPenalty { penalty_bps } => {
+                if *penalty_bps > BPS_DENOMINATOR {
+                    msg!("Early exit penalty exceeds 100%: {}", penalty_bps);
+                    return Err(AstrapeError::ValueOutOfRange(*penalty_bps));
+                }
+            }
+            EarlyExitPolicy::StepSchedule(steps) => {
+                if steps.is_empty() || steps.len() > MAX_REFUND_STEPS {
+                    msg!(
+                        "Invalid number of refund steps: {}, max={}",
+                        steps.len(),
+                        MAX_REFUND_STEPS
+                    );
+                    return Err(AstrapeError::InvalidInput);
+                }
+                let mut previous_until_bps = 0;
+                for step in steps {
+                    if step.until_bps <= previous_until_bps || step.until_bps > BPS_DENOMINATOR {
+                        msg!("Refund steps must end in increasing order: {:?}", step);
+                        return Err(AstrapeError::ValueOutOfRange(step.until_bps));
+                    }
+                    if step.refund_bps > BPS_DENOMINATOR {
+                        msg!("Refund step exceeds 100%: {:?}", step);
+                        return Err(AstrapeError::ValueOutOfRange(step.refund_bps));
+                    }
+                    previous_until_bps = step.until_bps;
+                }
+            }
         }
+        Ok(())
+    }
 
-        let rent = Rent::get()?;
-        // Initialize authority account
-        let authority_signer_seeds: &[&[_]] = &[AUTHORITY_SEED, &[authority_bump]];
-        invoke_signed(
-            &system_instruction::create_account(
-                admin_info.key,
-                authority_account.key,
-                rent.minimum_balance(0),
-                0,
-                program_id,
-            ),
+    fn read_mint_decimals(
+        name: &str,
+        mint_info: &AccountInfo,
+        expected_mint: &Pubkey,
+    ) -> Result<u8, ProgramError> {
+        if mint_info.key != expected_mint {
+            msg!(
+                "Invalid {} mint: expected={}, actual={}",
+                name,
+                expected_mint,
+                mint_info.key
+            );
+            return Err(AstrapeError::InvalidMint.into());
+        }
+        if mint_info.owner != &spl_token::id() {
+            msg!("Invalid {} mint owner: {}", name, mint_info.owner);
+            return Err(AstrapeError::InvalidAccountOwner.into());
+        }
+        Ok(Mint::unpack(&mint_info.data.borrow())?.decimals)
+    }
+
+    // Unpacks a deposit account, which must be the PDA of the depositor and index it records
+    fn unpack_user_deposit(
+        user_deposit_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<UserDeposit, ProgramError> {
+        if user_deposit_account.owner != program_id {
+            return Err(AstrapeError::invalid_account_owner(
+                &user_deposit_account.owner.to_string(),
+            )
+            .into());
+        }
+        if user_deposit_account.data_len() != UserDeposit::LEN {
+            msg!("Invalid user deposit account: {}", user_deposit_account.key);
+            return Err(AstrapeError::InvalidAccountOwner.into());
+        }
+        let deposit = UserDeposit::unpack(&user_deposit_account.data.borrow())?;
+        if deposit.discriminator != UserDeposit::DISCRIMINATOR {
+            msg!("Invalid user deposit account: {}", user_deposit_account.key);
+            return Err(AstrapeError::InvalidAccountOwner.into());
+        }
+        let _ = Self::check_pda(
+            "user deposit",
+            user_deposit_account.key,
             &[
-                admin_info.clone(),
-                authority_account.clone(),
-                system_program_info.clone(),
+                USER_DEPOSIT_SEED,
+                deposit.depositor.as_ref(),
+                &deposit.deposit_index.to_le_bytes(),
             ],
-            &[authority_signer_seeds],
+            program_id,
         )?;
+        Ok(deposit)
+    }
 
-        // Initialize config account
-        let config_signer_seeds: &[&[_]] = &[CONFIG_SEED, &[config_bump]];
-        let config_size = AstrapeConfig::LEN;
-        msg!("Config size: {}", config_size);
-        let config_lamports = rent.minimum_balance(config_size).max(1);
+    // Loads the deposit at `deposit_index` of its depositor
+    fn load_user_deposit(
+        user_deposit_account: &AccountInfo,
+        deposit_index: u64,
+        program_id: &Pubkey,
+    ) -> Result<UserDeposit, ProgramError> {
+        let deposit = Self::unpack_user_deposit(user_deposit_account, program_id)?;
+        if deposit.deposit_index != deposit_index {
+            msg!(
+                "Invalid deposit index: expected={}, actual={}",
+                deposit.deposit_index,
+                deposit_index
+            );
+            return Err(AstrapeError::InvalidPDA(1).into());
+        }
+        Ok(deposit)
+    }
 
-        invoke_signed(
-            &system_instruction::create_account(
-                admin_info.key,
-                config_info.key,
-                config_lamports,
-                config_size as u64,
-                program_id,
-            ),
-            &[
-                admin_info.clone(),
-                config_info.clone(),
-                system_program_info.clone(),
-            ],
-            &[config_signer_seeds],
+    fn check_position_owner(
+        owner_info: &AccountInfo,
+        deposit: &UserDeposit,
+    ) -> Result<(), AstrapeError> {
+        if deposit.owner != *owner_info.key {
+            msg!(
+                "Invalid position owner: expected={}, actual={}",
+                deposit.owner,
+                owner_info.key
+            );
+            return Err(AstrapeError::NotPositionOwner);
+        }
+        Ok(())
+    }
+
+    // Loads a deposit for an instruction signed by its owner, which is the holder who redeemed it
+    // once the receipt is burned
+    fn load_owned_deposit(
+        owner_info: &AccountInfo,
+        user_deposit_account: &AccountInfo,
+        deposit_index: u64,
+        program_id: &Pubkey,
+    ) -> Result<UserDeposit, ProgramError> {
+        if !owner_info.is_signer {
+            msg!("Position owner must be a signer");
+            return Err(AstrapeError::SignerRequired.into());
+        }
+        let deposit = Self::load_user_deposit(user_deposit_account, deposit_index, program_id)?;
+        Self::check_position_owner(owner_info, &deposit)?;
+        Ok(deposit)
+    }
+
+    // Loads a deposit for an instruction signed by the holder of its receipt. The receipt can
+    // also move by a plain token transfer, so the owner is updated to the holder.
+    fn load_held_deposit(
+        holder_info: &AccountInfo,
+        receipt_account: &AccountInfo,
+        user_deposit_account: &AccountInfo,
+        deposit_index: u64,
+        program_id: &Pubkey,
+    ) -> Result<UserDeposit, ProgramError> {
+        Self::check_receipt_holder(
+            holder_info,
+            receipt_account,
+            user_deposit_account,
+            program_id,
         )?;
+        let mut deposit = Self::load_user_deposit(user_deposit_account, deposit_index, program_id)?;
+        deposit.owner = *holder_info.key;
+        Ok(deposit)
+    }
 
-        // Create interest pool ATA - the ATA will be owned by the authority PDA
-        invoke(
-            &ata_instruction::create_associated_token_account(
-                admin_info.key,        // Payer
-                authority_account.key, // Owner of the new account (authority PDA)
-                &interest_mint,        // Mint
-                &spl_token::id(),      // Token program ID
-            ),
-            &[
-                admin_info.clone(),            // Payer
-                interest_pool_account.clone(), // Associated token account
-                authority_account.clone(),     // Owner of the new account (authority PDA)
-                interest_mint_account.clone(), // Mint account
-                system_program_info.clone(),   // System program
-                token_program_info.clone(),    // Token program
-                ata_program_info.clone(),      // Associated token program
-            ],
+    fn load_pool_state(
+        pool_state_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<PoolState, ProgramError> {
+        let _ = Self::check_pda(
+            "pool state",
+            pool_state_account.key,
+            &[POOL_STATE_SEED],
+            program_id,
         )?;
+        Ok(PoolState::unpack(&pool_state_account.data.borrow())?)
+    }
 
-        // Create collateral pool ATA - the ATA will be owned by the authority PDA
-        invoke(
-            &ata_instruction::create_associated_token_account(
-                admin_info.key,        // Payer
-                authority_account.key, // Owner of the new account (authority PDA)
-                &collateral_mint,      // Mint
-                &spl_token::id(),      // Token program ID
-            ),
-            &[
-                admin_info.clone(),              // Payer
-                collateral_pool_account.clone(), // Associated token account
-                authority_account.clone(),       // Owner of the new account (authority PDA)
-                collateral_mint_account.clone(), // Mint account
-                system_program_info.clone(),     // System program
-                token_program_info.clone(),      // Token program
-                ata_program_info.clone(),        // Associated token program
-            ],
+    fn load_withdrawal_queue(
+        withdrawal_queue_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<WithdrawalQueue, ProgramError> {
+        let _ = Self::check_pda(
+            "withdrawal queue",
+            withdrawal_queue_account.key,
+            &[WITHDRAWAL_QUEUE_SEED],
+            program_id,
         )?;
+        Ok(WithdrawalQueue::unpack(
+            &withdrawal_queue_account.data.borrow(),
+        )?)
+    }
 
-        // create withdrawal pool account and initialize token account
-        let token_account_size = TokenAccount::LEN;
-        let token_account_lamports = rent.minimum_balance(token_account_size).max(1);
-        let withdrawal_pool_signer_seeds: &[&[_]] =
-            &[WITHDRAWAL_POOL_SEED, &[withdrawal_pool_bump]];
+    // Append a position in `WithdrawRequested` to the withdrawal queue. It is linked from
+    // `queue_tail_account`, the newest queued position, which is ignored when the queue is empty.
+    // The caller serializes the deposit afterwards.
+    fn enqueue_withdrawal(
+        withdrawal_queue_account: &AccountInfo,
+        queue_tail_account: &AccountInfo,
+        program_id: &Pubkey,
+        deposit_account: &Pubkey,
+        deposit: &mut UserDeposit,
+        slot: u64,
+        early: bool,
+    ) -> ProgramResult {
+        let mut withdrawal_queue =
+            Self::load_withdrawal_queue(withdrawal_queue_account, program_id)?;
+        let mut tail = match withdrawal_queue.tail {
+            Some(_) => Some(Self::unpack_user_deposit(queue_tail_account, program_id)?),
+            None => None,
+        };
+        withdrawal_queue
+            .push(
+                deposit_account,
+                deposit,
+                tail.as_mut().map(|tail| (queue_tail_account.key, tail)),
+                slot,
+            )
+            .with_context("Pass the newest position of the withdrawal queue")?;
+
+        if let Some(tail) = tail {
+            let mut tail_data = queue_tail_account.data.borrow_mut();
+            let mut dst = &mut tail_data[..];
+            tail.serialize(&mut dst)?;
+        }
+
+        let mut withdrawal_queue_data = withdrawal_queue_account.data.borrow_mut();
+        let mut dst = &mut withdrawal_queue_data[..];
+        withdrawal_queue.serialize(&mut dst)?;
+
+        AstrapeEvent::WithdrawalRequested(WithdrawalRequestedEvent {
+            deposit: *deposit_account,
+            amount: deposit.amount,
+            early,
+            total_pending: withdrawal_queue.total_pending,
+        })
+        .emit();
+
+        msg!(
+            "Queued withdrawal of {}, pending {}",
+            deposit.amount,
+            withdrawal_queue.total_pending
+        );
+        Ok(())
+    }
+
+    // Each position has a 0-decimal receipt mint derived from its deposit account, with the
+    // authority PDA as mint authority. Whoever holds its single token redeems the collateral.
+    fn create_receipt_mint<'a>(
+        program_id: &Pubkey,
+        payer_info: &AccountInfo<'a>,
+        user_deposit_account: &AccountInfo<'a>,
+        receipt_mint_account: &AccountInfo<'a>,
+        authority_info: &AccountInfo<'a>,
+        system_program_info: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        let receipt_mint_bump = Self::check_pda(
+            "receipt mint",
+            receipt_mint_account.key,
+            &[RECEIPT_MINT_SEED, user_deposit_account.key.as_ref()],
+            program_id,
+        )?;
+
+        let rent = Rent::get()?;
         invoke_signed(
             &system_instruction::create_account(
-                admin_info.key,
-                withdrawal_pool_account.key,
-                token_account_lamports,
-                token_account_size as u64,
-                token_program_info.key,
+                payer_info.key,
+                receipt_mint_account.key,
+                rent.minimum_balance(Mint::LEN).max(1),
+                Mint::LEN as u64,
+                &spl_token::id(),
             ),
             &[
-                admin_info.clone(),
-                withdrawal_pool_account.clone(),
-                authority_account.clone(),
+                payer_info.clone(),
+                receipt_mint_account.clone(),
                 system_program_info.clone(),
             ],
-            &[withdrawal_pool_signer_seeds],
+            &[&[
+                RECEIPT_MINT_SEED,
+                user_deposit_account.key.as_ref(),
+                &[receipt_mint_bump],
+            ]],
         )?;
         invoke(
-            &token_instruction::initialize_account(
+            &token_instruction::initialize_mint2(
                 &spl_token::id(),
-                withdrawal_pool_account.key,
-                collateral_mint_account.key,
-                authority_account.key,
+                receipt_mint_account.key,
+                authority_info.key,
+                None,
+                0,
+            )?,
+            std::slice::from_ref(receipt_mint_account),
+        )
+    }
+
+    fn mint_receipt<'a>(
+        authority_info: &AccountInfo<'a>,
+        authority_bump: u8,
+        receipt_mint_account: &AccountInfo<'a>,
+        receipt_account: &AccountInfo<'a>,
+    ) -> ProgramResult {
+        invoke_signed(
+            &token_instruction::mint_to(
+                &spl_token::id(),
+                receipt_mint_account.key,
+                receipt_account.key,
+                authority_info.key,
+                &[],
+                1,
             )?,
             &[
-                withdrawal_pool_account.clone(),
-                collateral_mint_account.clone(),
-                authority_account.clone(),
-                rent_account_info.clone(),
+                receipt_mint_account.clone(),
+                receipt_account.clone(),
+                authority_info.clone(),
             ],
-            // &[withdrawal_pool_signer_seeds],
-        )?;
+            &[&[AUTHORITY_SEED, &[authority_bump]]],
+        )
+    }
 
-        // Initialize config with provided values
-        let config = AstrapeConfig {
-            interest_mint,
-            collateral_mint,
-            base_interest_rate,
-            pyth_price_max_age,
-            min_commission_rate,
-            max_commission_rate,
-            min_deposit_amount,
-            max_deposit_amount,
-            deposit_periods,
-        };
-        let mut config_data = config_info.data.borrow_mut();
-        let mut dst = &mut config_data[..];
-        config.serialize(&mut dst)?;
+    fn check_receipt_holder(
+        holder_info: &AccountInfo,
+        receipt_account: &AccountInfo,
+        user_deposit_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if !holder_info.is_signer {
+            msg!("Receipt holder must be a signer");
+            return Err(AstrapeError::SignerRequired.into());
+        }
+        Self::check_receipt(
+            holder_info,
+            receipt_account,
+            user_deposit_account,
+            program_id,
+        )
+    }
+
+    // Checks that `holder_info` holds the receipt of the position, without requiring a signature
+    fn check_receipt(
+        holder_info: &AccountInfo,
+        receipt_account: &AccountInfo,
+        user_deposit_account: &AccountInfo,
+        program_id: &Pubkey,
+    ) -> Result<(), ProgramError> {
+        if receipt_account.owner != &spl_token::id() {
+            return Err(
+                AstrapeError::invalid_account_owner(&receipt_account.owner.to_string()).into(),
+            );
+        }
+        let (receipt_mint, _) = Pubkey::find_program_address(
+            &[RECEIPT_MINT_SEED, user_deposit_account.key.as_ref()],
+            program_id,
+        );
+        let receipt = TokenAccount::unpack(&receipt_account.data.borrow())?;
+        if receipt.mint != receipt_mint || receipt.owner != *holder_info.key || receipt.amount == 0
+        {
+            msg!(
+                "Account {} does not hold the receipt {}",
+                holder_info.key,
+                receipt_mint
+            );
+            return Err(AstrapeError::ReceiptNotHeld.into());
+        }
+        Ok(())
+    }
+
+    fn check_ata(
+        name: &str,
+        ata: &Pubkey,
+        wallet: &Pubkey,
+        mint: &Pubkey,
+    ) -> Result<(), AstrapeError> {
+        let expected_ata = spl_associated_token_account::get_associated_token_address(wallet, mint);
+        if expected_ata != *ata {
+            msg!(
+                "Invalid ATA for {}: expected={}, actual={}",
+                name,
+                expected_ata,
+                ata
+            );
+            return Err(AstrapeError::InvalidPDA(1));
+        }
+        Ok(())
+    }
+
+    fn deserialize_price_update(
+        pyth_price_feed_account: &AccountInfo,
+    ) -> Result<PriceUpdateV2, AstrapeError> {
+        if pyth_price_feed_account.owner != &pyth_solana_receiver_sdk::ID {
+            msg!(
+                "Invalid pyth price feed owner: {}",
+                pyth_price_feed_account.owner
+            );
+            return Err(AstrapeError::InvalidPythPriceFeed);
+        }
+        let buf = pyth_price_feed_account.data.borrow();
+        if buf.len() < PYTH_PRICE_UPDATE_DISCRIMINATOR.len() {
+            return Err(AstrapeError::InvalidPythPriceFeed);
+        }
+        let given_disc = &buf[..PYTH_PRICE_UPDATE_DISCRIMINATOR.len()];
+        if PYTH_PRICE_UPDATE_DISCRIMINATOR != given_disc {
+            return Err(AstrapeError::InvalidPythPriceFeed);
+        }
+        let mut data: &[u8] = &buf[PYTH_PRICE_UPDATE_DISCRIMINATOR.len()..];
+        let price_update = PriceUpdateV2::deserialize(&mut data)
+            .map_err(|_| AstrapeError::InvalidPythPriceFeed)?;
+        Ok(price_update)
+    }
+
+    // Fresh price of the collateral from the configured feed
+    fn read_collateral_price(
+        pyth_price_feed_account: &AccountInfo,
+        config: &AstrapeConfig,
+    ) -> Result<u64, ProgramError> {
+        let price_update = Self::deserialize_price_update(pyth_price_feed_account)?;
+
+        let price_object = price_update
+            .get_price_no_older_than(
+                &Clock::get()?,
+                config.pyth_price_max_age,
+                &config.pyth_feed_id,
+            )
+            .map_err(|_| AstrapeError::GetPriceError)?;
+
+        Ok(Self::normalize_pyth_price(
+            price_object.price,
+            price_object.exponent,
+        )?)
+    }
+
+    /// Converts a Pyth price `price * 10^exponent` to a fixed decimal with
+    /// `PRICE_DECIMALS` decimal places. Extra precision is rounded down.
+    pub fn normalize_pyth_price(price: i64, exponent: i32) -> Result<u64, AstrapeError> {
+        if price <= 0 {
+            msg!("Invalid pyth price: {}", price);
+            return Err(AstrapeError::GetPriceError);
+        }
+        let price = price as u64;
+
+        let shift = exponent
+            .checked_add(PRICE_DECIMALS as i32)
+            .ok_or(AstrapeError::GetPriceError)?;
+        let scale = 10_u64.checked_pow(shift.unsigned_abs());
+        if shift >= 0 {
+            scale
+                .and_then(|scale| price.checked_mul(scale))
+                .ok_or(AstrapeError::ArithmeticOverflow)
+        } else {
+            // Nothing of the price is left if the scale does not even fit in u64
+            Ok(scale.map_or(0, |scale| price / scale))
+        }
+    }
+
+    /// Interest paid upfront for a deposit, in base units of the interest mint,
+    /// at the interest rate of the `deposit_period` tenor
+    pub fn calculate_interest_amount(
+        amount: u64,
+        price: u64,
+        commission_rate: u64,
+        deposit_period: u64,
+        config: &AstrapeConfig,
+    ) -> Result<u64, AstrapeError> {
+        let tenor = config.deposit_period(deposit_period).ok_or_else(|| {
+            msg!("No tenor for lock period {}", deposit_period);
+            AstrapeError::InvalidLockPeriod(deposit_period)
+        })?;
+
+        Self::calculate_interest_for_rate(
+            amount,
+            price,
+            tenor.interest_rate,
+            commission_rate,
+            deposit_period,
+            config,
+        )
+    }
+
+    /// Interest paid upfront for a deposit at `interest_rate`, in base units of the
+    /// interest mint. `price` is the price of one collateral token in interest tokens
+    /// with `PRICE_DECIMALS` decimal places. It is computed in two steps:
+    ///
+    /// 1. `collateral_value = amount * price * 10^interest_decimals
+    ///    / (10^collateral_decimals * 10^PRICE_DECIMALS)`, the collateral value in
+    ///    base units of the interest mint
+    /// 2. `collateral_value * (interest_rate / 1000) * ((1000 - commission_rate) / 1000)
+    ///    * (deposit_period * MS_PER_SLOT / MS_PER_YEAR)`
+    ///
+    /// Each step is computed in `u128` and divided once at its end. The divisions
+    /// truncate, so fractions of a unit are rounded down in favor of the pool.
+    pub fn calculate_interest_for_rate(
+        amount: u64,
+        price: u64,
+        interest_rate: u64,
+        commission_rate: u64,
+        deposit_period: u64,
+        config: &AstrapeConfig,
+    ) -> Result<u64, AstrapeError> {
+        let ratio_without_commission = RATE_DENOMINATOR
+            .checked_sub(commission_rate)
+            .ok_or(AstrapeError::CommissionRateOutOfBounds(commission_rate))?;
+
+        let collateral_value = Self::convert_to_interest_units(amount, price, config)?;
+
+        let numerator = [
+            interest_rate,
+            ratio_without_commission,
+            deposit_period,
+            MS_PER_SLOT,
+        ]
+        .iter()
+        .try_fold(collateral_value, |acc, &factor| {
+            acc.checked_mul(factor as u128)
+        })
+        .ok_or(AstrapeError::ArithmeticOverflow)?;
+        let denominator =
+            (RATE_DENOMINATOR as u128) * (RATE_DENOMINATOR as u128) * (MS_PER_YEAR as u128);
+
+        u64::try_from(numerator / denominator).map_err(|_| AstrapeError::ArithmeticOverflow)
+    }
+
+    /// Commission taken from the interest of a deposit, in base units of the interest
+    /// mint. It is the interest without commission minus the interest paid to the user,
+    /// so the rounding remainders of the user's interest go to the treasury.
+    pub fn calculate_commission_amount(
+        amount: u64,
+        price: u64,
+        commission_rate: u64,
+        deposit_period: u64,
+        config: &AstrapeConfig,
+    ) -> Result<u64, AstrapeError> {
+        let gross_interest =
+            Self::calculate_interest_amount(amount, price, 0, deposit_period, config)?;
+        let interest = Self::calculate_interest_amount(
+            amount,
+            price,
+            commission_rate,
+            deposit_period,
+            config,
+        )?;
+        gross_interest
+            .checked_sub(interest)
+            .ok_or(AstrapeError::ArithmeticOverflow)
+    }
+
+    /// Interest and commission for extending `user_deposit` at `current_slot` by `amount`
+    /// of collateral and until `unlock_slot`, at `interest_rate` and the commission rate of
+    /// the position. The added collateral earns from now, the existing collateral from the
+    /// later of its current unlock slot and now.
+    pub fn calculate_extension_interest(
+        user_deposit: &UserDeposit,
+        amount: u64,
+        unlock_slot: u64,
+        current_slot: u64,
+        price: u64,
+        interest_rate: u64,
+        config: &AstrapeConfig,
+    ) -> Result<(u64, u64), AstrapeError> {
+        let added_period = unlock_slot
+            .checked_sub(current_slot)
+            .ok_or(AstrapeError::InvalidLockPeriod(unlock_slot))?;
+        let extended_period = unlock_slot
+            .checked_sub(user_deposit.unlock_slot.max(current_slot))
+            .ok_or(AstrapeError::InvalidLockPeriod(unlock_slot))?;
+
+        let interest_with_commission = |commission_rate| -> Result<u64, AstrapeError> {
+            let added_interest = Self::calculate_interest_for_rate(
+                amount,
+                price,
+                interest_rate,
+                commission_rate,
+                added_period,
+                config,
+            )?;
+            let extended_interest = Self::calculate_interest_for_rate(
+                user_deposit.amount,
+                price,
+                interest_rate,
+                commission_rate,
+                extended_period,
+                config,
+            )?;
+            added_interest
+                .checked_add(extended_interest)
+                .ok_or(AstrapeError::ArithmeticOverflow)
+        };
+
+        let interest = interest_with_commission(user_deposit.commission_rate)?;
+        let commission = interest_with_commission(0)?
+            .checked_sub(interest)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        Ok((interest, commission))
+    }
+
+    fn convert_to_interest_units(
+        amount: u64,
+        price: u64,
+        config: &AstrapeConfig,
+    ) -> Result<u128, AstrapeError> {
+        let value = amount as u128 * price as u128;
+        let interest_decimals = config.interest_decimals as u32;
+        let collateral_decimals = config.collateral_decimals as u32 + PRICE_DECIMALS;
+
+        // Scale by the decimals difference only, so that rounding happens once
+        if interest_decimals >= collateral_decimals {
+            10_u128
+                .checked_pow(interest_decimals - collateral_decimals)
+                .and_then(|scale| value.checked_mul(scale))
+                .ok_or(AstrapeError::ArithmeticOverflow)
+        } else {
+            Ok(10_u128
+                .checked_pow(collateral_decimals - interest_decimals)
+                .map_or(0, |scale| value / scale))
+        }
+    }
+
+    /// Interest the user refunds to the pool when leaving `user_deposit` at `current_slot`
+    /// under the early exit `policy`. Never more than the interest received.
+    pub fn calculate_interest_to_return(
+        user_deposit: &UserDeposit,
+        policy: &EarlyExitPolicy,
+        current_slot: u64,
+    ) -> Result<u64, AstrapeError> {
+        let total_lock_duration = user_deposit
+            .unlock_slot
+            .checked_sub(user_deposit.deposit_slot)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        if total_lock_duration == 0 {
+            return Err(AstrapeError::DivisionByZero);
+        }
+        let actual_lock_duration = current_slot
+            .saturating_sub(user_deposit.deposit_slot)
+            .min(total_lock_duration);
+
+        // u64 * u64 products cannot overflow u128
+        let interest_received = user_deposit.interest_received as u128;
+        let unearned_interest = interest_received
+            * (total_lock_duration - actual_lock_duration) as u128
+            / total_lock_duration as u128;
+
+        let interest_to_return = match policy {
+            EarlyExitPolicy::LinearUnearned => unearned_interest,
+            EarlyExitPolicy::FlatPenalty { penalty_bps } => {
+                let penalty = interest_received * *penalty_bps as u128 / BPS_DENOMINATOR as u128;
+                (unearned_interest + penalty).min(interest_received)
+            }
+            EarlyExitPolicy::StepSchedule(steps) => {
+                let elapsed_bps = actual_lock_duration as u128 * BPS_DENOMINATOR as u128
+                    / total_lock_duration as u128;
+                let refund_bps = steps
+                    .iter()
+                    .find(|step| elapsed_bps < step.until_bps as u128)
+                    .map_or(0, |step| step.refund_bps.min(BPS_DENOMINATOR));
+                interest_received * refund_bps as u128 / BPS_DENOMINATOR as u128
+            }
+        };
+
+        u64::try_from(interest_to_return).map_err(|_| AstrapeError::ArithmeticOverflow)
+    }
+
+    /// Applies an extension of `deposit` paying `interest_amount` upfront. The lock restarts at
+    /// `current_slot`: the interest already earned is settled, and the unearned part of the
+    /// previous interest stays refundable together with the new interest, so an early exit
+    /// right after the extension refunds all of it.
+    pub fn extend_position(
+        deposit: &mut UserDeposit,
+        amount: u64,
+        deposit_period: u64,
+        unlock_slot: u64,
+        interest_amount: u64,
+        current_slot: u64,
+    ) -> Result<(), AstrapeError> {
+        let unearned_interest = Self::calculate_interest_to_return(
+            deposit,
+            &EarlyExitPolicy::LinearUnearned,
+            current_slot,
+        )?;
+
+        deposit.amount = deposit
+            .amount
+            .checked_add(amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        deposit.deposit_slot = current_slot;
+        deposit.unlock_slot = unlock_slot;
+        deposit.deposit_period = deposit_period;
+        deposit.interest_received = unearned_interest
+            .checked_add(interest_amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// Collateral which can still be locked in the pool and in `tenor`, or `None` when
+    /// neither is capped
+    pub fn remaining_capacity(
+        config: &AstrapeConfig,
+        tenor: &DepositPeriod,
+        pool_state: &PoolState,
+    ) -> Option<u64> {
+        let pool_remaining = config
+            .pool_capacity
+            .map(|capacity| capacity.saturating_sub(pool_state.total_locked));
+        let tenor_remaining = tenor.capacity.map(|capacity| {
+            let locked = pool_state
+                .tenor_totals(tenor.period)
+                .map_or(0, |totals| totals.amount);
+            capacity.saturating_sub(locked)
+        });
+        pool_remaining.into_iter().chain(tenor_remaining).min()
+    }
+
+    fn check_capacity(
+        config: &AstrapeConfig,
+        tenor: &DepositPeriod,
+        pool_state: &PoolState,
+        amount: u64,
+    ) -> Result<(), AstrapeError> {
+        if let Some(remaining) = Self::remaining_capacity(config, tenor, pool_state) {
+            if amount > remaining {
+                msg!(
+                    "Deposit capacity exceeded: amount={}, remaining={}",
+                    amount,
+                    remaining
+                );
+                return Err(AstrapeError::DepositCapacityExceeded(remaining));
+            }
+        }
+        Ok(())
+    }
+
+    /// Splits `amount` of collateral off `deposit` together with its pro rata share of the
+    /// interest received, and returns the split-off part with the same lock. `deposit` keeps
+    /// the rest, which must hold at least `min_amount`.
+    pub fn split_deposit(
+        deposit: &mut UserDeposit,
+        amount: u64,
+        min_amount: u64,
+    ) -> Result<UserDeposit, AstrapeError> {
+        if amount == 0 || amount >= deposit.amount {
+            msg!(
+                "Invalid partial withdrawal amount: amount={}, deposit amount={}",
+                amount,
+                deposit.amount
+            );
+            return Err(AstrapeError::InvalidInput);
+        }
+        if deposit.amount - amount < min_amount {
+            msg!(
+                "Remaining deposit below minimum: remaining={}, min={}",
+                deposit.amount - amount,
+                min_amount
+            );
+            return Err(AstrapeError::DepositAmountOutOfBounds(
+                deposit.amount - amount,
+            ));
+        }
+
+        // Rounded down, so the remaining position keeps the rounding remainder
+        let interest_share =
+            deposit.interest_received as u128 * amount as u128 / deposit.amount as u128;
+        let interest_share =
+            u64::try_from(interest_share).map_err(|_| AstrapeError::ArithmeticOverflow)?;
+
+        deposit.amount -= amount;
+        deposit.interest_received -= interest_share;
+
+        Ok(UserDeposit {
+            amount,
+            interest_received: interest_share,
+            ..deposit.clone()
+        })
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_initialize(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        interest_mint: Pubkey,
+        collateral_mint: Pubkey,
+        pyth_feed_id: [u8; 32],
+        pyth_price_max_age: u64,
+        min_commission_rate: u64,
+        max_commission_rate: u64,
+        min_deposit_amount: u64,
+        max_deposit_amount: u64,
+        deposit_periods: Vec<DepositPeriod>,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_account = next_account_info(account_info_iter)?;
+        let interest_pool_account = next_account_info(account_info_iter)?;
+        let collateral_pool_account = next_account_info(account_info_iter)?;
+        let withdrawal_pool_account = next_account_info(account_info_iter)?;
+        let treasury_account = next_account_info(account_info_iter)?;
+        let interest_mint_account = next_account_info(account_info_iter)?;
+        let collateral_mint_account = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let ata_program_info = next_account_info(account_info_iter)?;
+        let rent_account_info = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+        let investment_ledger_account = next_account_info(account_info_iter)?;
+        let withdrawal_queue_account = next_account_info(account_info_iter)?;
+        let program_data_info = next_account_info(account_info_iter)?;
+
+        // The initializer becomes the admin, and only the upgrade authority of the program
+        // can initialize it
+        if !admin_info.is_signer {
+            msg!("Admin must be a signer");
+            return Err(AstrapeError::SignerRequired.into());
+        }
+        Self::check_upgrade_authority(admin_info, program_data_info, program_id)?;
+
+        // Verify PDAs
+        // 1. authority PDA
+        let authority_bump = Self::check_pda(
+            "authority",
+            authority_account.key,
+            &[AUTHORITY_SEED],
+            program_id,
+        )?;
+
+        // 2. config PDA
+        let config_bump = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+
+        // 3. interest pool PDA
+        Self::check_ata(
+            "interest pool",
+            interest_pool_account.key,
+            authority_account.key,
+            &interest_mint,
+        )?;
+
+        // 4. collateral pool PDA
+        Self::check_ata(
+            "collateral pool",
+            collateral_pool_account.key,
+            authority_account.key,
+            &collateral_mint,
+        )?;
+
+        // 5. withdrawal pool PDA
+        let withdrawal_pool_bump = Self::check_pda(
+            "withdrawal pool",
+            withdrawal_pool_account.key,
+            &[WITHDRAWAL_POOL_SEED],
+            program_id,
+        )?;
+
+        // 6. treasury PDA
+        let treasury_bump = Self::check_pda(
+            "treasury",
+            treasury_account.key,
+            &[TREASURY_SEED],
+            program_id,
+        )?;
+
+        // 7. pool state PDA
+        let pool_state_bump = Self::check_pda(
+            "pool state",
+            pool_state_account.key,
+            &[POOL_STATE_SEED],
+            program_id,
+        )?;
+
+        // 8. investment ledger PDA
+        let investment_ledger_bump = Self::check_pda(
+            "investment ledger",
+            investment_ledger_account.key,
+            &[INVESTMENT_LEDGER_SEED],
+            program_id,
+        )?;
+
+        // 9. withdrawal queue PDA
+        let withdrawal_queue_bump = Self::check_pda(
+            "withdrawal queue",
+            withdrawal_queue_account.key,
+            &[WITHDRAWAL_QUEUE_SEED],
+            program_id,
+        )?;
+
+        // Verify programs
+        // 1. system program
+        if *system_program_info.key != solana_program::system_program::id() {
+            msg!(
+                "Invalid system program: expected={}, actual={}",
+                solana_program::system_program::id(),
+                system_program_info.key
+            );
+            return Err(AstrapeError::InvalidAccountOwner.into());
+        }
+
+        // 2. token program
+        if *token_program_info.key != spl_token::id() {
+            msg!(
+                "Invalid token program: expected={}, actual={}",
+                spl_token::id(),
+                token_program_info.key
+            );
+            return Err(AstrapeError::InvalidAccountOwner.into());
+        }
+
+        // 3. ATA program
+        if *ata_program_info.key != spl_associated_token_account::id() {
+            msg!(
+                "Invalid ATA program: expected={}, actual={}",
+                spl_associated_token_account::id(),
+                ata_program_info.key
+            );
+            return Err(AstrapeError::InvalidAccountOwner.into());
+        }
+
+        // Check if accounts are already initialized
+        if config_info.owner != system_program_info.key {
+            return Err(AstrapeError::AccountAlreadyInitialized.into());
+        }
+
+        let interest_decimals =
+            Self::read_mint_decimals("interest", interest_mint_account, &interest_mint)?;
+        let collateral_decimals =
+            Self::read_mint_decimals("collateral", collateral_mint_account, &collateral_mint)?;
+
+        // Validate configuration parameters
+        if min_commission_rate > max_commission_rate {
+            return Err(AstrapeError::InvalidInput.into());
+        }
+        if max_commission_rate > RATE_DENOMINATOR {
+            msg!("Max commission rate {} exceeds 100%", max_commission_rate);
+            return Err(AstrapeError::InvalidInput.into());
+        }
+        if min_deposit_amount > max_deposit_amount {
+            return Err(AstrapeError::InvalidInput.into());
+        }
+        Self::check_deposit_periods(&deposit_periods)?;
+        if pyth_price_max_age == 0 {
+            return Err(AstrapeError::ValueOutOfRange(0).into());
+        }
+
+        let rent = Rent::get()?;
+        // Initialize authority account
+        let authority_signer_seeds: &[&[_]] = &[AUTHORITY_SEED, &[authority_bump]];
+        invoke_signed(
+            &system_instruction::create_account(
+                admin_info.key,
+                authority_account.key,
+                rent.minimum_balance(0),
+                0,
+                program_id,
+            ),
+            &[
+                admin_info.clone(),
+                authority_account.clone(),
+                system_program_info.clone(),
+            ],
+            &[authority_signer_seeds],
+        )?;
+
+        // Initialize config account
+        let config_signer_seeds: &[&[_]] = &[CONFIG_SEED, &[config_bump]];
+        let config_size = AstrapeConfig::LEN;
+        msg!("Config size: {}", config_size);
+        let config_lamports = rent.minimum_balance(config_size).max(1);
+
+        invoke_signed(
+            &system_instruction::create_account(
+                admin_info.key,
+                config_info.key,
+                config_lamports,
+                config_size as u64,
+                program_id,
+            ),
+            &[
+                admin_info.clone(),
+                config_info.clone(),
+                system_program_info.clone(),
+            ],
+            &[config_signer_seeds],
+        )?;
+
+        // Initialize pool state account
+        invoke_signed(
+            &system_instruction::create_account(
+                admin_info.key,
+                pool_state_account.key,
+                rent.minimum_balance(PoolState::LEN).max(1),
+                PoolState::LEN as u64,
+                program_id,
+            ),
+            &[
+                admin_info.clone(),
+                pool_state_account.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[POOL_STATE_SEED, &[pool_state_bump]]],
+        )?;
+
+        // Initialize investment ledger account
+        invoke_signed(
+            &system_instruction::create_account(
+                admin_info.key,
+                investment_ledger_account.key,
+                rent.minimum_balance(InvestmentLedger::LEN).max(1),
+                InvestmentLedger::LEN as u64,
+                program_id,
+            ),
+            &[
+                admin_info.clone(),
+                investment_ledger_account.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[INVESTMENT_LEDGER_SEED, &[investment_ledger_bump]]],
+        )?;
+
+        // Initialize withdrawal queue account
+        invoke_signed(
+            &system_instruction::create_account(
+                admin_info.key,
+                withdrawal_queue_account.key,
+                rent.minimum_balance(WithdrawalQueue::LEN).max(1),
+                WithdrawalQueue::LEN as u64,
+                program_id,
+            ),
+            &[
+                admin_info.clone(),
+                withdrawal_queue_account.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[WITHDRAWAL_QUEUE_SEED, &[withdrawal_queue_bump]]],
+        )?;
+
+        // Create interest pool ATA - the ATA will be owned by the authority PDA
+        invoke(
+            &ata_instruction::create_associated_token_account(
+                admin_info.key,        // Payer
+                authority_account.key, // Owner of the new account (authority PDA)
+                &interest_mint,        // Mint
+                &spl_token::id(),      // Token program ID
+            ),
+            &[
+                admin_info.clone(),            // Payer
+                interest_pool_account.clone(), // Associated token account
+                authority_account.clone(),     // Owner of the new account (authority PDA)
+                interest_mint_account.clone(), // Mint account
+                system_program_info.clone(),   // System program
+                token_program_info.clone(),    // Token program
+                ata_program_info.clone(),      // Associated token program
+            ],
+        )?;
+
+        // Create collateral pool ATA - the ATA will be owned by the authority PDA
+        invoke(
+            &ata_instruction::create_associated_token_account(
+                admin_info.key,        // Payer
+                authority_account.key, // Owner of the new account (authority PDA)
+                &collateral_mint,      // Mint
+                &spl_token::id(),      // Token program ID
+            ),
+            &[
+                admin_info.clone(),              // Payer
+                collateral_pool_account.clone(), // Associated token account
+                authority_account.clone(),       // Owner of the new account (authority PDA)
+                collateral_mint_account.clone(), // Mint account
+                system_program_info.clone(),     // System program
+                token_program_info.clone(),      // Token program
+                ata_program_info.clone(),        // Associated token program
+            ],
+        )?;
+
+        // create withdrawal pool account and initialize token account
+        let token_account_size = TokenAccount::LEN;
+        let token_account_lamports = rent.minimum_balance(token_account_size).max(1);
+        let withdrawal_pool_signer_seeds: &[&[_]] =
+            &[WITHDRAWAL_POOL_SEED, &[withdrawal_pool_bump]];
+        invoke_signed(
+            &system_instruction::create_account(
+                admin_info.key,
+                withdrawal_pool_account.key,
+                token_account_lamports,
+                token_account_size as u64,
+                token_program_info.key,
+            ),
+            &[
+                admin_info.clone(),
+                withdrawal_pool_account.clone(),
+                authority_account.clone(),
+                system_program_info.clone(),
+            ],
+            &[withdrawal_pool_signer_seeds],
+        )?;
+        invoke(
+            &token_instruction::initialize_account(
+                &spl_token::id(),
+                withdrawal_pool_account.key,
+                collateral_mint_account.key,
+                authority_account.key,
+            )?,
+            &[
+                withdrawal_pool_account.clone(),
+                collateral_mint_account.clone(),
+                authority_account.clone(),
+                rent_account_info.clone(),
+            ],
+            // &[withdrawal_pool_signer_seeds],
+        )?;
+
+        // create treasury account and initialize token account for the interest mint
+        invoke_signed(
+            &system_instruction::create_account(
+                admin_info.key,
+                treasury_account.key,
+                token_account_lamports,
+                token_account_size as u64,
+                token_program_info.key,
+            ),
+            &[
+                admin_info.clone(),
+                treasury_account.clone(),
+                system_program_info.clone(),
+            ],
+            &[&[TREASURY_SEED, &[treasury_bump]]],
+        )?;
+        invoke(
+            &token_instruction::initialize_account(
+                &spl_token::id(),
+                treasury_account.key,
+                interest_mint_account.key,
+                authority_account.key,
+            )?,
+            &[
+                treasury_account.clone(),
+                interest_mint_account.clone(),
+                authority_account.clone(),
+                rent_account_info.clone(),
+            ],
+        )?;
+
+        // Initialize config with provided values
+        let config = AstrapeConfig {
+            admin: *admin_info.key,
+            pending_admin: None,
+            multisig_enabled: false,
+            paused: PauseFlags::default(),
+            interest_mint,
+            collateral_mint,
+            interest_decimals,
+            collateral_decimals,
+            pyth_feed_id,
+            pyth_price_max_age,
+            min_commission_rate,
+            max_commission_rate,
+            min_deposit_amount,
+            max_deposit_amount,
+            pool_capacity: None,
+            withdrawal_sla: None,
+            late_payment_rate: 0,
+            commission_revenue: 0,
+            early_exit_policy: EarlyExitPolicy::LinearUnearned,
+            deposit_periods,
+        };
+        let mut config_data = config_info.data.borrow_mut();
+        let mut dst = &mut config_data[..];
+        config.serialize(&mut dst)?;
+
+        let pool_state = PoolState::default();
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        let investment_ledger = InvestmentLedger::default();
+        let mut investment_ledger_data = investment_ledger_account.data.borrow_mut();
+        let mut dst = &mut investment_ledger_data[..];
+        investment_ledger.serialize(&mut dst)?;
+
+        let withdrawal_queue = WithdrawalQueue::default();
+        let mut withdrawal_queue_data = withdrawal_queue_account.data.borrow_mut();
+        let mut dst = &mut withdrawal_queue_data[..];
+        withdrawal_queue.serialize(&mut dst)?;
+
+        Ok(())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn process_update_config(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        update: ConfigUpdate,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+
+        // Verify config PDA
+        let _config_bump = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+
+        let mut config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        // Verify admin
+        Self::check_admin(admin_info, &config)?;
+        Self::check_multisig_disabled(&config)?;
+
+        let pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        Self::apply_config_update(&mut config, update, &pool_state)?;
+
+        let mut config_data = config_info.data.borrow_mut();
+        let mut dst = &mut config_data[..];
+        config.serialize(&mut dst)?;
+        Ok(())
+    }
+
+    fn apply_config_update(
+        config: &mut AstrapeConfig,
+        update: ConfigUpdate,
+        pool_state: &PoolState,
+    ) -> ProgramResult {
+        let ConfigUpdate {
+            param,
+            deposit_period,
+            pyth_price_max_age,
+            min_commission_rate,
+            max_commission_rate,
+            min_deposit_amount,
+            max_deposit_amount,
+            deposit_periods,
+            pyth_feed_id,
+            early_exit_policy,
+            pool_capacity,
+            withdrawal_sla,
+            late_payment_rate,
+        } = update;
+
+        // Update config based on parameter, an absent value leaves it unchanged
+        let mut value = None;
+        match param {
+            0 => {
+                if let Some(deposit_period) = deposit_period {
+                    Self::check_deposit_period(&deposit_period)?;
+                    Self::check_tenor_capacity(&deposit_period, pool_state)?;
+                    if let Some(i) = config
+                        .deposit_periods
+                        .iter()
+                        .position(|tenor| tenor.period == deposit_period.period)
+                    {
+                        config.deposit_periods[i] = deposit_period;
+                    } else if config.deposit_periods.len() < MAX_DEPOSIT_PERIODS {
+                        config.deposit_periods.push(deposit_period);
+                    } else {
+                        msg!("Deposit periods are full, max={}", MAX_DEPOSIT_PERIODS);
+                        return Err(AstrapeError::InvalidInput.into());
+                    }
+                    msg!("Updated deposit period to {:?}", deposit_period);
+                    value = Some(ConfigValue::DepositPeriod(deposit_period));
+                }
+            }
+            1 => {
+                if let Some(pyth_price_max_age) = pyth_price_max_age {
+                    if pyth_price_max_age == 0 {
+                        msg!("Price factor cannot be zero");
+                        return Err(AstrapeError::ValueOutOfRange(0).into());
+                    }
+                    config.pyth_price_max_age = pyth_price_max_age;
+                    msg!("Updated pyth price max age to {}", pyth_price_max_age);
+                    value = Some(ConfigValue::PythPriceMaxAge(pyth_price_max_age));
+                }
+            }
+            2 => {
+                if let Some(min_rate) = min_commission_rate {
+                    if min_rate > config.max_commission_rate {
+                        msg!(
+                            "Min commission rate {} exceeds max rate {}",
+                            min_rate,
+                            config.max_commission_rate
+                        );
+                        return Err(AstrapeError::ValueOutOfRange(min_rate).into());
+                    }
+                    config.min_commission_rate = min_rate;
+                    msg!("Updated min commission rate to {}", min_rate);
+                    value = Some(ConfigValue::MinCommissionRate(min_rate));
+                }
+            }
+            3 => {
+                if let Some(max_rate) = max_commission_rate {
+                    if max_rate < config.min_commission_rate {
+                        msg!(
+                            "Max commission rate {} is less than min rate {}",
+                            max_rate,
+                            config.min_commission_rate
+                        );
+                        return Err(AstrapeError::ValueOutOfRange(max_rate).into());
+                    }
+                    if max_rate > RATE_DENOMINATOR {
+                        msg!("Max commission rate {} exceeds 100%", max_rate);
+                        return Err(AstrapeError::InvalidInput.into());
+                    }
+                    config.max_commission_rate = max_rate;
+                    msg!("Updated max commission rate to {}", max_rate);
+                    value = Some(ConfigValue::MaxCommissionRate(max_rate));
+                }
+            }
+            4 => {
+                if let Some(min_amount) = min_deposit_amount {
+                    if min_amount > config.max_deposit_amount {
+                        msg!(
+                            "Min deposit amount {} exceeds max amount {}",
+                            min_amount,
+                            config.max_deposit_amount
+                        );
+                        return Err(AstrapeError::ValueOutOfRange(min_amount).into());
+                    }
+                    config.min_deposit_amount = min_amount;
+                    msg!("Updated min deposit amount to {}", min_amount);
+                    value = Some(ConfigValue::MinDepositAmount(min_amount));
+                }
+            }
+            5 => {
+                if let Some(max_amount) = max_deposit_amount {
+                    if max_amount < config.min_deposit_amount {
+                        msg!(
+                            "Max deposit amount {} is less than min amount {}",
+                            max_amount,
+                            config.min_deposit_amount
+                        );
+                        return Err(AstrapeError::ValueOutOfRange(max_amount).into());
+                    }
+                    config.max_deposit_amount = max_amount;
+                    msg!("Updated max deposit amount to {}", max_amount);
+                    value = Some(ConfigValue::MaxDepositAmount(max_amount));
+                }
+            }
+            6 => {
+                if let Some(periods) = deposit_periods {
+                    Self::check_deposit_periods(&periods)?;
+                    for deposit_period in &periods {
+                        Self::check_tenor_capacity(deposit_period, pool_state)?;
+                    }
+                    config.deposit_periods = periods.clone();
+                    msg!("Updated deposit periods to {:?}", periods);
+                    value = Some(ConfigValue::DepositPeriods(periods));
+                }
+            }
+            7 => {
+                if let Some(feed_id) = pyth_feed_id {
+                    config.pyth_feed_id = feed_id;
+                    msg!("Updated pyth feed id to {:?}", feed_id);
+                    value = Some(ConfigValue::PythFeedId(feed_id));
+                }
+            }
+            8 => {
+                if let Some(policy) = early_exit_policy {
+                    Self::check_early_exit_policy(&policy)?;
+                    msg!("Updated early exit policy to {:?}", policy);
+                    config.early_exit_policy = policy.clone();
+                    value = Some(ConfigValue::EarlyExitPolicy(policy));
+                }
+            }
+            9 => {
+                if let Some(capacity) = pool_capacity {
+                    Self::check_capacity_covers("pool", capacity, pool_state.total_locked)?;
+                    config.pool_capacity = capacity;
+                    msg!("Updated pool capacity to {:?}", capacity);
+                    value = Some(ConfigValue::PoolCapacity(capacity));
+                }
+            }
+            10 => {
+                if let Some(sla) = withdrawal_sla {
+                    if sla == Some(0) {
+                        msg!("Withdrawal SLA cannot be zero slots");
+                        return Err(AstrapeError::InvalidInput.into());
+                    }
+                    config.withdrawal_sla = sla;
+                    msg!("Updated withdrawal SLA to {:?}", sla);
+                    value = Some(ConfigValue::WithdrawalSla(sla));
+                }
+            }
+            11 => {
+                if let Some(rate) = late_payment_rate {
+                    if rate > RATE_DENOMINATOR {
+                        msg!("Late payment rate {} exceeds 100%", rate);
+                        return Err(AstrapeError::InvalidInput.into());
+                    }
+                    config.late_payment_rate = rate;
+                    msg!("Updated late payment rate to {}", rate);
+                    value = Some(ConfigValue::LatePaymentRate(rate));
+                }
+            }
+            _ => {
+                msg!("Invalid config parameter: {}", param);
+                return Err(AstrapeError::InvalidConfigParam(param).into());
+            }
+        }
+
+        if let Some(value) = value {
+            AstrapeEvent::ConfigUpdated(ConfigUpdatedEvent { param, value }).emit();
+        }
+        Ok(())
+    }
+
+    fn process_admin_withdraw_collateral_for_investment(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+        strategy: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let admin_token_account = next_account_info(account_info_iter)?;
+        let collateral_pool_account = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let ata_program_info = next_account_info(account_info_iter)?;
+        let investment_ledger_account = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+        let authority_bump = Self::check_pda(
+            "authority",
+            authority_info.key,
+            &[AUTHORITY_SEED],
+            program_id,
+        )?;
+
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        // Verify admin
+        Self::check_admin(admin_info, &config).with_context("Admin must be signer")?;
+        Self::check_multisig_disabled(&config)?;
+
+        Self::check_ata(
+            "collateral pool",
+            collateral_pool_account.key,
+            authority_info.key,
+            &config.collateral_mint,
+        )?;
+
+        // Create admin's ATA if it doesn't exist
+        if admin_token_account.data_is_empty() {
+            invoke(
+                &ata_instruction::create_associated_token_account(
+                    admin_info.key,
+                    admin_info.key,
+                    &config.collateral_mint,
+                    &spl_token::id(),
+                ),
+                &[
+                    admin_info.clone(),
+                    admin_token_account.clone(),
+                    system_program_info.clone(),
+                    token_program_info.clone(),
+                    ata_program_info.clone(),
+                ],
+            )?;
+        }
+
+        Self::withdraw_collateral_for_investment(
+            authority_info,
+            authority_bump,
+            collateral_pool_account,
+            admin_token_account,
+            amount,
+        )?;
+        Self::record_investment(investment_ledger_account, program_id, strategy, amount)
+    }
+
+    fn withdraw_collateral_for_investment<'a>(
+        authority_info: &AccountInfo<'a>,
+        authority_bump: u8,
+        collateral_pool_account: &AccountInfo<'a>,
+        destination_account: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        if amount == 0 {
+            msg!("Investment amount cannot be zero");
+            return Err(AstrapeError::InvalidInput.into());
+        }
+        // Get pool's collateral balance
+        let balance = TokenAccount::unpack(&collateral_pool_account.data.borrow())?.amount;
+        if amount > balance {
+            msg!(
+                "Investment amount exceeds pool balance: amount={}, balance={}",
+                amount,
+                balance
+            );
+            return Err(AstrapeError::InsufficientPoolBalance(balance))
+                .with_context("Insufficient pool balance");
+        }
+
+        // Transfer collateral to the destination
+        Self::transfer_from_pool(
+            authority_info,
+            authority_bump,
+            collateral_pool_account,
+            destination_account,
+            amount,
+        )
+    }
+
+    // Track the collateral deployed in each strategy
+    fn record_investment(
+        investment_ledger_account: &AccountInfo,
+        program_id: &Pubkey,
+        strategy: u64,
+        amount: u64,
+    ) -> ProgramResult {
+        let _ = Self::check_pda(
+            "investment ledger",
+            investment_ledger_account.key,
+            &[INVESTMENT_LEDGER_SEED],
+            program_id,
+        )?;
+        let mut investment_ledger =
+            InvestmentLedger::unpack(&investment_ledger_account.data.borrow())?;
+
+        let clock = Clock::get()?;
+        investment_ledger
+            .record_withdrawal(strategy, amount, clock.slot)
+            .with_context("Investment ledger is full")?;
+
+        let mut investment_ledger_data = investment_ledger_account.data.borrow_mut();
+        let mut dst = &mut investment_ledger_data[..];
+        investment_ledger.serialize(&mut dst)?;
+
+        AstrapeEvent::AdminFunds(AdminFundsEvent {
+            movement: FundMovement::CollateralInvested { strategy },
+            amount,
+        })
+        .emit();
+
+        msg!(
+            "Withdrew {} collateral for strategy {}, deployed {}",
+            amount,
+            strategy,
+            investment_ledger.deployed(strategy)
+        );
+        Ok(())
+    }
+
+    fn process_admin_return_collateral(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        strategy: u64,
+        principal: u64,
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let admin_token_account = next_account_info(account_info_iter)?;
+        let collateral_pool_account = next_account_info(account_info_iter)?;
+        let investment_ledger_account = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+        let _ = Self::check_pda(
+            "authority",
+            authority_info.key,
+            &[AUTHORITY_SEED],
+            program_id,
+        )?;
+
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        // Verify admin
+        Self::check_admin(admin_info, &config).with_context("Admin must be signer")?;
+        Self::check_multisig_disabled(&config)?;
+
+        Self::check_ata(
+            "collateral pool",
+            collateral_pool_account.key,
+            authority_info.key,
+            &config.collateral_mint,
+        )?;
+
+        Self::record_return(
+            investment_ledger_account,
+            program_id,
+            strategy,
+            principal,
+            amount,
+        )?;
+        Self::return_collateral(
+            admin_info,
+            admin_token_account,
+            collateral_pool_account,
+            amount,
+        )
+    }
+
+    // Settle returned collateral against the collateral deployed in the strategy
+    fn record_return(
+        investment_ledger_account: &AccountInfo,
+        program_id: &Pubkey,
+        strategy: u64,
+        principal: u64,
+        amount: u64,
+    ) -> ProgramResult {
+        let _ = Self::check_pda(
+            "investment ledger",
+            investment_ledger_account.key,
+            &[INVESTMENT_LEDGER_SEED],
+            program_id,
+        )?;
+        if principal == 0 && amount == 0 {
+            msg!("Returned principal and amount cannot both be zero");
+            return Err(AstrapeError::InvalidInput.into());
+        }
+
+        let mut investment_ledger =
+            InvestmentLedger::unpack(&investment_ledger_account.data.borrow())?;
+        let clock = Clock::get()?;
+        investment_ledger
+            .record_return(strategy, principal, amount, clock.slot)
+            .with_context("Principal exceeds the collateral deployed in the strategy")?;
+
+        let mut investment_ledger_data = investment_ledger_account.data.borrow_mut();
+        let mut dst = &mut investment_ledger_data[..];
+        investment_ledger.serialize(&mut dst)?;
+
+        AstrapeEvent::AdminFunds(AdminFundsEvent {
+            movement: FundMovement::CollateralReturned {
+                strategy,
+                principal,
+            },
+            amount,
+        })
+        .emit();
+
+        msg!(
+            "Returned {} collateral for {} principal from strategy {}, deployed {}",
+            amount,
+            principal,
+            strategy,
+            investment_ledger.deployed(strategy)
+        );
+        Ok(())
+    }
+
+    // Credit the returned collateral to the pool
+    fn return_collateral<'a>(
+        owner_info: &AccountInfo<'a>,
+        source_account: &AccountInfo<'a>,
+        collateral_pool_account: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        if amount == 0 {
+            return Ok(());
+        }
+        invoke(
+            &token_instruction::transfer(
+                &spl_token::id(),
+                source_account.key,
+                collateral_pool_account.key,
+                owner_info.key,
+                &[],
+                amount,
+            )?,
+            &[
+                owner_info.clone(),
+                collateral_pool_account.clone(),
+                source_account.clone(),
+            ],
+        )
+    }
+
+    // Transfer tokens out of a token account owned by the authority PDA
+    fn transfer_from_pool<'a>(
+        authority_info: &AccountInfo<'a>,
+        authority_bump: u8,
+        pool_account: &AccountInfo<'a>,
+        destination_account: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        invoke_signed(
+            &token_instruction::transfer(
+                &spl_token::id(),
+                pool_account.key,
+                destination_account.key,
+                authority_info.key,
+                &[],
+                amount,
+            )?,
+            &[
+                authority_info.clone(),
+                pool_account.clone(),
+                destination_account.clone(),
+            ],
+            &[&[AUTHORITY_SEED, &[authority_bump]]],
+        )
+    }
+
+    fn process_admin_prepare_withdrawal(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        deposit_index: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let admin_token_account = next_account_info(account_info_iter)?;
+        let withdrawal_pool_account = next_account_info(account_info_iter)?;
+        let user_info = next_account_info(account_info_iter)?;
+        let user_deposit_account = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+        let withdrawal_queue_account = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        // Verify admin
+        Self::check_admin(admin_info, &config).with_context("Admin must be signer")?;
+
+        let _ = Self::check_pda(
+            "withdrawal pool",
+            withdrawal_pool_account.key,
+            &[WITHDRAWAL_POOL_SEED],
+            program_id,
+        )?;
+
+        let mut pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        let mut withdrawal_queue =
+            Self::load_withdrawal_queue(withdrawal_queue_account, program_id)?;
+        let deposit = Self::load_user_deposit(user_deposit_account, deposit_index, program_id)?;
+        let amount = Self::mark_withdrawal_ready(
+            user_info,
+            user_deposit_account,
+            deposit,
+            &mut pool_state,
+            &mut withdrawal_queue,
+        )?;
+
+        // Transfer collateral from admin to withdrawal pool (not the main pool)
+        Self::transfer_to_withdrawal_pool(
+            admin_info,
+            admin_token_account,
+            withdrawal_pool_account,
+            amount,
+        )?;
+
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        let mut withdrawal_queue_data = withdrawal_queue_account.data.borrow_mut();
+        let mut dst = &mut withdrawal_queue_data[..];
+        withdrawal_queue.serialize(&mut dst)?;
+
+        msg!(
+            "Prepared withdrawal of deposit position {}, pending {}",
+            deposit_index,
+            withdrawal_queue.total_pending
+        );
+
+        Ok(())
+    }
+
+    fn process_admin_prepare_withdrawal_batch(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let admin_token_account = next_account_info(account_info_iter)?;
+        let withdrawal_pool_account = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+        let withdrawal_queue_account = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        // Verify admin
+        Self::check_admin(admin_info, &config).with_context("Admin must be signer")?;
+
+        let _ = Self::check_pda(
+            "withdrawal pool",
+            withdrawal_pool_account.key,
+            &[WITHDRAWAL_POOL_SEED],
+            program_id,
+        )?;
+
+        let mut pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        let mut withdrawal_queue =
+            Self::load_withdrawal_queue(withdrawal_queue_account, program_id)?;
+
+        // The remaining accounts are (user, deposit) pairs in the order of the withdrawal queue
+        let mut count: u64 = 0;
+        let mut total_amount: u64 = 0;
+        while let Some(user_info) = account_info_iter.next() {
+            let user_deposit_account = next_account_info(account_info_iter)?;
+            let deposit = Self::unpack_user_deposit(user_deposit_account, program_id)?;
+            let amount = Self::mark_withdrawal_ready(
+                user_info,
+                user_deposit_account,
+                deposit,
+                &mut pool_state,
+                &mut withdrawal_queue,
+            )?;
+            total_amount = total_amount
+                .checked_add(amount)
+                .ok_or(AstrapeError::ArithmeticOverflow)?;
+            count += 1;
+        }
+        if count == 0 {
+            msg!("No deposit positions to prepare");
+            return Err(AstrapeError::InvalidInput.into());
+        }
+
+        // One transfer covers every position of the batch
+        Self::transfer_to_withdrawal_pool(
+            admin_info,
+            admin_token_account,
+            withdrawal_pool_account,
+            total_amount,
+        )?;
+
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        let mut withdrawal_queue_data = withdrawal_queue_account.data.borrow_mut();
+        let mut dst = &mut withdrawal_queue_data[..];
+        withdrawal_queue.serialize(&mut dst)?;
+
+        msg!(
+            "Prepared withdrawal of {} deposit positions for {}, pending {}",
+            count,
+            total_amount,
+            withdrawal_queue.total_pending
+        );
+
+        Ok(())
+    }
+
+    // Moves the oldest queued position to `WithdrawReady` and returns its collateral amount
+    fn mark_withdrawal_ready(
+        user_info: &AccountInfo,
+        user_deposit_account: &AccountInfo,
+        mut deposit: UserDeposit,
+        pool_state: &mut PoolState,
+        withdrawal_queue: &mut WithdrawalQueue,
+    ) -> Result<u64, ProgramError> {
+        Self::check_position_owner(user_info, &deposit)?;
+
+        // Verify deposit state
+        if deposit.state != UserDepositState::WithdrawRequested {
+            let current_state = deposit.state as u8;
+            let expected_state = UserDepositState::WithdrawRequested as u8;
+            msg!(
+                "Invalid deposit state: current={:?}, expected={:?}",
+                deposit.state,
+                UserDepositState::WithdrawRequested
+            );
+            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
+        }
+
+        // Withdrawals are prepared first come, first served
+        withdrawal_queue
+            .pop(user_deposit_account.key, &mut deposit)
+            .with_context("Prepare the oldest withdrawal request first")?;
+
+        pool_state.remove_position(&deposit)?;
+
+        // Update deposit state
+        deposit.state = UserDepositState::WithdrawReady;
+        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
+        let mut dst = &mut user_deposit_data[..];
+        deposit.serialize(&mut dst)?;
+
+        pool_state.add_position(&deposit)?;
+
+        AstrapeEvent::WithdrawalPrepared(WithdrawalPreparedEvent {
+            deposit: *user_deposit_account.key,
+            amount: deposit.amount,
+        })
+        .emit();
+        Ok(deposit.amount)
+    }
+
+    // Transfer collateral from the admin's token account to the withdrawal pool
+    fn transfer_to_withdrawal_pool<'a>(
+        admin_info: &AccountInfo<'a>,
+        admin_token_account: &AccountInfo<'a>,
+        withdrawal_pool_account: &AccountInfo<'a>,
+        amount: u64,
+    ) -> ProgramResult {
+        invoke(
+            &token_instruction::transfer(
+                &spl_token::id(),
+                admin_token_account.key,
+                withdrawal_pool_account.key,
+                admin_info.key,
+                &[],
+                amount,
+            )?,
+            &[
+                admin_token_account.clone(),
+                withdrawal_pool_account.clone(),
+                admin_info.clone(),
+            ],
+        )
+    }
+
+    fn process_admin_deposit_interest(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let admin_interest_account = next_account_info(account_info_iter)?;
+        let interest_pool_account = next_account_info(account_info_iter)?;
+        let _system_program_info = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+        let _ata_program_info = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+        let _ = Self::check_pda(
+            "authority",
+            authority_info.key,
+            &[AUTHORITY_SEED],
+            program_id,
+        )?;
+
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        // Verify admin
+        Self::check_admin(admin_info, &config).with_context("Admin must be signer")?;
+
+        Self::check_ata(
+            "interest pool",
+            interest_pool_account.key,
+            authority_info.key,
+            &config.interest_mint,
+        )?;
+        // Transfer interest to pool
+        invoke(
+            &token_instruction::transfer(
+                &spl_token::id(),
+                admin_interest_account.key,
+                interest_pool_account.key,
+                admin_info.key,
+                &[],
+                amount,
+            )?,
+            &[
+                admin_info.clone(),
+                interest_pool_account.clone(),
+                admin_interest_account.clone(),
+            ],
+        )?;
+
+        AstrapeEvent::AdminFunds(AdminFundsEvent {
+            movement: FundMovement::InterestDeposited,
+            amount,
+        })
+        .emit();
+
+        Ok(())
+    }
+
+    fn process_admin_withdraw_interest(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let admin_interest_account = next_account_info(account_info_iter)?;
+        let interest_pool_account = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let ata_program_info = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+        let authority_bump = Self::check_pda(
+            "authority",
+            authority_info.key,
+            &[AUTHORITY_SEED],
+            program_id,
+        )?;
+
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        // Verify admin
+        Self::check_admin(admin_info, &config).with_context("Admin must be signer")?;
+
+        Self::check_multisig_disabled(&config)?;
+
+        Self::check_ata(
+            "admin interest",
+            admin_interest_account.key,
+            admin_info.key,
+            &config.interest_mint,
+        )?;
+        Self::check_ata(
+            "interest pool",
+            interest_pool_account.key,
+            authority_info.key,
+            &config.interest_mint,
+        )?;
+
+        // Create admin's ATA if it doesn't exist
+        if admin_interest_account.data_is_empty() {
+            invoke(
+                &ata_instruction::create_associated_token_account(
+                    admin_info.key,
+                    admin_info.key,
+                    &config.interest_mint,
+                    &spl_token::id(),
+                ),
+                &[
+                    admin_info.clone(),
+                    admin_interest_account.clone(),
+                    system_program_info.clone(),
+                    token_program_info.clone(),
+                    ata_program_info.clone(),
+                ],
+            )?;
+        }
+
+        // Transfer interest from pool to admin
+        Self::transfer_from_pool(
+            authority_info,
+            authority_bump,
+            interest_pool_account,
+            admin_interest_account,
+            amount,
+        )?;
+
+        AstrapeEvent::AdminFunds(AdminFundsEvent {
+            movement: FundMovement::InterestWithdrawn,
+            amount,
+        })
+        .emit();
+
+        Ok(())
+    }
+
+    fn process_deposit_collateral(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+        deposit_period: u64,
+        commission_rate: u64,
+        rollover: bool,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let user_token_account = next_account_info(account_info_iter)?;
+        let user_deposit_counter_account = next_account_info(account_info_iter)?;
+        let user_deposit_account = next_account_info(account_info_iter)?;
+        let collateral_pool_account = next_account_info(account_info_iter)?;
+        let user_interest_account = next_account_info(account_info_iter)?;
+        let interest_pool_account = next_account_info(account_info_iter)?;
+        let treasury_account = next_account_info(account_info_iter)?;
+        let pyth_price_feed_account = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let receipt_mint_account = next_account_info(account_info_iter)?;
+        let user_receipt_account = next_account_info(account_info_iter)?;
+        let ata_program_info = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+        let authority_bump = Self::check_pda(
+            "authority",
+            authority_info.key,
+            &[AUTHORITY_SEED],
+            program_id,
+        )?;
+        let user_deposit_counter_bump = Self::check_pda(
+            "user deposit counter",
+            user_deposit_counter_account.key,
+            &[USER_DEPOSIT_COUNTER_SEED, user_info.key.as_ref()],
+            program_id,
+        )?;
+        let _ = Self::check_pda(
+            "treasury",
+            treasury_account.key,
+            &[TREASURY_SEED],
+            program_id,
+        )?;
+
+        let mut config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        Self::check_ata(
+            "interest pool",
+            interest_pool_account.key,
+            authority_info.key,
+            &config.interest_mint,
+        )?;
+
+        Self::check_ata(
+            "collateral pool",
+            collateral_pool_account.key,
+            authority_info.key,
+            &config.collateral_mint,
+        )?;
+
+        let rent = Rent::get()?;
+
+        // Create the user's deposit counter on the first deposit
+        if user_deposit_counter_account.data_is_empty() {
+            let size = UserDepositCounter::LEN;
+            let lamports = rent.minimum_balance(size).max(1);
+            invoke_signed(
+                &system_instruction::create_account(
+                    user_info.key,
+                    user_deposit_counter_account.key,
+                    lamports,
+                    size as u64,
+                    program_id,
+                ),
+                &[
+                    user_info.clone(),
+                    user_deposit_counter_account.clone(),
+                    system_program_info.clone(),
+                ],
+                &[&[
+                    USER_DEPOSIT_COUNTER_SEED,
+                    user_info.key.as_ref(),
+                    &[user_deposit_counter_bump],
+                ]],
+            )?;
+        }
+        let mut deposit_counter =
+            UserDepositCounter::try_from_slice(&user_deposit_counter_account.data.borrow())?;
+
+        // The new position takes the next free index of the user
+        let deposit_index = deposit_counter.deposit_count;
+        let user_deposit_bump = Self::check_pda(
+            "user deposit",
+            user_deposit_account.key,
+            &[
+                USER_DEPOSIT_SEED,
+                user_info.key.as_ref(),
+                &deposit_index.to_le_bytes(),
+            ],
+            program_id,
+        )?;
+
+        if user_deposit_account.data_is_empty() {
+            let size = UserDeposit::LEN;
+            let lamports = rent.minimum_balance(size).max(1);
+            invoke_signed(
+                &system_instruction::create_account(
+                    user_info.key,
+                    user_deposit_account.key,
+                    lamports,
+                    size as u64,
+                    program_id,
+                ),
+                &[
+                    user_info.clone(),
+                    user_deposit_account.clone(),
+                    system_program_info.clone(),
+                ],
+                &[&[
+                    USER_DEPOSIT_SEED,
+                    user_info.key.as_ref(),
+                    &deposit_index.to_le_bytes(),
+                    &[user_deposit_bump],
+                ]],
+            )?;
+        } else {
+            return Err(AstrapeError::UserDepositAlreadyExists.into());
+        }
+
+        let clock = Clock::get()?;
+
+        // Verify deposit amount is within limits
+        if amount < config.min_deposit_amount || amount > config.max_deposit_amount {
+            msg!(
+                "Deposit amount out of bounds: amount={}, min={}, max={}",
+                amount,
+                config.min_deposit_amount,
+                config.max_deposit_amount
+            );
+            return Err(AstrapeError::DepositAmountOutOfBounds(amount).into());
+        }
+        if commission_rate < config.min_commission_rate
+            || commission_rate > config.max_commission_rate
+        {
+            msg!(
+                "Commission rate out of bounds: rate={}, min={}, max={}",
+                commission_rate,
+                config.min_commission_rate,
+                config.max_commission_rate
+            );
+            return Err(AstrapeError::CommissionRateOutOfBounds(commission_rate).into());
+        }
+
+        // Verify lock period is valid and still open for deposits
+        let tenor = match config.deposit_period(deposit_period) {
+            Some(tenor) if tenor.enabled => *tenor,
+            tenor => {
+                msg!(
+                    "Invalid lock period: period={}, tenor={:?}, allowed periods={:?}",
+                    deposit_period,
+                    tenor,
+                    config.deposit_periods
+                );
+                return Err(AstrapeError::InvalidLockPeriod(deposit_period).into());
+            }
+        };
+
+        // The pool and the tenor must have room for the deposit
+        let mut pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        Self::check_capacity(&config, &tenor, &pool_state, amount)?;
+
+        let price = Self::read_collateral_price(pyth_price_feed_account, &config)?;
+
+        let interest_amount = Self::calculate_interest_amount(
+            amount,
+            price,
+            commission_rate,
+            deposit_period,
+            &config,
+        )?;
+        let commission_amount = Self::calculate_commission_amount(
+            amount,
+            price,
+            commission_rate,
+            deposit_period,
+            &config,
+        )?;
+
+        // Transfer collateral to pool
+        invoke(
+            &token_instruction::transfer(
+                &spl_token::id(),
+                user_token_account.key,
+                collateral_pool_account.key,
+                user_info.key,
+                &[],
+                amount,
+            )?,
+            &[
+                user_info.clone(),
+                collateral_pool_account.clone(),
+                user_token_account.clone(),
+            ],
+        )?;
+
+        // Transfer interest to user
+        invoke_signed(
+            &token_instruction::transfer(
+                &spl_token::id(),
+                interest_pool_account.key,
+                user_interest_account.key,
+                authority_info.key,
+                &[],
+                interest_amount,
+            )?,
+            &[
+                authority_info.clone(),
+                interest_pool_account.clone(),
+                user_interest_account.clone(),
+            ],
+            &[&[AUTHORITY_SEED, &[authority_bump]]],
+        )?;
+
+        // Move the commission to the treasury
+        if commission_amount > 0 {
+            Self::transfer_from_pool(
+                authority_info,
+                authority_bump,
+                interest_pool_account,
+                treasury_account,
+                commission_amount,
+            )?;
+        }
+        config.commission_revenue = config
+            .commission_revenue
+            .checked_add(commission_amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let mut dst = &mut config_data[..];
+        config.serialize(&mut dst)?;
+
+        // Add user deposit
+        let user_deposit = UserDeposit {
+            discriminator: UserDeposit::DISCRIMINATOR,
+            depositor: *user_info.key,
+            deposit_index,
+            owner: *user_info.key,
+            amount,
+            deposit_slot: clock.slot,
+            unlock_slot: clock.slot + deposit_period,
+            interest_received: interest_amount,
+            state: UserDepositState::Deposited,
+            commission_rate,
+            deposit_period,
+            rollover,
+            queued: None,
+        };
+        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
+        let mut dst = &mut user_deposit_data[..];
+        user_deposit.serialize(&mut dst)?;
+
+        Self::create_receipt_mint(
+            program_id,
+            user_info,
+            user_deposit_account,
+            receipt_mint_account,
+            authority_info,
+            system_program_info,
+        )?;
+        if user_receipt_account.data_is_empty() {
+            invoke(
+                &ata_instruction::create_associated_token_account(
+                    user_info.key,
+                    user_info.key,
+                    receipt_mint_account.key,
+                    &spl_token::id(),
+                ),
+                &[
+                    user_info.clone(),
+                    user_receipt_account.clone(),
+                    user_info.clone(),
+                    receipt_mint_account.clone(),
+                    system_program_info.clone(),
+                    token_program_info.clone(),
+                    ata_program_info.clone(),
+                ],
+            )?;
+        }
+        Self::mint_receipt(
+            authority_info,
+            authority_bump,
+            receipt_mint_account,
+            user_receipt_account,
+        )?;
+
+        pool_state.add_position(&user_deposit)?;
+        pool_state.total_interest_paid = pool_state
+            .total_interest_paid
+            .checked_add(interest_amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        deposit_counter.deposit_count = deposit_index
+            .checked_add(1)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        let mut deposit_counter_data = user_deposit_counter_account.data.borrow_mut();
+        let mut dst = &mut deposit_counter_data[..];
+        deposit_counter.serialize(&mut dst)?;
+
+        AstrapeEvent::Deposit(DepositEvent {
+            deposit: *user_deposit_account.key,
+            owner: *user_info.key,
+            amount,
+            deposit_period,
+            unlock_slot: user_deposit.unlock_slot,
+            interest: interest_amount,
+            commission: commission_amount,
+        })
+        .emit();
+
+        msg!(
+            "Created deposit position {}, commission {}",
+            deposit_index,
+            commission_amount
+        );
+
+        Ok(())
+    }
+
+    fn process_extend_deposit(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        deposit_index: u64,
+        amount: u64,
+        deposit_period: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let user_token_account = next_account_info(account_info_iter)?;
+        let user_deposit_account = next_account_info(account_info_iter)?;
+        let collateral_pool_account = next_account_info(account_info_iter)?;
+        let user_interest_account = next_account_info(account_info_iter)?;
+        let interest_pool_account = next_account_info(account_info_iter)?;
+        let treasury_account = next_account_info(account_info_iter)?;
+        let pyth_price_feed_account = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+        let user_receipt_account = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+        let authority_bump = Self::check_pda(
+            "authority",
+            authority_info.key,
+            &[AUTHORITY_SEED],
+            program_id,
+        )?;
+        let _ = Self::check_pda(
+            "treasury",
+            treasury_account.key,
+            &[TREASURY_SEED],
+            program_id,
+        )?;
+
+        let mut config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        Self::check_ata(
+            "interest pool",
+            interest_pool_account.key,
+            authority_info.key,
+            &config.interest_mint,
+        )?;
+        Self::check_ata(
+            "collateral pool",
+            collateral_pool_account.key,
+            authority_info.key,
+            &config.collateral_mint,
+        )?;
+
+        let clock = Clock::get()?;
+        let mut deposit = Self::load_held_deposit(
+            user_info,
+            user_receipt_account,
+            user_deposit_account,
+            deposit_index,
+            program_id,
+        )?;
+
+        // Verify deposit state
+        if deposit.state != UserDepositState::Deposited {
+            let current_state = deposit.state as u8;
+            let expected_state = UserDepositState::Deposited as u8;
+            msg!(
+                "Invalid deposit state: current={:?}, expected={:?}",
+                deposit.state,
+                UserDepositState::Deposited
+            );
+            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
+        }
+
+        // The new lock starts now and cannot end before the current one
+        let tenor = match config.deposit_period(deposit_period) {
+            Some(tenor) if tenor.enabled => *tenor,
+            tenor => {
+                msg!(
+                    "Invalid lock period: period={}, tenor={:?}, allowed periods={:?}",
+                    deposit_period,
+                    tenor,
+                    config.deposit_periods
+                );
+                return Err(AstrapeError::InvalidLockPeriod(deposit_period).into());
+            }
+        };
+        let unlock_slot = clock
+            .slot
+            .checked_add(deposit_period)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        if unlock_slot < deposit.unlock_slot {
+            msg!(
+                "Lock cannot be shortened: unlock slot={}, current unlock slot={}",
+                unlock_slot,
+                deposit.unlock_slot
+            );
+            return Err(AstrapeError::InvalidLockPeriod(deposit_period).into());
+        }
+        if amount == 0 && unlock_slot == deposit.unlock_slot {
+            msg!("Extension adds neither collateral nor lock time");
+            return Err(AstrapeError::InvalidInput.into());
+        }
+
+        let total_amount = deposit
+            .amount
+            .checked_add(amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        if total_amount > config.max_deposit_amount {
+            msg!(
+                "Deposit amount out of bounds: amount={}, max={}",
+                total_amount,
+                config.max_deposit_amount
+            );
+            return Err(AstrapeError::DepositAmountOutOfBounds(total_amount).into());
+        }
+
+        // Collateral added to the pool or moved to another tenor needs free capacity there
+        let mut pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        pool_state.remove_position(&deposit)?;
+        if amount > 0 || deposit_period != deposit.deposit_period {
+            Self::check_capacity(&config, &tenor, &pool_state, total_amount)?;
+        }
+
+        let price = Self::read_collateral_price(pyth_price_feed_account, &config)?;
+        let (interest_amount, commission_amount) = Self::calculate_extension_interest(
+            &deposit,
+            amount,
+            unlock_slot,
+            clock.slot,
+            price,
+            tenor.interest_rate,
+            &config,
+        )?;
+
+        // Transfer the added collateral to pool
+        if amount > 0 {
+            invoke(
+                &token_instruction::transfer(
+                    &spl_token::id(),
+                    user_token_account.key,
+                    collateral_pool_account.key,
+                    user_info.key,
+                    &[],
+                    amount,
+                )?,
+                &[
+                    user_info.clone(),
+                    collateral_pool_account.clone(),
+                    user_token_account.clone(),
+                ],
+            )?;
+        }
+
+        // Pay the interest of the increment and move its commission to the treasury
+        Self::transfer_from_pool(
+            authority_info,
+            authority_bump,
+            interest_pool_account,
+            user_interest_account,
+            interest_amount,
+        )?;
+        if commission_amount > 0 {
+            Self::transfer_from_pool(
+                authority_info,
+                authority_bump,
+                interest_pool_account,
+                treasury_account,
+                commission_amount,
+            )?;
+        }
+        config.commission_revenue = config
+            .commission_revenue
+            .checked_add(commission_amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let mut dst = &mut config_data[..];
+        config.serialize(&mut dst)?;
+
+        Self::extend_position(
+            &mut deposit,
+            amount,
+            deposit_period,
+            unlock_slot,
+            interest_amount,
+            clock.slot,
+        )?;
+        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
+        let mut dst = &mut user_deposit_data[..];
+        deposit.serialize(&mut dst)?;
+
+        pool_state.add_position(&deposit)?;
+        pool_state.total_interest_paid = pool_state
+            .total_interest_paid
+            .checked_add(interest_amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        AstrapeEvent::Relock(RelockEvent {
+            deposit: *user_deposit_account.key,
+            amount: deposit.amount,
+            deposit_period,
+            unlock_slot,
+            interest: interest_amount,
+            commission: commission_amount,
+            rollover: false,
+        })
+        .emit();
+
+        msg!(
+            "Extended deposit position {}, interest {}, commission {}",
+            deposit_index,
+            interest_amount,
+            commission_amount
+        );
+
+        Ok(())
+    }
+
+    fn process_set_rollover(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        deposit_index: u64,
+        rollover: bool,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user_info = next_account_info(account_info_iter)?;
+        let user_deposit_account = next_account_info(account_info_iter)?;
+        let user_receipt_account = next_account_info(account_info_iter)?;
+
+        let mut deposit = Self::load_held_deposit(
+            user_info,
+            user_receipt_account,
+            user_deposit_account,
+            deposit_index,
+            program_id,
+        )?;
+
+        // Verify deposit state
+        if deposit.state != UserDepositState::Deposited {
+            let current_state = deposit.state as u8;
+            let expected_state = UserDepositState::Deposited as u8;
+            msg!(
+                "Invalid deposit state: current={:?}, expected={:?}",
+                deposit.state,
+                UserDepositState::Deposited
+            );
+            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
+        }
+
+        deposit.rollover = rollover;
+        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
+        let mut dst = &mut user_deposit_data[..];
+        deposit.serialize(&mut dst)?;
+
+        AstrapeEvent::RolloverUpdated(RolloverUpdatedEvent {
+            deposit: *user_deposit_account.key,
+            rollover,
+        })
+        .emit();
+
+        msg!(
+            "Set rollover of deposit position {} to {}",
+            deposit_index,
+            rollover
+        );
+
+        Ok(())
+    }
+
+    fn process_rollover(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        deposit_index: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let user_deposit_account = next_account_info(account_info_iter)?;
+        let user_interest_account = next_account_info(account_info_iter)?;
+        let interest_pool_account = next_account_info(account_info_iter)?;
+        let treasury_account = next_account_info(account_info_iter)?;
+        let pyth_price_feed_account = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+        let user_receipt_account = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+        let authority_bump = Self::check_pda(
+            "authority",
+            authority_info.key,
+            &[AUTHORITY_SEED],
+            program_id,
+        )?;
+        let _ = Self::check_pda(
+            "treasury",
+            treasury_account.key,
+            &[TREASURY_SEED],
+            program_id,
+        )?;
+
+        let mut config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        Self::check_ata(
+            "interest pool",
+            interest_pool_account.key,
+            authority_info.key,
+            &config.interest_mint,
+        )?;
+        // The crank is permissionless, so the interest can only go to the ATA of the receipt holder
+        Self::check_ata(
+            "user interest",
+            user_interest_account.key,
+            user_info.key,
+            &config.interest_mint,
+        )?;
+
+        let clock = Clock::get()?;
+        Self::check_receipt(
+            user_info,
+            user_receipt_account,
+            user_deposit_account,
+            program_id,
+        )?;
+        let mut deposit = Self::load_user_deposit(user_deposit_account, deposit_index, program_id)?;
+        deposit.owner = *user_info.key;
+
+        // Verify deposit state
+        if deposit.state != UserDepositState::Deposited {
+            let current_state = deposit.state as u8;
+            let expected_state = UserDepositState::Deposited as u8;
+            msg!(
+                "Invalid deposit state: current={:?}, expected={:?}",
+                deposit.state,
+                UserDepositState::Deposited
+            );
+            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
+        }
+        if !deposit.rollover {
+            msg!(
+                "Deposit position {} did not opt in to rollover",
+                deposit_index
+            );
+            return Err(AstrapeError::InvalidInput.into());
+        }
+        if clock.slot < deposit.unlock_slot {
+            msg!(
+                "Deposit is still locked: current slot={}, unlock slot={}",
+                clock.slot,
+                deposit.unlock_slot
+            );
+            return Err(AstrapeError::NotUnlockedYet(clock.slot, deposit.unlock_slot).into());
+        }
+
+        // The same tenor must still be open for deposits
+        let deposit_period = deposit.deposit_period;
+        let tenor = match config.deposit_period(deposit_period) {
+            Some(tenor) if tenor.enabled => *tenor,
+            tenor => {
+                msg!(
+                    "Invalid lock period: period={}, tenor={:?}, allowed periods={:?}",
+                    deposit_period,
+                    tenor,
+                    config.deposit_periods
+                );
+                return Err(AstrapeError::InvalidLockPeriod(deposit_period).into());
+            }
+        };
+
+        // The new term locks the collateral again, so it needs free capacity like a deposit
+        let mut pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        pool_state.remove_position(&deposit)?;
+        Self::check_capacity(&config, &tenor, &pool_state, deposit.amount)?;
+
+        let price = Self::read_collateral_price(pyth_price_feed_account, &config)?;
+        let interest_amount = Self::calculate_interest_amount(
+            deposit.amount,
+            price,
+            deposit.commission_rate,
+            deposit_period,
+            &config,
+        )?;
+        let commission_amount = Self::calculate_commission_amount(
+            deposit.amount,
+            price,
+            deposit.commission_rate,
+            deposit_period,
+            &config,
+        )?;
+
+        Self::transfer_from_pool(
+            authority_info,
+            authority_bump,
+            interest_pool_account,
+            user_interest_account,
+            interest_amount,
+        )?;
+        if commission_amount > 0 {
+            Self::transfer_from_pool(
+                authority_info,
+                authority_bump,
+                interest_pool_account,
+                treasury_account,
+                commission_amount,
+            )?;
+        }
+        config.commission_revenue = config
+            .commission_revenue
+            .checked_add(commission_amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let mut dst = &mut config_data[..];
+        config.serialize(&mut dst)?;
+
+        // The previous lock has been fully earned, the new one starts now
+        deposit.deposit_slot = clock.slot;
+        deposit.unlock_slot = clock
+            .slot
+            .checked_add(deposit_period)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        deposit.interest_received = interest_amount;
+        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
+        let mut dst = &mut user_deposit_data[..];
+        deposit.serialize(&mut dst)?;
+
+        pool_state.add_position(&deposit)?;
+        pool_state.total_interest_paid = pool_state
+            .total_interest_paid
+            .checked_add(interest_amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        AstrapeEvent::Relock(RelockEvent {
+            deposit: *user_deposit_account.key,
+            amount: deposit.amount,
+            deposit_period,
+            unlock_slot: deposit.unlock_slot,
+            interest: interest_amount,
+            commission: commission_amount,
+            rollover: true,
+        })
+        .emit();
+
+        msg!(
+            "Rolled over deposit position {}, interest {}, commission {}",
+            deposit_index,
+            interest_amount,
+            commission_amount
+        );
+
+        Ok(())
+    }
+
+    fn process_transfer_position(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        deposit_index: u64,
+        new_owner: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let owner_info = next_account_info(account_info_iter)?;
+        let user_deposit_account = next_account_info(account_info_iter)?;
+        let new_owner_info = next_account_info(account_info_iter)?;
+        let receipt_mint_account = next_account_info(account_info_iter)?;
+        let owner_receipt_account = next_account_info(account_info_iter)?;
+        let new_owner_receipt_account = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+        let token_program_info = next_account_info(account_info_iter)?;
+        let ata_program_info = next_account_info(account_info_iter)?;
+
+        if *new_owner_info.key != new_owner {
+            msg!(
+                "Invalid new owner account: expected={}, actual={}",
+                new_owner,
+                new_owner_info.key
+            );
+            return Err(AstrapeError::InvalidInput.into());
+        }
+        let _ = Self::check_pda(
+            "receipt mint",
+            receipt_mint_account.key,
+            &[RECEIPT_MINT_SEED, user_deposit_account.key.as_ref()],
+            program_id,
+        )?;
+
+        let mut deposit = Self::load_held_deposit(
+            owner_info,
+            owner_receipt_account,
+            user_deposit_account,
+            deposit_index,
+            program_id,
+        )?;
+
+        if deposit.state == UserDepositState::WithdrawCompleted {
+            msg!(
+                "Completed deposit position {} cannot be transferred",
+                deposit_index
+            );
+            return Err(AstrapeError::InvalidDepositState(
+                deposit.state as u8,
+                UserDepositState::Deposited as u8,
+            )
+            .into());
+        }
+        if new_owner == deposit.owner {
+            msg!("Position is already owned by {}", new_owner);
+            return Err(AstrapeError::InvalidInput.into());
+        }
+
+        // The receipt moves with the position
+        if new_owner_receipt_account.data_is_empty() {
+            invoke(
+                &ata_instruction::create_associated_token_account(
+                    owner_info.key,
+                    new_owner_info.key,
+                    receipt_mint_account.key,
+                    &spl_token::id(),
+                ),
+                &[
+                    owner_info.clone(),
+                    new_owner_receipt_account.clone(),
+                    new_owner_info.clone(),
+                    receipt_mint_account.clone(),
+                    system_program_info.clone(),
+                    token_program_info.clone(),
+                    ata_program_info.clone(),
+                ],
+            )?;
+        }
+        invoke(
+            &token_instruction::transfer_checked(
+                &spl_token::id(),
+                owner_receipt_account.key,
+                receipt_mint_account.key,
+                new_owner_receipt_account.key,
+                owner_info.key,
+                &[],
+                1,
+                0,
+            )?,
+            &[
+                owner_receipt_account.clone(),
+                receipt_mint_account.clone(),
+                new_owner_receipt_account.clone(),
+                owner_info.clone(),
+            ],
+        )?;
+
+        deposit.owner = new_owner;
+        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
+        let mut dst = &mut user_deposit_data[..];
+        deposit.serialize(&mut dst)?;
+
+        AstrapeEvent::PositionTransferred(PositionTransferredEvent {
+            deposit: *user_deposit_account.key,
+            from: *owner_info.key,
+            to: new_owner,
+        })
+        .emit();
+
+        msg!(
+            "Transferred deposit position {} from {} to {}",
+            deposit_index,
+            owner_info.key,
+            new_owner
+        );
 
         Ok(())
     }
 
-    fn process_update_config(
+    fn process_request_withdrawal_early(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        param: u8,
-        base_interest_rate: Option<u64>,
-        pyth_price_max_age: Option<u64>,
-        min_commission_rate: Option<u64>,
-        max_commission_rate: Option<u64>,
-        min_deposit_amount: Option<u64>,
-        max_deposit_amount: Option<u64>,
-        deposit_periods: Option<Vec<u64>>,
+        deposit_index: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        let admin_info = next_account_info(account_info_iter)?;
+        let user_info = next_account_info(account_info_iter)?;
         let config_info = next_account_info(account_info_iter)?;
+        let authority_info = next_account_info(account_info_iter)?;
+        let user_deposit_account = next_account_info(account_info_iter)?;
+        let user_interest_account = next_account_info(account_info_iter)?;
+        let interest_pool_account = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+        let user_receipt_account = next_account_info(account_info_iter)?;
+        let withdrawal_queue_account = next_account_info(account_info_iter)?;
+        let queue_tail_account = next_account_info(account_info_iter)?;
 
-        // Verify admin
-        if !admin_info.is_signer || config_feature::admin::id() != *admin_info.key {
-            msg!("Admin must be a signer");
-            return Err(AstrapeError::SignerRequired.into());
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+        let _ = Self::check_pda(
+            "authority",
+            authority_info.key,
+            &[AUTHORITY_SEED],
+            program_id,
+        )?;
+
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        Self::check_ata(
+            "interest pool",
+            interest_pool_account.key,
+            authority_info.key,
+            &config.interest_mint,
+        )?;
+
+        let clock = Clock::get()?;
+        // Find user's deposit
+        let mut deposit = Self::load_held_deposit(
+            user_info,
+            user_receipt_account,
+            user_deposit_account,
+            deposit_index,
+            program_id,
+        )?;
+
+        // Verify deposit state
+        if deposit.state != UserDepositState::Deposited {
+            let current_state = deposit.state as u8;
+            let expected_state = UserDepositState::Deposited as u8;
+            msg!(
+                "Invalid deposit state: current={:?}, expected={:?}",
+                deposit.state,
+                UserDepositState::Deposited
+            );
+            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
         }
 
-        // Verify config PDA
-        let _config_bump = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+        let interest_to_return =
+            Self::calculate_interest_to_return(&deposit, &config.early_exit_policy, clock.slot)?;
 
-        // Update config based on parameter
-        let mut config = AstrapeConfig::try_from_slice(&config_info.data.borrow())?;
-        match param {
-            0 => {
-                if let Some(rate) = base_interest_rate {
-                    config.base_interest_rate = rate;
-                    msg!("Updated base interest rate to {}", rate);
-                }
-            }
-            1 => {
-                if let Some(pyth_price_max_age) = pyth_price_max_age {
-                    if pyth_price_max_age == 0 {
-                        msg!("Price factor cannot be zero");
-                        return Err(AstrapeError::ValueOutOfRange(0).into());
-                    }
-                    config.pyth_price_max_age = pyth_price_max_age;
-                    msg!("Updated pyth price max age to {}", pyth_price_max_age);
-                }
-            }
-            2 => {
-                if let Some(min_rate) = min_commission_rate {
-                    if min_rate > config.max_commission_rate {
-                        msg!(
-                            "Min commission rate {} exceeds max rate {}",
-                            min_rate,
-                            config.max_commission_rate
-                        );
-                        return Err(AstrapeError::ValueOutOfRange(min_rate).into());
-                    }
-                    config.min_commission_rate = min_rate;
-                    msg!("Updated min commission rate to {}", min_rate);
-                }
-            }
-            3 => {
-                if let Some(max_rate) = max_commission_rate {
-                    if max_rate < config.min_commission_rate {
-                        msg!(
-                            "Max commission rate {} is less than min rate {}",
-                            max_rate,
-                            config.min_commission_rate
-                        );
-                        return Err(AstrapeError::ValueOutOfRange(max_rate).into());
-                    }
-                    config.max_commission_rate = max_rate;
-                    msg!("Updated max commission rate to {}", max_rate);
-                }
-            }
-            4 => {
-                if let Some(min_amount) = min_deposit_amount {
-                    if min_amount > config.max_deposit_amount {
-                        msg!(
-                            "Min deposit amount {} exceeds max amount {}",
-                            min_amount,
-                            config.max_deposit_amount
-                        );
-                        return Err(AstrapeError::ValueOutOfRange(min_amount).into());
-                    }
-                    config.min_deposit_amount = min_amount;
-                    msg!("Updated min deposit amount to {}", min_amount);
-                }
-            }
-            5 => {
-                if let Some(max_amount) = max_deposit_amount {
-                    if max_amount < config.min_deposit_amount {
-                        msg!(
-                            "Max deposit amount {} is less than min amount {}",
-                            max_amount,
-                            config.min_deposit_amount
-                        );
-                        return Err(AstrapeError::ValueOutOfRange(max_amount).into());
-                    }
-                    config.max_deposit_amount = max_amount;
-                    msg!("Updated max deposit amount to {}", max_amount);
-                }
-            }
-            6 => {
-                if let Some(periods) = deposit_periods {
-                    if periods.is_empty() {
-                        msg!("Deposit periods cannot be empty");
-                        return Err(AstrapeError::InvalidInput.into());
-                    }
-                    config.deposit_periods = periods.clone();
-                    msg!("Updated deposit periods to {:?}", periods);
-                }
-            }
-            _ => {
-                msg!("Invalid config parameter: {}", param);
-                return Err(AstrapeError::InvalidConfigParam(param).into());
-            }
+        // Check if user has enough interest tokens to return
+        let user_interest_balance =
+            TokenAccount::unpack(&user_interest_account.data.borrow())?.amount;
+        if user_interest_balance < interest_to_return {
+            msg!(
+                "Insufficient interest balance: balance={}, required={}",
+                user_interest_balance,
+                interest_to_return
+            );
+            return Err(AstrapeError::InsufficientInterestBalance(user_interest_balance).into());
         }
 
-        let mut config_data = config_info.data.borrow_mut();
-        let mut dst = &mut config_data[..];
-        config.serialize(&mut dst)?;
+        // Transfer interest back to pool
+        invoke(
+            &token_instruction::transfer(
+                &spl_token::id(),
+                user_interest_account.key,
+                interest_pool_account.key,
+                user_info.key,
+                &[],
+                interest_to_return,
+            )?,
+            &[
+                user_info.clone(),
+                interest_pool_account.clone(),
+                user_interest_account.clone(),
+            ],
+        )?;
+
+        let mut pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        pool_state.remove_position(&deposit)?;
+
+        // Update deposit state
+        deposit.state = UserDepositState::WithdrawRequested;
+        Self::enqueue_withdrawal(
+            withdrawal_queue_account,
+            queue_tail_account,
+            program_id,
+            user_deposit_account.key,
+            &mut deposit,
+            clock.slot,
+            true,
+        )?;
+        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
+        let mut dst = &mut user_deposit_data[..];
+        deposit.serialize(&mut dst)?;
+
+        pool_state.add_position(&deposit)?;
+        pool_state.total_interest_refunded = pool_state
+            .total_interest_refunded
+            .checked_add(interest_to_return)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        AstrapeEvent::Refund(RefundEvent {
+            deposit: *user_deposit_account.key,
+            amount: deposit.amount,
+            refund: interest_to_return,
+        })
+        .emit();
+
+        msg!(
+            "Requested early withdrawal of deposit position {}",
+            deposit_index
+        );
+
         Ok(())
     }
 
-    fn process_admin_withdraw_collateral_for_investment(
+    fn process_request_partial_withdrawal_early(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
+        deposit_index: u64,
+        amount: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        let admin_info = next_account_info(account_info_iter)?;
+        let user_info = next_account_info(account_info_iter)?;
         let config_info = next_account_info(account_info_iter)?;
         let authority_info = next_account_info(account_info_iter)?;
-        let admin_token_account = next_account_info(account_info_iter)?;
+        let user_deposit_account = next_account_info(account_info_iter)?;
+        let user_interest_account = next_account_info(account_info_iter)?;
+        let interest_pool_account = next_account_info(account_info_iter)?;
+        let user_collateral_account = next_account_info(account_info_iter)?;
         let collateral_pool_account = next_account_info(account_info_iter)?;
-        let system_program_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
-        let ata_program_info = next_account_info(account_info_iter)?;
-
-        // Verify admin
-        if !admin_info.is_signer || config_feature::admin::id() != *admin_info.key {
-            return Err(AstrapeError::InvalidAdmin(0)).with_context("Admin must be signer");
-        }
+        let _token_program_info = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+        let user_receipt_account = next_account_info(account_info_iter)?;
 
         let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
         let authority_bump = Self::check_pda(
@@ -660,213 +3588,417 @@ impl Processor {
             program_id,
         )?;
 
-        let config = AstrapeConfig::try_from_slice(&config_info.data.borrow())?;
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
 
-        let _ = Self::check_ata(
+        Self::check_ata(
+            "interest pool",
+            interest_pool_account.key,
+            authority_info.key,
+            &config.interest_mint,
+        )?;
+        Self::check_ata(
             "collateral pool",
             collateral_pool_account.key,
             authority_info.key,
             &config.collateral_mint,
         )?;
+        Self::check_ata(
+            "user collateral",
+            user_collateral_account.key,
+            user_info.key,
+            &config.collateral_mint,
+        )?;
 
-        // Create admin's ATA if it doesn't exist
-        if admin_token_account.data_is_empty() {
-            invoke(
-                &ata_instruction::create_associated_token_account(
-                    admin_info.key,
-                    admin_info.key,
-                    &config.collateral_mint,
-                    &spl_token::id(),
-                ),
-                &[
-                    admin_info.clone(),
-                    admin_token_account.clone(),
-                    system_program_info.clone(),
-                    token_program_info.clone(),
-                    ata_program_info.clone(),
-                ],
-            )?;
+        let clock = Clock::get()?;
+        let mut deposit = Self::load_held_deposit(
+            user_info,
+            user_receipt_account,
+            user_deposit_account,
+            deposit_index,
+            program_id,
+        )?;
+
+        // Verify deposit state
+        if deposit.state != UserDepositState::Deposited {
+            let current_state = deposit.state as u8;
+            let expected_state = UserDepositState::Deposited as u8;
+            msg!(
+                "Invalid deposit state: current={:?}, expected={:?}",
+                deposit.state,
+                UserDepositState::Deposited
+            );
+            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
         }
 
-        // Get pool's collateral balance
-        let amount = TokenAccount::unpack(&collateral_pool_account.data.borrow())?.amount;
-        if amount == 0 {
-            return Err(AstrapeError::InsufficientPoolBalance(0))
-                .with_context("Insufficient pool balance");
+        let mut pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        pool_state.remove_position(&deposit)?;
+
+        // The position shrinks in place, keeping its lock
+        let withdrawn = Self::split_deposit(&mut deposit, amount, config.min_deposit_amount)?;
+
+        // Only the interest of the withdrawn part is refunded
+        let interest_to_return =
+            Self::calculate_interest_to_return(&withdrawn, &config.early_exit_policy, clock.slot)?;
+
+        let user_interest_balance =
+            TokenAccount::unpack(&user_interest_account.data.borrow())?.amount;
+        if user_interest_balance < interest_to_return {
+            msg!(
+                "Insufficient interest balance: balance={}, required={}",
+                user_interest_balance,
+                interest_to_return
+            );
+            return Err(AstrapeError::InsufficientInterestBalance(user_interest_balance).into());
+        }
+
+        // The withdrawn collateral is paid out of the collateral pool right away
+        let pool_balance = TokenAccount::unpack(&collateral_pool_account.data.borrow())?.amount;
+        if pool_balance < withdrawn.amount {
+            msg!(
+                "Insufficient collateral pool balance: balance={}, required={}",
+                pool_balance,
+                withdrawn.amount
+            );
+            return Err(AstrapeError::InsufficientPoolBalance(pool_balance).into());
         }
 
-        // Transfer collateral to admin
-        invoke_signed(
+        // Transfer interest back to pool
+        invoke(
             &token_instruction::transfer(
                 &spl_token::id(),
-                collateral_pool_account.key,
-                admin_token_account.key,
-                authority_info.key,
+                user_interest_account.key,
+                interest_pool_account.key,
+                user_info.key,
                 &[],
-                amount,
+                interest_to_return,
             )?,
             &[
-                authority_info.clone(),
-                collateral_pool_account.clone(),
-                admin_token_account.clone(),
+                user_info.clone(),
+                interest_pool_account.clone(),
+                user_interest_account.clone(),
             ],
-            &[&[AUTHORITY_SEED, &[authority_bump]]],
         )?;
 
+        Self::transfer_from_pool(
+            authority_info,
+            authority_bump,
+            collateral_pool_account,
+            user_collateral_account,
+            withdrawn.amount,
+        )?;
+
+        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
+        let mut dst = &mut user_deposit_data[..];
+        deposit.serialize(&mut dst)?;
+
+        pool_state.add_position(&deposit)?;
+        pool_state.total_interest_refunded = pool_state
+            .total_interest_refunded
+            .checked_add(interest_to_return)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        AstrapeEvent::Refund(RefundEvent {
+            deposit: *user_deposit_account.key,
+            amount: withdrawn.amount,
+            refund: interest_to_return,
+        })
+        .emit();
+
+        msg!(
+            "Withdrew {} of deposit position {}, {} remaining",
+            amount,
+            deposit_index,
+            deposit.amount
+        );
+
         Ok(())
     }
 
-    fn process_admin_prepare_withdrawal(
+    fn process_request_withdrawal(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
+        deposit_index: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        let admin_info = next_account_info(account_info_iter)?;
-        let config_info = next_account_info(account_info_iter)?;
-        let admin_token_account = next_account_info(account_info_iter)?;
-        let withdrawal_pool_account = next_account_info(account_info_iter)?;
         let user_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
         let user_deposit_account = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
-
-        // Verify admin
-        // For testing purposes, just verify the signer
-        if !admin_info.is_signer || config_feature::admin::id() != *admin_info.key {
-            return Err(AstrapeError::InvalidAdmin(0)).with_context("Admin must be signer");
-        }
+        let user_receipt_account = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+        let withdrawal_queue_account = next_account_info(account_info_iter)?;
+        let queue_tail_account = next_account_info(account_info_iter)?;
 
         let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
-        let _ = Self::check_pda(
-            "withdrawal pool",
-            withdrawal_pool_account.key,
-            &[WITHDRAWAL_POOL_SEED],
-            program_id,
-        )?;
-        let _ = Self::check_pda(
-            "user deposit",
-            user_deposit_account.key,
-            &[user_info.key.as_ref()],
+
+        // The holder of the receipt redeems the position
+        let mut deposit = Self::load_held_deposit(
+            user_info,
+            user_receipt_account,
+            user_deposit_account,
+            deposit_index,
             program_id,
         )?;
 
-        // Find user's deposit
-        let mut deposit = UserDeposit::try_from_slice(&user_deposit_account.data.borrow())?;
+        let clock = Clock::get()?;
 
-        // Verify deposit state
-        if deposit.state != UserDepositState::WithdrawRequested {
+        if deposit.state != UserDepositState::Deposited {
             let current_state = deposit.state as u8;
-            let expected_state = UserDepositState::WithdrawRequested as u8;
+            let expected_state = UserDepositState::Deposited as u8;
             msg!(
                 "Invalid deposit state: current={:?}, expected={:?}",
                 deposit.state,
-                UserDepositState::WithdrawRequested
+                UserDepositState::Deposited
             );
+
             return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
         }
 
-        // Transfer collateral from admin to withdrawal pool (not the main pool)
-        invoke(
-            &token_instruction::transfer(
-                &spl_token::id(),
-                admin_token_account.key,
-                withdrawal_pool_account.key,
-                admin_info.key,
-                &[],
-                deposit.amount,
-            )?,
-            &[
-                admin_token_account.clone(),
-                withdrawal_pool_account.clone(),
-                admin_info.clone(),
-            ],
-        )?;
+        if clock.slot < deposit.unlock_slot {
+            msg!(
+                "Deposit is not yet unlocked: slot={}, unlock_slot={}",
+                clock.slot,
+                deposit.unlock_slot
+            );
+            return Err(AstrapeError::NotUnlockedYet(clock.slot, deposit.unlock_slot).into());
+        }
 
-        // Update deposit state
-        deposit.state = UserDepositState::WithdrawReady;
+        let mut pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        pool_state.remove_position(&deposit)?;
+
+        deposit.state = UserDepositState::WithdrawRequested;
+        Self::enqueue_withdrawal(
+            withdrawal_queue_account,
+            queue_tail_account,
+            program_id,
+            user_deposit_account.key,
+            &mut deposit,
+            clock.slot,
+            false,
+        )?;
         let mut user_deposit_data = user_deposit_account.data.borrow_mut();
         let mut dst = &mut user_deposit_data[..];
         deposit.serialize(&mut dst)?;
 
+        pool_state.add_position(&deposit)?;
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        msg!("Requested withdrawal of deposit position {}", deposit_index);
+
         Ok(())
     }
 
-    fn process_admin_deposit_interest(
+    fn process_withdraw_collateral(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        amount: u64,
+        deposit_index: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        let admin_info = next_account_info(account_info_iter)?;
+        let user_info = next_account_info(account_info_iter)?;
         let config_info = next_account_info(account_info_iter)?;
         let authority_info = next_account_info(account_info_iter)?;
-        let admin_interest_account = next_account_info(account_info_iter)?;
-        let interest_pool_account = next_account_info(account_info_iter)?;
-        let system_program_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
-        let ata_program_info = next_account_info(account_info_iter)?;
-
-        // Verify admin
-        // For testing purposes, just verify the signer
-        if !admin_info.is_signer || config_feature::admin::id() != *admin_info.key {
-            return Err(AstrapeError::InvalidAdmin(0)).with_context("Admin must be signer");
-        }
+        let user_deposit_account = next_account_info(account_info_iter)?;
+        let user_token_account = next_account_info(account_info_iter)?;
+        let withdrawal_pool_account = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+        let receipt_mint_account = next_account_info(account_info_iter)?;
+        let user_receipt_account = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
 
         let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
-        let _ = Self::check_pda(
+        let authority_bump = Self::check_pda(
             "authority",
             authority_info.key,
             &[AUTHORITY_SEED],
             program_id,
         )?;
+        let _ = Self::check_pda(
+            "withdrawal pool",
+            withdrawal_pool_account.key,
+            &[WITHDRAWAL_POOL_SEED],
+            program_id,
+        )?;
 
-        let config = AstrapeConfig::try_from_slice(&config_info.data.borrow())?;
+        let _config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+        // The holder of the receipt redeems the position
+        let mut deposit = Self::load_held_deposit(
+            user_info,
+            user_receipt_account,
+            user_deposit_account,
+            deposit_index,
+            program_id,
+        )?;
+        let _ = Self::check_pda(
+            "receipt mint",
+            receipt_mint_account.key,
+            &[RECEIPT_MINT_SEED, user_deposit_account.key.as_ref()],
+            program_id,
+        )?;
 
-        Self::check_ata(
-            "interest pool",
-            interest_pool_account.key,
-            authority_info.key,
-            &config.interest_mint,
+        // Verify deposit state
+        if deposit.state != UserDepositState::WithdrawReady {
+            let current_state = deposit.state as u8;
+            let expected_state = UserDepositState::WithdrawReady as u8;
+            msg!(
+                "Invalid deposit state: current={:?}, expected={:?}",
+                deposit.state,
+                UserDepositState::WithdrawReady
+            );
+            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
+        }
+
+        // Check if withdrawal pool has enough tokens
+        let pool_balance = TokenAccount::unpack(&withdrawal_pool_account.data.borrow())?.amount;
+        if pool_balance < deposit.amount {
+            msg!(
+                "Insufficient withdrawal pool balance: balance={}, required={}",
+                pool_balance,
+                deposit.amount
+            );
+            return Err(AstrapeError::InsufficientPoolBalance(pool_balance).into());
+        }
+
+        // Transfer collateral from withdrawal pool to user
+        invoke_signed(
+            &token_instruction::transfer(
+                &spl_token::id(),
+                withdrawal_pool_account.key,
+                user_token_account.key,
+                authority_info.key,
+                &[],
+                deposit.amount,
+            )?,
+            &[
+                authority_info.clone(),
+                withdrawal_pool_account.clone(),
+                user_token_account.clone(),
+            ],
+            &[&[AUTHORITY_SEED, &[authority_bump]]],
         )?;
-        // Transfer interest to pool
+
+        // Burn the receipt, the position is redeemed
         invoke(
-            &token_instruction::transfer(
+            &token_instruction::burn(
                 &spl_token::id(),
-                admin_interest_account.key,
-                interest_pool_account.key,
-                &admin_info.key,
+                user_receipt_account.key,
+                receipt_mint_account.key,
+                user_info.key,
                 &[],
-                amount,
+                1,
             )?,
             &[
-                admin_info.clone(),
-                interest_pool_account.clone(),
-                admin_interest_account.clone(),
+                user_receipt_account.clone(),
+                receipt_mint_account.clone(),
+                user_info.clone(),
             ],
         )?;
 
+        let mut pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        pool_state.remove_position(&deposit)?;
+
+        // Remove deposit
+        deposit.state = UserDepositState::WithdrawCompleted;
+        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
+        let mut dst = &mut user_deposit_data[..];
+        deposit.serialize(&mut dst)?;
+
+        pool_state.add_position(&deposit)?;
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        AstrapeEvent::WithdrawalCompleted(WithdrawalCompletedEvent {
+            deposit: *user_deposit_account.key,
+            recipient: *user_info.key,
+            amount: deposit.amount,
+            overdue: false,
+        })
+        .emit();
+
+        msg!("Withdrew deposit position {}", deposit_index);
+
         Ok(())
     }
 
-    fn process_admin_withdraw_interest(
+    // Without a withdrawal SLA the deadline never passes
+    fn check_withdrawal_overdue(
+        config: &AstrapeConfig,
+        request: &QueuedWithdrawal,
+        slot: u64,
+    ) -> Result<(), AstrapeError> {
+        let deadline = config
+            .withdrawal_sla
+            .map_or(u64::MAX, |sla| request.slot.saturating_add(sla));
+        if slot < deadline {
+            msg!(
+                "Withdrawal is not overdue: slot={}, deadline={}",
+                slot,
+                deadline
+            );
+            return Err(AstrapeError::WithdrawalNotOverdue(deadline));
+        }
+        Ok(())
+    }
+
+    /// Late payment compensation for one missed withdrawal deadline, in base units of
+    /// the interest mint: `interest_received * late_payment_rate / 1000`, rounded down
+    pub fn calculate_late_payment(
+        deposit: &UserDeposit,
+        late_payment_rate: u64,
+    ) -> Result<u64, AstrapeError> {
+        let compensation = (deposit.interest_received as u128)
+            .checked_mul(late_payment_rate as u128)
+            .ok_or(AstrapeError::ArithmeticOverflow)?
+            / RATE_DENOMINATOR as u128;
+        u64::try_from(compensation).map_err(|_| AstrapeError::ArithmeticOverflow)
+    }
+
+    // Loads a queued deposit in `WithdrawRequested` and returns it with its queue node
+    fn load_queued_deposit(
+        user_deposit_account: &AccountInfo,
+        deposit_index: u64,
+        program_id: &Pubkey,
+    ) -> Result<(UserDeposit, QueuedWithdrawal), ProgramError> {
+        let deposit = Self::load_user_deposit(user_deposit_account, deposit_index, program_id)?;
+        if deposit.state != UserDepositState::WithdrawRequested {
+            let current_state = deposit.state as u8;
+            let expected_state = UserDepositState::WithdrawRequested as u8;
+            msg!(
+                "Invalid deposit state: current={:?}, expected={:?}",
+                deposit.state,
+                UserDepositState::WithdrawRequested
+            );
+            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
+        }
+        let node = deposit
+            .queued
+            .ok_or(AstrapeError::NoDepositFound)
+            .with_context("Deposit position is not in the withdrawal queue")?;
+        Ok((deposit, node))
+    }
+
+    fn process_withdraw_overdue_collateral(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        amount: u64,
+        deposit_index: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        let admin_info = next_account_info(account_info_iter)?;
+        let user_info = next_account_info(account_info_iter)?;
         let config_info = next_account_info(account_info_iter)?;
         let authority_info = next_account_info(account_info_iter)?;
-        let admin_interest_account = next_account_info(account_info_iter)?;
-        let interest_pool_account = next_account_info(account_info_iter)?;
-        let system_program_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
-        let ata_program_info = next_account_info(account_info_iter)?;
-
-        // Verify admin
-        // For testing purposes, just verify the signer
-        if !admin_info.is_signer || config_feature::admin::id() != *admin_info.key {
-            return Err(AstrapeError::InvalidAdmin(0)).with_context("Admin must be signer");
-        }
+        let user_deposit_account = next_account_info(account_info_iter)?;
+        let user_token_account = next_account_info(account_info_iter)?;
+        let collateral_pool_account = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+        let receipt_mint_account = next_account_info(account_info_iter)?;
+        let user_receipt_account = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+        let withdrawal_queue_account = next_account_info(account_info_iter)?;
+        let queue_previous_account = next_account_info(account_info_iter)?;
 
         let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
         let authority_bump = Self::check_pda(
@@ -876,80 +4008,148 @@ impl Processor {
             program_id,
         )?;
 
-        let config = AstrapeConfig::try_from_slice(&config_info.data.borrow())?;
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
 
         Self::check_ata(
-            "admin interest",
-            admin_interest_account.key,
-            admin_info.key,
-            &config.interest_mint,
-        )?;
-        Self::check_ata(
-            "interest pool",
-            interest_pool_account.key,
+            "collateral pool",
+            collateral_pool_account.key,
             authority_info.key,
-            &config.interest_mint,
+            &config.collateral_mint,
         )?;
 
-        // Create admin's ATA if it doesn't exist
-        if admin_interest_account.data_is_empty() {
-            invoke(
-                &ata_instruction::create_associated_token_account(
-                    admin_info.key,
-                    admin_info.key,
-                    &config.interest_mint,
-                    &spl_token::id(),
-                ),
-                &[
-                    admin_info.clone(),
-                    admin_interest_account.clone(),
-                    system_program_info.clone(),
-                    token_program_info.clone(),
-                    ata_program_info.clone(),
-                ],
-            )?;
+        // The holder of the receipt redeems the position
+        Self::check_receipt_holder(
+            user_info,
+            user_receipt_account,
+            user_deposit_account,
+            program_id,
+        )?;
+        let _ = Self::check_pda(
+            "receipt mint",
+            receipt_mint_account.key,
+            &[RECEIPT_MINT_SEED, user_deposit_account.key.as_ref()],
+            program_id,
+        )?;
+
+        let clock = Clock::get()?;
+        let mut withdrawal_queue =
+            Self::load_withdrawal_queue(withdrawal_queue_account, program_id)?;
+        let (mut deposit, node) =
+            Self::load_queued_deposit(user_deposit_account, deposit_index, program_id)?;
+        Self::check_withdrawal_overdue(&config, &node, clock.slot)?;
+
+        // Check if collateral pool has enough tokens
+        let pool_balance = TokenAccount::unpack(&collateral_pool_account.data.borrow())?.amount;
+        if pool_balance < deposit.amount {
+            msg!(
+                "Insufficient collateral pool balance: balance={}, required={}",
+                pool_balance,
+                deposit.amount
+            );
+            return Err(AstrapeError::InsufficientPoolBalance(pool_balance))
+                .with_context("Claim the late payment compensation instead");
         }
 
-        // Transfer interest from pool to admin
-        invoke_signed(
-            &token_instruction::transfer(
+        // Transfer collateral from collateral pool to user
+        Self::transfer_from_pool(
+            authority_info,
+            authority_bump,
+            collateral_pool_account,
+            user_token_account,
+            deposit.amount,
+        )?;
+
+        // Burn the receipt, the position is redeemed
+        invoke(
+            &token_instruction::burn(
                 &spl_token::id(),
-                interest_pool_account.key,
-                admin_interest_account.key,
-                &authority_info.key,
+                user_receipt_account.key,
+                receipt_mint_account.key,
+                user_info.key,
                 &[],
-                amount,
+                1,
             )?,
             &[
-                authority_info.clone(),
-                interest_pool_account.clone(),
-                admin_interest_account.clone(),
+                user_receipt_account.clone(),
+                receipt_mint_account.clone(),
+                user_info.clone(),
             ],
-            &[&[AUTHORITY_SEED, &[authority_bump]]],
         )?;
 
+        // Unlink the position from the one queued right before it, unless it is the oldest
+        let mut previous = if withdrawal_queue.head == Some(*user_deposit_account.key) {
+            None
+        } else {
+            Some(Self::unpack_user_deposit(
+                queue_previous_account,
+                program_id,
+            )?)
+        };
+        withdrawal_queue
+            .remove(
+                user_deposit_account.key,
+                &mut deposit,
+                previous
+                    .as_mut()
+                    .map(|previous| (queue_previous_account.key, previous)),
+            )
+            .with_context("Pass the position queued right before this one")?;
+        if let Some(previous) = previous {
+            let mut previous_data = queue_previous_account.data.borrow_mut();
+            let mut dst = &mut previous_data[..];
+            previous.serialize(&mut dst)?;
+        }
+
+        let mut pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        pool_state.remove_position(&deposit)?;
+
+        // The holder who redeemed the position can close it
+        deposit.owner = *user_info.key;
+        deposit.state = UserDepositState::WithdrawCompleted;
+        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
+        let mut dst = &mut user_deposit_data[..];
+        deposit.serialize(&mut dst)?;
+
+        pool_state.add_position(&deposit)?;
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        let mut withdrawal_queue_data = withdrawal_queue_account.data.borrow_mut();
+        let mut dst = &mut withdrawal_queue_data[..];
+        withdrawal_queue.serialize(&mut dst)?;
+
+        AstrapeEvent::WithdrawalCompleted(WithdrawalCompletedEvent {
+            deposit: *user_deposit_account.key,
+            recipient: *user_info.key,
+            amount: deposit.amount,
+            overdue: true,
+        })
+        .emit();
+
+        msg!(
+            "Withdrew overdue deposit position {} from the collateral pool",
+            deposit_index
+        );
+
         Ok(())
     }
 
-    fn process_deposit_collateral(
+    fn process_claim_late_payment(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
-        amount: u64,
-        deposit_period: u64,
-        commission_rate: u64,
+        deposit_index: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
         let user_info = next_account_info(account_info_iter)?;
         let config_info = next_account_info(account_info_iter)?;
         let authority_info = next_account_info(account_info_iter)?;
-        let user_token_account = next_account_info(account_info_iter)?;
         let user_deposit_account = next_account_info(account_info_iter)?;
-        let collateral_pool_account = next_account_info(account_info_iter)?;
+        let user_receipt_account = next_account_info(account_info_iter)?;
         let user_interest_account = next_account_info(account_info_iter)?;
         let interest_pool_account = next_account_info(account_info_iter)?;
-        let pyth_price_feed_account = next_account_info(account_info_iter)?;
-        let system_program_info = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
+        let collateral_pool_account = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
 
         let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
         let authority_bump = Self::check_pda(
@@ -958,14 +4158,8 @@ impl Processor {
             &[AUTHORITY_SEED],
             program_id,
         )?;
-        let user_deposit_bump = Self::check_pda(
-            "user deposit",
-            user_deposit_account.key,
-            &[user_info.key.as_ref()],
-            program_id,
-        )?;
 
-        let config = AstrapeConfig::try_from_slice(&config_info.data.borrow())?;
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
 
         Self::check_ata(
             "interest pool",
@@ -973,7 +4167,6 @@ impl Processor {
             authority_info.key,
             &config.interest_mint,
         )?;
-
         Self::check_ata(
             "collateral pool",
             collateral_pool_account.key,
@@ -981,276 +4174,742 @@ impl Processor {
             &config.collateral_mint,
         )?;
 
-        if user_deposit_account.data_is_empty() {
-            let rent = Rent::get()?;
-            let size = UserDeposit::LEN;
-            let lamports = rent.minimum_balance(size).max(1);
-            invoke_signed(
-                &system_instruction::create_account(
-                    user_info.key,
-                    user_deposit_account.key,
-                    lamports,
-                    size as u64,
-                    program_id,
-                ),
-                &[user_info.clone(), user_deposit_account.clone()],
-                &[&[user_info.key.as_ref(), &[user_deposit_bump]]],
-            )?;
-        } else {
-            return Err(AstrapeError::UserDepositAlreadyExists.into());
+        // The holder of the receipt is owed the collateral
+        Self::check_receipt_holder(
+            user_info,
+            user_receipt_account,
+            user_deposit_account,
+            program_id,
+        )?;
+
+        if config.late_payment_rate == 0 {
+            msg!("No late payment rate is configured");
+            return Err(AstrapeError::InvalidInput.into());
         }
 
         let clock = Clock::get()?;
+        let (mut deposit, node) =
+            Self::load_queued_deposit(user_deposit_account, deposit_index, program_id)?;
+        Self::check_withdrawal_overdue(&config, &node, clock.slot)?;
 
-        // Verify deposit amount is within limits
-        if amount < config.min_deposit_amount || amount > config.max_deposit_amount {
+        // Compensation is only owed while the user cannot withdraw from the collateral pool
+        let pool_balance = TokenAccount::unpack(&collateral_pool_account.data.borrow())?.amount;
+        if pool_balance >= deposit.amount {
+            return Err(AstrapeError::PoolLiquidityAvailable(pool_balance))
+                .with_context("Withdraw the overdue collateral instead");
+        }
+
+        // A share of the interest of the position, so splitting a deposit earns no more
+        let compensation = Self::calculate_late_payment(&deposit, config.late_payment_rate)?;
+        Self::transfer_from_pool(
+            authority_info,
+            authority_bump,
+            interest_pool_account,
+            user_interest_account,
+            compensation,
+        )?;
+
+        // The deadline restarts, compensation is paid once per missed deadline
+        deposit.queued = Some(QueuedWithdrawal {
+            slot: clock.slot,
+            ..node
+        });
+        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
+        let mut dst = &mut user_deposit_data[..];
+        deposit.serialize(&mut dst)?;
+
+        AstrapeEvent::LatePayment(LatePaymentEvent {
+            deposit: *user_deposit_account.key,
+            recipient: *user_info.key,
+            compensation,
+        })
+        .emit();
+
+        msg!(
+            "Paid {} late payment compensation for deposit position {}",
+            compensation,
+            deposit_index
+        );
+
+        Ok(())
+    }
+
+    fn process_close_deposit(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        deposit_index: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let user_info = next_account_info(account_info_iter)?;
+        let user_deposit_account = next_account_info(account_info_iter)?;
+        let depositor_info = next_account_info(account_info_iter)?;
+        let user_receipt_account = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
+        let pool_state_account = next_account_info(account_info_iter)?;
+
+        let deposit =
+            Self::load_owned_deposit(user_info, user_deposit_account, deposit_index, program_id)?;
+
+        // Only fully withdrawn deposits can be closed
+        if deposit.state != UserDepositState::WithdrawCompleted {
+            let current_state = deposit.state as u8;
+            let expected_state = UserDepositState::WithdrawCompleted as u8;
             msg!(
-                "Deposit amount out of bounds: amount={}, min={}, max={}",
-                amount,
-                config.min_deposit_amount,
-                config.max_deposit_amount
+                "Invalid deposit state: current={:?}, expected={:?}",
+                deposit.state,
+                UserDepositState::WithdrawCompleted
             );
-            return Err(AstrapeError::DepositAmountOutOfBounds(amount).into());
+            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
         }
-        if commission_rate < config.min_commission_rate
-            || commission_rate > config.max_commission_rate
-        {
+
+        // The depositor paid the rent when opening the position
+        if deposit.depositor != *depositor_info.key {
             msg!(
-                "Commission rate out of bounds: rate={}, min={}, max={}",
-                commission_rate,
-                config.min_commission_rate,
-                config.max_commission_rate
+                "Invalid depositor: expected={}, actual={}",
+                deposit.depositor,
+                depositor_info.key
             );
-            return Err(AstrapeError::CommissionRateOutOfBounds(commission_rate).into());
+            return Err(AstrapeError::InvalidPDA(1).into());
+        }
+
+        // The receipt was burned on redemption, so the holder's empty receipt account can go too
+        let (receipt_mint, _) = Pubkey::find_program_address(
+            &[RECEIPT_MINT_SEED, user_deposit_account.key.as_ref()],
+            program_id,
+        );
+        Self::check_ata(
+            "user receipt",
+            user_receipt_account.key,
+            user_info.key,
+            &receipt_mint,
+        )?;
+        if !user_receipt_account.data_is_empty() {
+            invoke(
+                &token_instruction::close_account(
+                    &spl_token::id(),
+                    user_receipt_account.key,
+                    user_info.key,
+                    user_info.key,
+                    &[],
+                )?,
+                &[
+                    user_receipt_account.clone(),
+                    user_info.clone(),
+                    user_info.clone(),
+                ],
+            )?;
         }
 
-        // Verify lock period is valid
-        if !config.deposit_periods.contains(&deposit_period) {
+        let mut pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+        pool_state.remove_position(&deposit)?;
+        let mut pool_state_data = pool_state_account.data.borrow_mut();
+        let mut dst = &mut pool_state_data[..];
+        pool_state.serialize(&mut dst)?;
+
+        // Refund the rent to the depositor
+        let refund = user_deposit_account.lamports();
+        **depositor_info.try_borrow_mut_lamports()? = depositor_info
+            .lamports()
+            .checked_add(refund)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        **user_deposit_account.try_borrow_mut_lamports()? = 0;
+
+        // Wipe the data and hand the account back to the system program, so an account
+        // revived within the same transaction can never be read as a deposit again
+        user_deposit_account.data.borrow_mut().fill(0);
+        user_deposit_account.realloc(0, false)?;
+        user_deposit_account.assign(&solana_program::system_program::id());
+
+        AstrapeEvent::DepositClosed(DepositClosedEvent {
+            deposit: *user_deposit_account.key,
+            recipient: *depositor_info.key,
+            refund,
+        })
+        .emit();
+
+        msg!(
+            "Closed deposit position {}, refunded {} lamports",
+            deposit_index,
+            refund
+        );
+
+        Ok(())
+    }
+
+    fn process_propose_admin(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        new_admin: Pubkey,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+
+        let mut config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        // Verify admin
+        Self::check_admin(admin_info, &config)?;
+
+        // The new admin only takes over once it accepts the role
+        config.pending_admin = Some(new_admin);
+        msg!("Proposed new admin {}", new_admin);
+        AstrapeEvent::AdminProposed(AdminProposedEvent {
+            admin: config.admin,
+            pending_admin: new_admin,
+        })
+        .emit();
+
+        let mut config_data = config_info.data.borrow_mut();
+        let mut dst = &mut config_data[..];
+        config.serialize(&mut dst)?;
+        Ok(())
+    }
+
+    fn process_accept_admin(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let new_admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+
+        let mut config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        if !new_admin_info.is_signer {
+            msg!("New admin must be a signer");
+            return Err(AstrapeError::SignerRequired.into());
+        }
+        if config.pending_admin != Some(*new_admin_info.key) {
             msg!(
-                "Invalid lock period: period={}, allowed periods={:?}",
-                deposit_period,
-                config.deposit_periods
+                "Invalid pending admin: expected={:?}, actual={}",
+                config.pending_admin,
+                new_admin_info.key
             );
-            return Err(AstrapeError::InvalidLockPeriod(deposit_period).into());
+            return Err(AstrapeError::InvalidPendingAdmin.into());
         }
 
-        let price_update = Self::deserialize_price_update(pyth_price_feed_account)?;
+        msg!(
+            "Admin changed from {} to {}",
+            config.admin,
+            new_admin_info.key
+        );
+        config.admin = *new_admin_info.key;
+        config.pending_admin = None;
+        AstrapeEvent::AdminChanged(AdminChangedEvent {
+            admin: config.admin,
+        })
+        .emit();
 
-        let feed_id = get_feed_id_from_hex(
-            "0xe62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43",
-        )
-        .map_err(|_| AstrapeError::GetPriceError)?;
+        let mut config_data = config_info.data.borrow_mut();
+        let mut dst = &mut config_data[..];
+        config.serialize(&mut dst)?;
+        Ok(())
+    }
 
-        let price_object = price_update
-            .get_price_no_older_than(&Clock::get()?, config.pyth_price_max_age, &feed_id)
-            .map_err(|_| AstrapeError::GetPriceError)?;
+    fn check_multisig_signer(signer_info: &AccountInfo, multisig: &Multisig) -> ProgramResult {
+        if !signer_info.is_signer {
+            msg!("Multisig signer must be a signer");
+            return Err(AstrapeError::SignerRequired.into());
+        }
+        if !multisig.is_signer(signer_info.key) {
+            msg!("{} is not a multisig signer", signer_info.key);
+            return Err(AstrapeError::InvalidMultisigSigner.into());
+        }
+        Ok(())
+    }
 
-        let price = price_object.price as u64 * (10_u64.pow(price_object.exponent as u32));
+    fn process_admin_initialize_multisig(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+        let multisig_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
 
-        let interest_amount = Self::calculate_interest_amount(
-            amount,
-            price,
-            commission_rate,
-            deposit_period,
-            &config,
-        );
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+        let multisig_bump =
+            Self::check_pda("multisig", multisig_info.key, &[MULTISIG_SEED], program_id)?;
 
-        // Transfer collateral to pool
-        invoke(
-            &token_instruction::transfer(
-                &spl_token::id(),
-                user_token_account.key,
-                collateral_pool_account.key,
-                &user_info.key,
-                &[],
-                amount,
-            )?,
+        let mut config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        // Verify admin
+        Self::check_admin(admin_info, &config)?;
+        Self::check_multisig_disabled(&config)?;
+        Self::check_multisig_signers(&signers, threshold)?;
+
+        if !multisig_info.data_is_empty() {
+            msg!("Multisig already exists");
+            return Err(AstrapeError::InvalidInput.into());
+        }
+
+        let rent = Rent::get()?;
+        let size = Multisig::LEN;
+        let lamports = rent.minimum_balance(size).max(1);
+        invoke_signed(
+            &system_instruction::create_account(
+                admin_info.key,
+                multisig_info.key,
+                lamports,
+                size as u64,
+                program_id,
+            ),
             &[
-                user_info.clone(),
-                collateral_pool_account.clone(),
-                user_token_account.clone(),
+                admin_info.clone(),
+                multisig_info.clone(),
+                system_program_info.clone(),
             ],
+            &[&[MULTISIG_SEED, &[multisig_bump]]],
         )?;
 
-        // Transfer interest to user
+        msg!("Initialized {}-of-{} multisig", threshold, signers.len());
+        let multisig = Multisig {
+            signers,
+            threshold,
+            proposal_count: 0,
+        };
+        let mut multisig_data = multisig_info.data.borrow_mut();
+        let mut dst = &mut multisig_data[..];
+        multisig.serialize(&mut dst)?;
+
+        AstrapeEvent::MultisigUpdated(MultisigUpdatedEvent {
+            signers: multisig.signers,
+            threshold: multisig.threshold,
+        })
+        .emit();
+
+        // From now on privileged actions have to go through the multisig
+        config.multisig_enabled = true;
+        let mut config_data = config_info.data.borrow_mut();
+        let mut dst = &mut config_data[..];
+        config.serialize(&mut dst)?;
+        Ok(())
+    }
+
+    fn process_create_proposal(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        action: MultisigAction,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let proposer_info = next_account_info(account_info_iter)?;
+        let multisig_info = next_account_info(account_info_iter)?;
+        let proposal_info = next_account_info(account_info_iter)?;
+        let system_program_info = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("multisig", multisig_info.key, &[MULTISIG_SEED], program_id)?;
+
+        let mut multisig = Multisig::unpack(&multisig_info.data.borrow())?;
+        Self::check_multisig_signer(proposer_info, &multisig)?;
+
+        if let MultisigAction::SetSigners { signers, threshold } = &action {
+            Self::check_multisig_signers(signers, *threshold)?;
+        }
+
+        let proposal_index = multisig.proposal_count;
+        let proposal_bump = Self::check_pda(
+            "proposal",
+            proposal_info.key,
+            &[MULTISIG_PROPOSAL_SEED, &proposal_index.to_le_bytes()],
+            program_id,
+        )?;
+
+        let rent = Rent::get()?;
+        let size = MultisigProposal::space(&action)?;
+        let lamports = rent.minimum_balance(size).max(1);
         invoke_signed(
-            &token_instruction::transfer(
-                &spl_token::id(),
-                interest_pool_account.key,
-                user_interest_account.key,
-                &authority_info.key,
-                &[],
-                interest_amount,
-            )?,
+            &system_instruction::create_account(
+                proposer_info.key,
+                proposal_info.key,
+                lamports,
+                size as u64,
+                program_id,
+            ),
             &[
-                authority_info.clone(),
-                interest_pool_account.clone(),
-                user_interest_account.clone(),
+                proposer_info.clone(),
+                proposal_info.clone(),
+                system_program_info.clone(),
             ],
-            &[&[AUTHORITY_SEED, &[authority_bump]]],
+            &[&[
+                MULTISIG_PROPOSAL_SEED,
+                &proposal_index.to_le_bytes(),
+                &[proposal_bump],
+            ]],
         )?;
 
-        // Add user deposit
-        let user_deposit = UserDeposit {
-            amount,
-            deposit_slot: clock.slot,
-            unlock_slot: clock.slot + deposit_period,
-            interest_received: interest_amount,
-            state: UserDepositState::Deposited,
-            commission_rate,
+        // Creating a proposal counts as the proposer's approval
+        let proposal = MultisigProposal {
+            proposer: *proposer_info.key,
+            action,
+            approvals: vec![*proposer_info.key],
+            executed: false,
         };
-        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
-        let mut dst = &mut user_deposit_data[..];
-        user_deposit.serialize(&mut dst)?;
+        let mut proposal_data = proposal_info.data.borrow_mut();
+        let mut dst = &mut proposal_data[..];
+        proposal.serialize(&mut dst)?;
+
+        multisig.proposal_count = proposal_index
+            .checked_add(1)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        let mut multisig_data = multisig_info.data.borrow_mut();
+        let mut dst = &mut multisig_data[..];
+        multisig.serialize(&mut dst)?;
 
+        msg!("Created proposal {}", proposal_index);
         Ok(())
     }
 
-    fn process_request_withdrawal_early(
+    fn process_approve_proposal(
         program_id: &Pubkey,
         accounts: &[AccountInfo],
+        proposal_index: u64,
     ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        let user_info = next_account_info(account_info_iter)?;
-        let config_info = next_account_info(account_info_iter)?;
-        let authority_info = next_account_info(account_info_iter)?;
-        let user_deposit_account = next_account_info(account_info_iter)?;
-        let user_interest_account = next_account_info(account_info_iter)?;
-        let interest_pool_account = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
+        let signer_info = next_account_info(account_info_iter)?;
+        let multisig_info = next_account_info(account_info_iter)?;
+        let proposal_info = next_account_info(account_info_iter)?;
 
-        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
-        let _ = Self::check_pda(
-            "authority",
-            authority_info.key,
-            &[AUTHORITY_SEED],
-            program_id,
-        )?;
+        let _ = Self::check_pda("multisig", multisig_info.key, &[MULTISIG_SEED], program_id)?;
         let _ = Self::check_pda(
-            "user deposit",
-            user_deposit_account.key,
-            &[user_info.key.as_ref()],
+            "proposal",
+            proposal_info.key,
+            &[MULTISIG_PROPOSAL_SEED, &proposal_index.to_le_bytes()],
             program_id,
         )?;
 
-        let config = AstrapeConfig::try_from_slice(&config_info.data.borrow())?;
-
-        Self::check_ata(
-            "interest pool",
-            interest_pool_account.key,
-            authority_info.key,
-            &config.interest_mint,
-        )?;
-
-        let clock = Clock::get()?;
-        // Find user's deposit
-        let mut deposit = UserDeposit::try_from_slice(&user_deposit_account.data.borrow())?;
+        let multisig = Multisig::unpack(&multisig_info.data.borrow())?;
+        Self::check_multisig_signer(signer_info, &multisig)?;
 
-        // Verify deposit state
-        if deposit.state != UserDepositState::Deposited {
-            let current_state = deposit.state as u8;
-            let expected_state = UserDepositState::Deposited as u8;
-            msg!(
-                "Invalid deposit state: current={:?}, expected={:?}",
-                deposit.state,
-                UserDepositState::Deposited
-            );
-            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
+        let mut proposal = MultisigProposal::unpack(&proposal_info.data.borrow())?;
+        if proposal.executed {
+            return Err(AstrapeError::ProposalAlreadyExecuted.into());
         }
-
-        let interest_to_return = Self::calculate_interest_to_return(&deposit, clock.slot)?;
-
-        // Check if user has enough interest tokens to return
-        let user_interest_balance =
-            TokenAccount::unpack(&user_interest_account.data.borrow())?.amount;
-        if user_interest_balance < interest_to_return {
-            msg!(
-                "Insufficient interest balance: balance={}, required={}",
-                user_interest_balance,
-                interest_to_return
-            );
-            return Err(AstrapeError::InsufficientInterestBalance(user_interest_balance).into());
+        if proposal.approvals.contains(signer_info.key) {
+            return Err(AstrapeError::ProposalAlreadyApproved.into());
         }
+        if proposal.approvals.len() >= MAX_MULTISIG_SIGNERS {
+            // Approvals of replaced signers are dropped to make room
+            proposal
+                .approvals
+                .retain(|approver| multisig.is_signer(approver));
+        }
+        proposal.approvals.push(*signer_info.key);
 
-        // Transfer interest back to pool
-        invoke(
-            &token_instruction::transfer(
-                &spl_token::id(),
-                user_interest_account.key,
-                interest_pool_account.key,
-                &user_info.key,
-                &[],
-                interest_to_return,
-            )?,
-            &[
-                user_info.clone(),
-                interest_pool_account.clone(),
-                user_interest_account.clone(),
-            ],
-        )?;
-
-        // Update deposit state
-        deposit.state = UserDepositState::WithdrawRequested;
-        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
-        let mut dst = &mut user_deposit_data[..];
-        deposit.serialize(&mut dst)?;
+        let mut proposal_data = proposal_info.data.borrow_mut();
+        let mut dst = &mut proposal_data[..];
+        proposal.serialize(&mut dst)?;
 
+        msg!(
+            "Approved proposal {} ({} approvals)",
+            proposal_index,
+            proposal.approvals.len()
+        );
         Ok(())
     }
 
-    fn process_request_withdrawal(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    fn process_execute_proposal(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        proposal_index: u64,
+    ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        let user_info = next_account_info(account_info_iter)?;
-        let user_deposit_account = next_account_info(account_info_iter)?;
+        let signer_info = next_account_info(account_info_iter)?;
+        let multisig_info = next_account_info(account_info_iter)?;
+        let proposal_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
 
+        let _ = Self::check_pda("multisig", multisig_info.key, &[MULTISIG_SEED], program_id)?;
         let _ = Self::check_pda(
-            "user deposit",
-            user_deposit_account.key,
-            &[user_info.key.as_ref()],
+            "proposal",
+            proposal_info.key,
+            &[MULTISIG_PROPOSAL_SEED, &proposal_index.to_le_bytes()],
             program_id,
         )?;
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
 
-        let clock = Clock::get()?;
-        let mut deposit = UserDeposit::try_from_slice(&user_deposit_account.data.borrow())?;
-
-        if deposit.state != UserDepositState::WithdrawRequested {
-            let current_state = deposit.state as u8;
-            let expected_state = UserDepositState::WithdrawRequested as u8;
-            msg!(
-                "Invalid deposit state: current={:?}, expected={:?}",
-                deposit.state,
-                UserDepositState::WithdrawRequested
-            );
+        let mut multisig = Multisig::unpack(&multisig_info.data.borrow())?;
+        Self::check_multisig_signer(signer_info, &multisig)?;
 
-            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
+        let mut proposal = MultisigProposal::unpack(&proposal_info.data.borrow())?;
+        if proposal.executed {
+            return Err(AstrapeError::ProposalAlreadyExecuted.into());
         }
 
-        if clock.slot < deposit.unlock_slot {
+        // Only approvals of the current signer set count towards the threshold
+        let approvals = proposal
+            .approvals
+            .iter()
+            .filter(|approver| multisig.is_signer(approver))
+            .count() as u8;
+        if approvals < multisig.threshold {
             msg!(
-                "Deposit is not yet unlocked: slot={}, unlock_slot={}",
-                clock.slot,
-                deposit.unlock_slot
+                "Insufficient approvals: approvals={}, threshold={}",
+                approvals,
+                multisig.threshold
             );
-            return Err(AstrapeError::NotUnlockedYet(clock.slot, deposit.unlock_slot).into());
+            return Err(AstrapeError::InsufficientApprovals(approvals, multisig.threshold).into());
         }
 
-        deposit.state = UserDepositState::WithdrawReady;
-        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
-        let mut dst = &mut user_deposit_data[..];
-        deposit.serialize(&mut dst)?;
+        let mut config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        match proposal.action.clone() {
+            MultisigAction::WithdrawCollateralForInvestment {
+                amount,
+                strategy,
+                destination,
+            } => {
+                let authority_info = next_account_info(account_info_iter)?;
+                let destination_account = next_account_info(account_info_iter)?;
+                let collateral_pool_account = next_account_info(account_info_iter)?;
+                let _token_program_info = next_account_info(account_info_iter)?;
+                let investment_ledger_account = next_account_info(account_info_iter)?;
+
+                let authority_bump = Self::check_pda(
+                    "authority",
+                    authority_info.key,
+                    &[AUTHORITY_SEED],
+                    program_id,
+                )?;
+                Self::check_ata(
+                    "collateral pool",
+                    collateral_pool_account.key,
+                    authority_info.key,
+                    &config.collateral_mint,
+                )?;
+                if *destination_account.key != destination {
+                    msg!(
+                        "Invalid destination: expected={}, actual={}",
+                        destination,
+                        destination_account.key
+                    );
+                    return Err(AstrapeError::InvalidInput.into());
+                }
+
+                Self::withdraw_collateral_for_investment(
+                    authority_info,
+                    authority_bump,
+                    collateral_pool_account,
+                    destination_account,
+                    amount,
+                )?;
+                Self::record_investment(investment_ledger_account, program_id, strategy, amount)?;
+            }
+            MultisigAction::WithdrawInterest {
+                amount,
+                destination,
+            } => {
+                let authority_info = next_account_info(account_info_iter)?;
+                let destination_account = next_account_info(account_info_iter)?;
+                let interest_pool_account = next_account_info(account_info_iter)?;
+                let _token_program_info = next_account_info(account_info_iter)?;
+
+                let authority_bump = Self::check_pda(
+                    "authority",
+                    authority_info.key,
+                    &[AUTHORITY_SEED],
+                    program_id,
+                )?;
+                Self::check_ata(
+                    "interest pool",
+                    interest_pool_account.key,
+                    authority_info.key,
+                    &config.interest_mint,
+                )?;
+                if *destination_account.key != destination {
+                    msg!(
+                        "Invalid destination: expected={}, actual={}",
+                        destination,
+                        destination_account.key
+                    );
+                    return Err(AstrapeError::InvalidInput.into());
+                }
+
+                Self::transfer_from_pool(
+                    authority_info,
+                    authority_bump,
+                    interest_pool_account,
+                    destination_account,
+                    amount,
+                )?;
+                AstrapeEvent::AdminFunds(AdminFundsEvent {
+                    movement: FundMovement::InterestWithdrawn,
+                    amount,
+                })
+                .emit();
+            }
+            MultisigAction::WithdrawTreasury {
+                amount,
+                destination,
+            } => {
+                let authority_info = next_account_info(account_info_iter)?;
+                let destination_account = next_account_info(account_info_iter)?;
+                let treasury_account = next_account_info(account_info_iter)?;
+                let _token_program_info = next_account_info(account_info_iter)?;
+
+                let authority_bump = Self::check_pda(
+                    "authority",
+                    authority_info.key,
+                    &[AUTHORITY_SEED],
+                    program_id,
+                )?;
+                let _ = Self::check_pda(
+                    "treasury",
+                    treasury_account.key,
+                    &[TREASURY_SEED],
+                    program_id,
+                )?;
+                if *destination_account.key != destination {
+                    msg!(
+                        "Invalid destination: expected={}, actual={}",
+                        destination,
+                        destination_account.key
+                    );
+                    return Err(AstrapeError::InvalidInput.into());
+                }
+
+                Self::transfer_from_pool(
+                    authority_info,
+                    authority_bump,
+                    treasury_account,
+                    destination_account,
+                    amount,
+                )?;
+                AstrapeEvent::AdminFunds(AdminFundsEvent {
+                    movement: FundMovement::TreasuryWithdrawn,
+                    amount,
+                })
+                .emit();
+            }
+            MultisigAction::UpdateConfig(update) => {
+                let pool_state_account = next_account_info(account_info_iter)?;
+                let pool_state = Self::load_pool_state(pool_state_account, program_id)?;
+                Self::apply_config_update(&mut config, *update, &pool_state)?;
+
+                let mut config_data = config_info.data.borrow_mut();
+                let mut dst = &mut config_data[..];
+                config.serialize(&mut dst)?;
+            }
+            MultisigAction::SetSigners { signers, threshold } => {
+                Self::check_multisig_signers(&signers, threshold)?;
+                multisig.signers = signers;
+                multisig.threshold = threshold;
+
+                let mut multisig_data = multisig_info.data.borrow_mut();
+                let mut dst = &mut multisig_data[..];
+                multisig.serialize(&mut dst)?;
+
+                AstrapeEvent::MultisigUpdated(MultisigUpdatedEvent {
+                    signers: multisig.signers.clone(),
+                    threshold: multisig.threshold,
+                })
+                .emit();
+            }
+            MultisigAction::ReturnCollateral {
+                strategy,
+                principal,
+                amount,
+                source,
+            } => {
+                let authority_info = next_account_info(account_info_iter)?;
+                let source_account = next_account_info(account_info_iter)?;
+                let collateral_pool_account = next_account_info(account_info_iter)?;
+                let _token_program_info = next_account_info(account_info_iter)?;
+                let investment_ledger_account = next_account_info(account_info_iter)?;
+
+                let _ = Self::check_pda(
+                    "authority",
+                    authority_info.key,
+                    &[AUTHORITY_SEED],
+                    program_id,
+                )?;
+                Self::check_ata(
+                    "collateral pool",
+                    collateral_pool_account.key,
+                    authority_info.key,
+                    &config.collateral_mint,
+                )?;
+                if *source_account.key != source {
+                    msg!(
+                        "Invalid source: expected={}, actual={}",
+                        source,
+                        source_account.key
+                    );
+                    return Err(AstrapeError::InvalidInput.into());
+                }
 
+                // The executing signer owns the source account and signs the transfer
+                Self::record_return(
+                    investment_ledger_account,
+                    program_id,
+                    strategy,
+                    principal,
+                    amount,
+                )?;
+                Self::return_collateral(
+                    signer_info,
+                    source_account,
+                    collateral_pool_account,
+                    amount,
+                )?;
+            }
+        }
+
+        proposal.executed = true;
+        let mut proposal_data = proposal_info.data.borrow_mut();
+        let mut dst = &mut proposal_data[..];
+        proposal.serialize(&mut dst)?;
+
+        msg!("Executed proposal {}", proposal_index);
         Ok(())
     }
 
-    fn process_withdraw_collateral(program_id: &Pubkey, accounts: &[AccountInfo]) -> ProgramResult {
+    fn process_admin_set_pause(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        paused: PauseFlags,
+    ) -> ProgramResult {
         let account_info_iter = &mut accounts.iter();
-        let user_info = next_account_info(account_info_iter)?;
+        let admin_info = next_account_info(account_info_iter)?;
+        let config_info = next_account_info(account_info_iter)?;
+
+        let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
+
+        let mut config = AstrapeConfig::unpack(&config_info.data.borrow())?;
+
+        // Verify admin. Pausing stays available to the admin alone so that it can
+        // react to an incident without waiting for multisig approvals
+        Self::check_admin(admin_info, &config)?;
+
+        msg!(
+            "Pause flags changed from {:?} to {:?}",
+            config.paused,
+            paused
+        );
+        config.paused = paused;
+        AstrapeEvent::PauseUpdated(PauseUpdatedEvent { paused }).emit();
+
+        let mut config_data = config_info.data.borrow_mut();
+        let mut dst = &mut config_data[..];
+        config.serialize(&mut dst)?;
+        Ok(())
+    }
+
+    fn process_admin_withdraw_treasury(
+        program_id: &Pubkey,
+        accounts: &[AccountInfo],
+        amount: u64,
+    ) -> ProgramResult {
+        let account_info_iter = &mut accounts.iter();
+        let admin_info = next_account_info(account_info_iter)?;
         let config_info = next_account_info(account_info_iter)?;
         let authority_info = next_account_info(account_info_iter)?;
-        let user_deposit_account = next_account_info(account_info_iter)?;
-        let user_token_account = next_account_info(account_info_iter)?;
-        let withdrawal_pool_account = next_account_info(account_info_iter)?;
-        let token_program_info = next_account_info(account_info_iter)?;
+        let admin_interest_account = next_account_info(account_info_iter)?;
+        let treasury_account = next_account_info(account_info_iter)?;
+        let _token_program_info = next_account_info(account_info_iter)?;
 
         let _ = Self::check_pda("config", config_info.key, &[CONFIG_SEED], program_id)?;
         let authority_bump = Self::check_pda(
@@ -1260,69 +4919,41 @@ impl Processor {
             program_id,
         )?;
         let _ = Self::check_pda(
-            "user deposit",
-            user_deposit_account.key,
-            &[user_info.key.as_ref()],
-            program_id,
-        )?;
-        let _ = Self::check_pda(
-            "withdrawal pool",
-            withdrawal_pool_account.key,
-            &[WITHDRAWAL_POOL_SEED],
+            "treasury",
+            treasury_account.key,
+            &[TREASURY_SEED],
             program_id,
         )?;
 
-        let _config = AstrapeConfig::try_from_slice(&config_info.data.borrow())?;
-        // Find user's deposit
-        let mut deposit = UserDeposit::try_from_slice(&user_deposit_account.data.borrow())?;
+        let config = AstrapeConfig::unpack(&config_info.data.borrow())?;
 
-        // Verify deposit state
-        if deposit.state != UserDepositState::WithdrawReady {
-            let current_state = deposit.state as u8;
-            let expected_state = UserDepositState::WithdrawReady as u8;
-            msg!(
-                "Invalid deposit state: current={:?}, expected={:?}",
-                deposit.state,
-                UserDepositState::WithdrawReady
-            );
-            return Err(AstrapeError::InvalidDepositState(current_state, expected_state).into());
-        }
+        // Verify admin
+        Self::check_admin(admin_info, &config).with_context("Admin must be signer")?;
+        Self::check_multisig_disabled(&config)?;
 
-        // Check if withdrawal pool has enough tokens
-        let pool_balance = TokenAccount::unpack(&withdrawal_pool_account.data.borrow())?.amount;
-        if pool_balance < deposit.amount {
-            msg!(
-                "Insufficient withdrawal pool balance: balance={}, required={}",
-                pool_balance,
-                deposit.amount
-            );
-            return Err(AstrapeError::InsufficientPoolBalance(pool_balance).into());
-        }
+        Self::check_ata(
+            "admin interest",
+            admin_interest_account.key,
+            admin_info.key,
+            &config.interest_mint,
+        )?;
 
-        // Transfer collateral from withdrawal pool to user
-        invoke_signed(
-            &token_instruction::transfer(
-                &spl_token::id(),
-                withdrawal_pool_account.key,
-                user_token_account.key,
-                &authority_info.key,
-                &[],
-                deposit.amount,
-            )?,
-            &[
-                authority_info.clone(),
-                withdrawal_pool_account.clone(),
-                user_token_account.clone(),
-            ],
-            &[&[AUTHORITY_SEED, &[authority_bump]]],
+        // Transfer commission from treasury to admin
+        Self::transfer_from_pool(
+            authority_info,
+            authority_bump,
+            treasury_account,
+            admin_interest_account,
+            amount,
         )?;
 
-        // Remove deposit
-        deposit.state = UserDepositState::WithdrawCompleted;
-        let mut user_deposit_data = user_deposit_account.data.borrow_mut();
-        let mut dst = &mut user_deposit_data[..];
-        deposit.serialize(&mut dst)?;
+        AstrapeEvent::AdminFunds(AdminFundsEvent {
+            movement: FundMovement::TreasuryWithdrawn,
+            amount,
+        })
+        .emit();
 
+        msg!("Withdrew {} from the treasury", amount);
         Ok(())
     }
 }
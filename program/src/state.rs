@@ -2,6 +2,8 @@ use borsh::{BorshDeserialize, BorshSerialize};
 use solana_program::pubkey::Pubkey;
 use std::mem::size_of;
 
+use crate::errors::AstrapeError;
+
 #[derive(BorshSerialize, BorshDeserialize, Debug, PartialEq, Clone, Copy)]
 pub enum UserDepositState {
     Deposited,
@@ -12,6 +14,10 @@ pub enum UserDepositState {
 
 #[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
 pub struct UserDeposit {
+    pub discriminator: [u8; 8], // `UserDeposit::DISCRIMINATOR`
+    pub depositor: Pubkey,      // Opened the position, whose account is derived from it
+    pub deposit_index: u64,     // Index of the position among the positions of the depositor
+    pub owner: Pubkey,          // Current holder of the position, which can be transferred
     pub amount: u64,
     pub deposit_slot: u64, // Slot when the deposit was made
     pub unlock_slot: u64,  // Slot number for unlock time
@@ -19,27 +25,673 @@ pub struct UserDeposit {
     pub state: UserDepositState,
 
     pub commission_rate: u64,
+    pub deposit_period: u64,              // Tenor of the current lock
+    pub rollover: bool, // Re-locked for the same tenor at maturity by the `Rollover` crank
+    pub queued: Option<QueuedWithdrawal>, // Set while the position is in the withdrawal queue
 }
 
 impl UserDeposit {
-    pub const LEN: usize = 8 + 8 + 8 + 8 + size_of::<UserDepositState>() + 8;
+    // Tells deposit accounts apart from the other accounts of the program
+    pub const DISCRIMINATOR: [u8; 8] = *b"astrdpst";
+    pub const LEN: usize = 8
+        + 32
+        + 8
+        + 32
+        + 8
+        + 8
+        + 8
+        + 8
+        + size_of::<UserDepositState>()
+        + 8
+        + 8
+        + 1
+        + 1
+        + QueuedWithdrawal::LEN;
+
+    // The queue node makes the size variable, so the account can have unused bytes at the end
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        Self::deserialize(&mut &data[..])
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone)]
+pub struct UserDepositCounter {
+    pub deposit_count: u64, // Number of deposit positions opened, also the next position index
+}
+
+impl UserDepositCounter {
+    pub const LEN: usize = 8;
+}
+
+// Aggregates of a set of deposit positions
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct PositionTotals {
+    pub count: u64,
+    pub amount: u64,            // Collateral of the positions
+    pub interest_received: u64, // Upfront interest recorded on the positions
+}
+
+impl PositionTotals {
+    pub const LEN: usize = 8 + 8 + 8;
+
+    fn add(&mut self, deposit: &UserDeposit) -> Result<(), AstrapeError> {
+        self.count = self
+            .count
+            .checked_add(1)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        self.amount = self
+            .amount
+            .checked_add(deposit.amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        self.interest_received = self
+            .interest_received
+            .checked_add(deposit.interest_received)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    fn remove(&mut self, deposit: &UserDeposit) -> Result<(), AstrapeError> {
+        self.count = self
+            .count
+            .checked_sub(1)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        self.amount = self
+            .amount
+            .checked_sub(deposit.amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        self.interest_received = self
+            .interest_received
+            .checked_sub(deposit.interest_received)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+// Tenors removed from the config can still have locked positions
+pub const MAX_POOL_TENORS: usize = MAX_DEPOSIT_PERIODS * 2;
+
+// Aggregates of the locked positions of a tenor
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct TenorTotals {
+    pub period: u64,
+    pub totals: PositionTotals,
+}
+
+impl TenorTotals {
+    pub const LEN: usize = 8 + PositionTotals::LEN;
+}
+
+// Pool wide accounting, updated by every instruction which changes a position
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq)]
+pub struct PoolState {
+    pub total_locked: u64,             // Collateral of deposited positions
+    pub withdrawal_obligations: u64,   // Collateral of positions requested or ready for withdrawal
+    pub active_deposits: u64,          // Positions not fully withdrawn yet
+    pub total_interest_paid: u64,      // Cumulative upfront interest paid to users
+    pub total_interest_refunded: u64,  // Cumulative interest returned on early exits
+    pub by_state: [PositionTotals; 4], // Indexed by `UserDepositState`
+    pub by_tenor: Vec<TenorTotals>,    // Deposited positions by tenor
+}
+
+impl PoolState {
+    pub const LEN: usize = 8 * 5 + PositionTotals::LEN * 4 + 4 + TenorTotals::LEN * MAX_POOL_TENORS;
+
+    // The tenor list grows and shrinks, so the account can have unused space
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        Self::deserialize(&mut &data[..])
+    }
+
+    pub fn state_totals(&self, state: UserDepositState) -> &PositionTotals {
+        &self.by_state[state as usize]
+    }
+
+    pub fn tenor_totals(&self, period: u64) -> Option<&PositionTotals> {
+        self.by_tenor
+            .iter()
+            .find(|tenor| tenor.period == period)
+            .map(|tenor| &tenor.totals)
+    }
+
+    // Counts a position in the totals of its current state and tenor. Instructions remove
+    // the position before changing it and add it back afterwards.
+    pub fn add_position(&mut self, deposit: &UserDeposit) -> Result<(), AstrapeError> {
+        self.by_state[deposit.state as usize].add(deposit)?;
+        match deposit.state {
+            UserDepositState::Deposited => {
+                self.total_locked = self
+                    .total_locked
+                    .checked_add(deposit.amount)
+                    .ok_or(AstrapeError::ArithmeticOverflow)?;
+                let index = match self
+                    .by_tenor
+                    .iter()
+                    .position(|tenor| tenor.period == deposit.deposit_period)
+                {
+                    Some(index) => index,
+                    None if self.by_tenor.len() < MAX_POOL_TENORS => {
+                        self.by_tenor.push(TenorTotals {
+                            period: deposit.deposit_period,
+                            totals: PositionTotals::default(),
+                        });
+                        self.by_tenor.len() - 1
+                    }
+                    None => return Err(AstrapeError::InvalidLockPeriod(deposit.deposit_period)),
+                };
+                self.by_tenor[index].totals.add(deposit)?;
+            }
+            UserDepositState::WithdrawRequested | UserDepositState::WithdrawReady => {
+                self.withdrawal_obligations = self
+                    .withdrawal_obligations
+                    .checked_add(deposit.amount)
+                    .ok_or(AstrapeError::ArithmeticOverflow)?;
+            }
+            UserDepositState::WithdrawCompleted => return Ok(()),
+        }
+        self.active_deposits = self
+            .active_deposits
+            .checked_add(1)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    pub fn remove_position(&mut self, deposit: &UserDeposit) -> Result<(), AstrapeError> {
+        self.by_state[deposit.state as usize].remove(deposit)?;
+        match deposit.state {
+            UserDepositState::Deposited => {
+                self.total_locked = self
+                    .total_locked
+                    .checked_sub(deposit.amount)
+                    .ok_or(AstrapeError::ArithmeticOverflow)?;
+                let index = self
+                    .by_tenor
+                    .iter()
+                    .position(|tenor| tenor.period == deposit.deposit_period)
+                    .ok_or(AstrapeError::InvalidLockPeriod(deposit.deposit_period))?;
+                self.by_tenor[index].totals.remove(deposit)?;
+                if self.by_tenor[index].totals.count == 0 {
+                    self.by_tenor.swap_remove(index);
+                }
+            }
+            UserDepositState::WithdrawRequested | UserDepositState::WithdrawReady => {
+                self.withdrawal_obligations = self
+                    .withdrawal_obligations
+                    .checked_sub(deposit.amount)
+                    .ok_or(AstrapeError::ArithmeticOverflow)?;
+            }
+            UserDepositState::WithdrawCompleted => return Ok(()),
+        }
+        self.active_deposits = self
+            .active_deposits
+            .checked_sub(1)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+pub const MAX_STRATEGIES: usize = 8;
+pub const MAX_LEDGER_ENTRIES: usize = 32;
+
+// Collateral withdrawn from the pool for an investment strategy, or returned to it
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct InvestmentEntry {
+    pub strategy: u64,
+    pub amount: u64,           // Principal withdrawn or settled
+    pub returned: Option<u64>, // Collateral paid back to the pool, None for withdrawals
+    pub slot: u64,
+}
+
+impl InvestmentEntry {
+    pub const LEN: usize = 8 + 8 + 9 + 8;
+}
+
+// Collateral currently deployed in a strategy
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct StrategyAllocation {
+    pub strategy: u64,
+    pub deployed: u64,
+}
+
+impl StrategyAllocation {
+    pub const LEN: usize = 8 + 8;
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq)]
+pub struct InvestmentLedger {
+    pub total_deployed: u64,
+    pub realized_yield: u64, // Returned collateral above the settled principal
+    pub realized_loss: u64,  // Settled principal which was not returned
+    pub entry_count: u64,    // Entries ever recorded, only the latest `MAX_LEDGER_ENTRIES` are kept
+    pub strategies: Vec<StrategyAllocation>,
+    pub entries: Vec<InvestmentEntry>, // Oldest first
+}
+
+impl InvestmentLedger {
+    pub const LEN: usize = 8
+        + 8
+        + 8
+        + 8
+        + 4
+        + StrategyAllocation::LEN * MAX_STRATEGIES
+        + 4
+        + InvestmentEntry::LEN * MAX_LEDGER_ENTRIES; // 1224
+
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        Self::deserialize(&mut &data[..])
+    }
+
+    pub fn deployed(&self, strategy: u64) -> u64 {
+        self.strategies
+            .iter()
+            .find(|allocation| allocation.strategy == strategy)
+            .map_or(0, |allocation| allocation.deployed)
+    }
+
+    // Adds `amount` to the collateral deployed in `strategy` and records the withdrawal
+    pub fn record_withdrawal(
+        &mut self,
+        strategy: u64,
+        amount: u64,
+        slot: u64,
+    ) -> Result<(), AstrapeError> {
+        let index = match self
+            .strategies
+            .iter()
+            .position(|allocation| allocation.strategy == strategy)
+        {
+            Some(index) => index,
+            None if self.strategies.len() < MAX_STRATEGIES => {
+                self.strategies.push(StrategyAllocation {
+                    strategy,
+                    deployed: 0,
+                });
+                self.strategies.len() - 1
+            }
+            None => return Err(AstrapeError::InvalidInput),
+        };
+        self.strategies[index].deployed = self.strategies[index]
+            .deployed
+            .checked_add(amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        self.total_deployed = self
+            .total_deployed
+            .checked_add(amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+
+        self.push_entry(InvestmentEntry {
+            strategy,
+            amount,
+            returned: None,
+            slot,
+        })
+    }
+
+    // Settles `principal` of the collateral deployed in `strategy` against the `returned` amount
+    // and records the difference as realized yield or loss
+    pub fn record_return(
+        &mut self,
+        strategy: u64,
+        principal: u64,
+        returned: u64,
+        slot: u64,
+    ) -> Result<(), AstrapeError> {
+        let index = self
+            .strategies
+            .iter()
+            .position(|allocation| allocation.strategy == strategy)
+            .ok_or(AstrapeError::InvalidInput)?;
+        let deployed = self.strategies[index].deployed;
+        if principal > deployed {
+            return Err(AstrapeError::InsufficientBalance(deployed));
+        }
+        if deployed == principal {
+            self.strategies.remove(index);
+        } else {
+            self.strategies[index].deployed = deployed - principal;
+        }
+        self.total_deployed = self
+            .total_deployed
+            .checked_sub(principal)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+
+        if returned >= principal {
+            self.realized_yield = self
+                .realized_yield
+                .checked_add(returned - principal)
+                .ok_or(AstrapeError::ArithmeticOverflow)?;
+        } else {
+            self.realized_loss = self
+                .realized_loss
+                .checked_add(principal - returned)
+                .ok_or(AstrapeError::ArithmeticOverflow)?;
+        }
+
+        self.push_entry(InvestmentEntry {
+            strategy,
+            amount: principal,
+            returned: Some(returned),
+            slot,
+        })
+    }
+
+    fn push_entry(&mut self, entry: InvestmentEntry) -> Result<(), AstrapeError> {
+        if self.entries.len() == MAX_LEDGER_ENTRIES {
+            self.entries.remove(0);
+        }
+        self.entries.push(entry);
+        self.entry_count = self
+            .entry_count
+            .checked_add(1)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        Ok(())
+    }
+}
+
+// Instruction categories which can be halted during an incident
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, Default, PartialEq)]
+pub struct PauseFlags {
+    pub deposits: bool,
+    pub early_withdrawals: bool,
+    pub withdrawal_requests: bool,
+    pub collateral_withdrawals: bool,
+}
+
+pub const MAX_DEPOSIT_PERIODS: usize = 8;
+
+// A lock period (tenor) users can deposit for
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct DepositPeriod {
+    pub period: u64,           // Lock length in slots
+    pub interest_rate: u64,    // Fixed decimal at the first decimal place
+    pub enabled: bool,         // Disabled tenors take no new deposits
+    pub capacity: Option<u64>, // Max collateral locked in this tenor
+}
+
+impl DepositPeriod {
+    pub const LEN: usize = 8 + 8 + 1 + 9;
+}
+
+pub const MAX_REFUND_STEPS: usize = 8;
+
+// Share of the interest refunded while the elapsed share of the lock is below `until_bps`
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct RefundStep {
+    pub until_bps: u64,  // Elapsed part of the lock in basis points
+    pub refund_bps: u64, // Refunded part of the interest received in basis points
+}
+
+impl RefundStep {
+    pub const LEN: usize = 8 + 8;
+}
+
+// How much of the upfront interest a user returns when leaving a position early
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum EarlyExitPolicy {
+    /// Refund the unearned part of the interest, pro rata to the remaining lock
+    LinearUnearned,
+    /// Refund the unearned part plus `penalty_bps` of the interest received
+    FlatPenalty { penalty_bps: u64 },
+    /// Refund by the first step the elapsed part of the lock is below, nothing after the last
+    StepSchedule(Vec<RefundStep>),
+}
+
+impl EarlyExitPolicy {
+    pub const LEN: usize = 1 + 4 + RefundStep::LEN * MAX_REFUND_STEPS;
 }
 
 #[derive(BorshSerialize, BorshDeserialize, Debug)]
 pub struct AstrapeConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Option<Pubkey>, // Proposed admin, waiting to accept the role
+    pub multisig_enabled: bool,        // Fund withdrawals and config updates need multisig approval
+    pub paused: PauseFlags,
     pub interest_mint: Pubkey,
     pub collateral_mint: Pubkey,
-    pub base_interest_rate: u64, // Fixed decimal at the first decimal place
+    pub interest_decimals: u8, // Read from the mint accounts at initialization
+    pub collateral_decimals: u8,
 
+    pub pyth_feed_id: [u8; 32], // Pyth price feed of the collateral
     pub pyth_price_max_age: u64,
 
     pub min_commission_rate: u64, // Fixed decimal at the first decimal place
     pub max_commission_rate: u64, // Fixed decimal at the first decimal place
     pub min_deposit_amount: u64,
     pub max_deposit_amount: u64,
-    pub deposit_periods: Vec<u64>,
+    pub pool_capacity: Option<u64>, // Max collateral locked in the pool
+    pub withdrawal_sla: Option<u64>, // Slots the admin has to prepare a withdrawal
+    pub late_payment_rate: u64,     // Share of the interest paid per missed deadline
+    pub commission_revenue: u64,    // Cumulative commission moved to the treasury
+    pub early_exit_policy: EarlyExitPolicy,
+    pub deposit_periods: Vec<DepositPeriod>,
 }
 
 impl AstrapeConfig {
-    pub const LEN: usize = 32 * 2 + 8 * 6 + 8 * 3 + 4; // size_of::<Vec<u64>>(); // 160
+    pub const LEN: usize = 32
+        + 33
+        + 1
+        + 4
+        + 32 * 2
+        + 2
+        + 32
+        + 8 * 7
+        + 9 * 2
+        + EarlyExitPolicy::LEN
+        + 4
+        + DepositPeriod::LEN * MAX_DEPOSIT_PERIODS; // 587
+
+    // The account is allocated with `LEN` bytes, so the serialized config can be
+    // followed by unused space
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        Self::deserialize(&mut &data[..])
+    }
+
+    pub fn deposit_period(&self, period: u64) -> Option<&DepositPeriod> {
+        self.deposit_periods
+            .iter()
+            .find(|deposit_period| deposit_period.period == period)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub struct ConfigUpdate {
+    pub param: u8,
+    pub deposit_period: Option<DepositPeriod>, // Added, or replaces the tenor with the same period
+    pub pyth_price_max_age: Option<u64>,
+    pub min_commission_rate: Option<u64>,
+    pub max_commission_rate: Option<u64>,
+    pub min_deposit_amount: Option<u64>,
+    pub max_deposit_amount: Option<u64>,
+    pub deposit_periods: Option<Vec<DepositPeriod>>,
+    pub pyth_feed_id: Option<[u8; 32]>,
+    pub early_exit_policy: Option<EarlyExitPolicy>,
+    pub pool_capacity: Option<Option<u64>>, // `Some(None)` removes the cap
+    pub withdrawal_sla: Option<Option<u64>>, // `Some(None)` removes the deadline
+    pub late_payment_rate: Option<u64>,
+}
+
+pub const MAX_MULTISIG_SIGNERS: usize = 10;
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct Multisig {
+    pub signers: Vec<Pubkey>,
+    pub threshold: u8,
+    pub proposal_count: u64, // Also the next proposal index
+}
+
+impl Multisig {
+    pub const LEN: usize = 4 + 32 * MAX_MULTISIG_SIGNERS + 1 + 8;
+
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        Self::deserialize(&mut &data[..])
+    }
+
+    pub fn is_signer(&self, key: &Pubkey) -> bool {
+        self.signers.contains(key)
+    }
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, PartialEq)]
+pub enum MultisigAction {
+    /// Move `amount` of collateral from the pool to `destination` token account for `strategy`
+    WithdrawCollateralForInvestment {
+        amount: u64,
+        strategy: u64,
+        destination: Pubkey,
+    },
+    /// Move `amount` from the interest pool to `destination` token account
+    WithdrawInterest {
+        amount: u64,
+        destination: Pubkey,
+    },
+    /// Move `amount` of collected commission from the treasury to `destination` token account
+    WithdrawTreasury {
+        amount: u64,
+        destination: Pubkey,
+    },
+    UpdateConfig(Box<ConfigUpdate>),
+    /// Replace the signer set and threshold of the multisig
+    SetSigners {
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    },
+    /// Move `amount` of collateral from `source` token account back to the pool, settling
+    /// `principal` of the collateral deployed in `strategy`
+    ReturnCollateral {
+        strategy: u64,
+        principal: u64,
+        amount: u64,
+        source: Pubkey,
+    },
+}
+
+#[derive(BorshSerialize, BorshDeserialize, Debug)]
+pub struct MultisigProposal {
+    pub proposer: Pubkey,
+    pub action: MultisigAction,
+    pub approvals: Vec<Pubkey>,
+    pub executed: bool,
+}
+
+impl MultisigProposal {
+    // The action has a variable size, so the account is sized for the given action
+    // with room for an approval from every signer
+    pub fn space(action: &MultisigAction) -> Result<usize, std::io::Error> {
+        Ok(32 + action.try_to_vec()?.len() + 4 + 32 * MAX_MULTISIG_SIGNERS + 1)
+    }
+
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        Self::deserialize(&mut &data[..])
+    }
+}
+
+// Node of a position in the withdrawal queue, kept in its deposit account
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Copy, PartialEq)]
+pub struct QueuedWithdrawal {
+    pub slot: u64,            // Slot when the withdrawal was requested
+    pub next: Option<Pubkey>, // Deposit account of the next position in the queue
+}
+
+impl QueuedWithdrawal {
+    pub const LEN: usize = 8 + 1 + 32;
+}
+
+// Positions waiting for the admin to prepare their withdrawal, oldest first. The queue is a
+// linked list through the deposit accounts, so this account doesn't limit its length.
+#[derive(BorshSerialize, BorshDeserialize, Debug, Clone, Default, PartialEq)]
+pub struct WithdrawalQueue {
+    pub total_pending: u64, // Collateral of all queued positions
+    pub length: u64,
+    pub head: Option<Pubkey>, // Deposit account of the oldest position
+    pub tail: Option<Pubkey>, // Deposit account of the newest position
+}
+
+impl WithdrawalQueue {
+    pub const LEN: usize = 8 + 8 + 1 + 32 + 1 + 32;
+
+    pub fn unpack(data: &[u8]) -> Result<Self, std::io::Error> {
+        Self::deserialize(&mut &data[..])
+    }
+
+    // Appends the position of the deposit account `key` behind `tail`, the newest position,
+    // which is `None` when the queue is empty
+    pub fn push(
+        &mut self,
+        key: &Pubkey,
+        deposit: &mut UserDeposit,
+        tail: Option<(&Pubkey, &mut UserDeposit)>,
+        slot: u64,
+    ) -> Result<(), AstrapeError> {
+        if deposit.queued.is_some() {
+            return Err(AstrapeError::WithdrawalQueueMismatch);
+        }
+        match (self.tail, tail) {
+            (None, None) => self.head = Some(*key),
+            (Some(expected), Some((tail_key, tail))) if expected == *tail_key => {
+                let node = tail
+                    .queued
+                    .as_mut()
+                    .ok_or(AstrapeError::WithdrawalQueueMismatch)?;
+                node.next = Some(*key);
+            }
+            _ => return Err(AstrapeError::WithdrawalQueueMismatch),
+        }
+        self.tail = Some(*key);
+        self.length = self
+            .length
+            .checked_add(1)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        self.total_pending = self
+            .total_pending
+            .checked_add(deposit.amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        deposit.queued = Some(QueuedWithdrawal { slot, next: None });
+        Ok(())
+    }
+
+    // Removes the oldest position, which must be the one of `key`
+    pub fn pop(&mut self, key: &Pubkey, deposit: &mut UserDeposit) -> Result<(), AstrapeError> {
+        if self.head != Some(*key) {
+            return Err(AstrapeError::NotNextInWithdrawalQueue);
+        }
+        self.remove(key, deposit, None)
+    }
+
+    // Removes the position of `key` from anywhere in the queue. `previous` is the position
+    // queued right before it, `None` for the oldest position.
+    pub fn remove(
+        &mut self,
+        key: &Pubkey,
+        deposit: &mut UserDeposit,
+        previous: Option<(&Pubkey, &mut UserDeposit)>,
+    ) -> Result<(), AstrapeError> {
+        let node = deposit
+            .queued
+            .ok_or(AstrapeError::WithdrawalQueueMismatch)?;
+        let previous_key = match previous {
+            None if self.head == Some(*key) => {
+                self.head = node.next;
+                None
+            }
+            Some((previous_key, previous)) => match previous.queued.as_mut() {
+                Some(previous_node) if previous_node.next == Some(*key) => {
+                    previous_node.next = node.next;
+                    Some(*previous_key)
+                }
+                _ => return Err(AstrapeError::WithdrawalQueueMismatch),
+            },
+            None => return Err(AstrapeError::WithdrawalQueueMismatch),
+        };
+        if self.tail == Some(*key) {
+            self.tail = previous_key;
+        }
+        self.length = self
+            .length
+            .checked_sub(1)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        self.total_pending = self
+            .total_pending
+            .checked_sub(deposit.amount)
+            .ok_or(AstrapeError::ArithmeticOverflow)?;
+        deposit.queued = None;
+        Ok(())
+    }
 }
@@ -0,0 +1,71 @@
+// Fixtures shared by the unit test files
+#![allow(dead_code)]
+
+use {
+    astrape::{
+        processor::{MS_PER_SLOT, RATE_DENOMINATOR},
+        state::{
+            AstrapeConfig, DepositPeriod, EarlyExitPolicy, PauseFlags, UserDeposit,
+            UserDepositState,
+        },
+    },
+    solana_program::pubkey::Pubkey,
+};
+
+pub const ONE_MONTH: u64 = 30 * 24 * 60 * 60 * 1000 / MS_PER_SLOT; // 5_890_909 slots
+
+pub fn config_with(
+    deposit_periods: Vec<DepositPeriod>,
+    interest_decimals: u8,
+    collateral_decimals: u8,
+) -> AstrapeConfig {
+    AstrapeConfig {
+        admin: Pubkey::new_unique(),
+        pending_admin: None,
+        multisig_enabled: false,
+        paused: PauseFlags::default(),
+        interest_mint: Pubkey::new_unique(),
+        collateral_mint: Pubkey::new_unique(),
+        interest_decimals,
+        collateral_decimals,
+        pyth_feed_id: [0; 32],
+        pyth_price_max_age: 60,
+        min_commission_rate: 0,
+        max_commission_rate: RATE_DENOMINATOR,
+        min_deposit_amount: 0,
+        max_deposit_amount: u64::MAX,
+        pool_capacity: None,
+        withdrawal_sla: None,
+        late_payment_rate: 0,
+        commission_revenue: 0,
+        early_exit_policy: EarlyExitPolicy::LinearUnearned,
+        deposit_periods,
+    }
+}
+
+pub fn tenor(period: u64, interest_rate: u64, enabled: bool) -> DepositPeriod {
+    DepositPeriod {
+        period,
+        interest_rate,
+        enabled,
+        capacity: None,
+    }
+}
+
+pub fn position(deposit_slot: u64, lock: u64, interest_received: u64) -> UserDeposit {
+    UserDeposit {
+        discriminator: UserDeposit::DISCRIMINATOR,
+        depositor: Pubkey::default(),
+        deposit_index: 0,
+        owner: Pubkey::default(),
+        amount: 20_000_000,
+        deposit_slot,
+        unlock_slot: deposit_slot + lock,
+        interest_received,
+        state: UserDepositState::Deposited,
+        commission_rate: 200,
+        deposit_period: lock,
+        rollover: false,
+        queued: None,
+    }
+}
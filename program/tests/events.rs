@@ -0,0 +1,50 @@
+mod common;
+
+use {
+    astrape::events::{
+        AdminFundsEvent, AstrapeEvent, DepositEvent, FundMovement, WithdrawalRequestedEvent,
+    },
+    borsh::BorshSerialize,
+    common::{position, ONE_MONTH},
+    solana_program::pubkey::Pubkey,
+};
+
+#[test]
+fn test_event_encoding() {
+    let deposit = position(10, ONE_MONTH, 1_000);
+    let event = AstrapeEvent::Deposit(DepositEvent {
+        deposit: Pubkey::new_unique(),
+        owner: deposit.owner,
+        amount: deposit.amount,
+        deposit_period: deposit.deposit_period,
+        unlock_slot: deposit.unlock_slot,
+        interest: deposit.interest_received,
+        commission: 100,
+    });
+    let data = event.try_to_vec().unwrap();
+    // The first byte tells indexers which event follows
+    assert_eq!(data[0], 0);
+    assert_eq!(AstrapeEvent::unpack(&data).unwrap(), event);
+
+    let requested = AstrapeEvent::WithdrawalRequested(WithdrawalRequestedEvent {
+        deposit: Pubkey::new_unique(),
+        amount: deposit.amount,
+        early: false,
+        total_pending: 2 * deposit.amount,
+    });
+    let data = requested.try_to_vec().unwrap();
+    assert_eq!(data[0], 3);
+    assert_eq!(AstrapeEvent::unpack(&data).unwrap(), requested);
+
+    let returned = AstrapeEvent::AdminFunds(AdminFundsEvent {
+        movement: FundMovement::CollateralReturned {
+            strategy: 1,
+            principal: 5_000,
+        },
+        amount: 5_500,
+    });
+    let data = returned.try_to_vec().unwrap();
+    assert_eq!(data[0], 10);
+    assert_eq!(AstrapeEvent::unpack(&data).unwrap(), returned);
+    assert!(AstrapeEvent::unpack(&data[..data.len() - 1]).is_err());
+}
@@ -0,0 +1,850 @@
+use {
+    astrape::{
+        instruction as astrape_instruction,
+        instructions::AstrapeInstruction,
+        processor::{
+            AUTHORITY_SEED, CONFIG_SEED, INVESTMENT_LEDGER_SEED, MULTISIG_PROPOSAL_SEED,
+            MULTISIG_SEED, POOL_STATE_SEED, RECEIPT_MINT_SEED, TREASURY_SEED,
+            USER_DEPOSIT_COUNTER_SEED, USER_DEPOSIT_SEED, WITHDRAWAL_POOL_SEED,
+            WITHDRAWAL_QUEUE_SEED,
+        },
+        state::{ConfigUpdate, DepositPeriod, MultisigAction, PauseFlags},
+    },
+    solana_program::{
+        bpf_loader_upgradeable,
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program, sysvar,
+    },
+    spl_associated_token_account::get_associated_token_address,
+};
+
+// The builders are checked against the account order documented on each `AstrapeInstruction`
+// variant, which is the order the processor reads them in. Expected PDAs are derived here from
+// the seeds of the processor rather than with the address helpers of the builders.
+
+const DEPOSIT_INDEX: u64 = 3;
+
+struct Keys {
+    admin: Pubkey,
+    user: Pubkey,
+    holder: Pubkey,
+    interest_mint: Pubkey,
+    collateral_mint: Pubkey,
+    price_feed: Pubkey,
+}
+
+impl Keys {
+    fn new() -> Self {
+        Self {
+            admin: Pubkey::new_unique(),
+            user: Pubkey::new_unique(),
+            holder: Pubkey::new_unique(),
+            interest_mint: Pubkey::new_unique(),
+            collateral_mint: Pubkey::new_unique(),
+            price_feed: Pubkey::new_unique(),
+        }
+    }
+
+    fn deposit(&self) -> Pubkey {
+        deposit_of(&self.user, DEPOSIT_INDEX)
+    }
+}
+
+fn pda(seeds: &[&[u8]]) -> Pubkey {
+    Pubkey::find_program_address(seeds, &astrape::id()).0
+}
+
+fn config() -> Pubkey {
+    pda(&[CONFIG_SEED])
+}
+
+fn authority() -> Pubkey {
+    pda(&[AUTHORITY_SEED])
+}
+
+fn pool_state() -> Pubkey {
+    pda(&[POOL_STATE_SEED])
+}
+
+fn withdrawal_queue() -> Pubkey {
+    pda(&[WITHDRAWAL_QUEUE_SEED])
+}
+
+fn deposit_of(user: &Pubkey, deposit_index: u64) -> Pubkey {
+    pda(&[
+        USER_DEPOSIT_SEED,
+        user.as_ref(),
+        &deposit_index.to_le_bytes(),
+    ])
+}
+
+fn receipt_mint(deposit: &Pubkey) -> Pubkey {
+    pda(&[RECEIPT_MINT_SEED, deposit.as_ref()])
+}
+
+fn receipt(holder: &Pubkey, deposit: &Pubkey) -> Pubkey {
+    get_associated_token_address(holder, &receipt_mint(deposit))
+}
+
+// Pool's token account of `mint`
+fn pool(mint: &Pubkey) -> Pubkey {
+    get_associated_token_address(&authority(), mint)
+}
+
+// Signers pay the transaction fee, which makes them writable in any case
+fn signer(key: Pubkey) -> AccountMeta {
+    AccountMeta::new(key, true)
+}
+
+fn writable(key: Pubkey) -> AccountMeta {
+    AccountMeta::new(key, false)
+}
+
+fn readonly(key: Pubkey) -> AccountMeta {
+    AccountMeta::new_readonly(key, false)
+}
+
+fn assert_accounts(instruction: &Instruction, expected: &[AccountMeta]) {
+    assert_eq!(instruction.program_id, astrape::id());
+    for (index, (account, expected)) in instruction.accounts.iter().zip(expected).enumerate() {
+        assert_eq!(account, expected, "account {}", index);
+    }
+    assert_eq!(instruction.accounts.len(), expected.len());
+}
+
+fn unpack(instruction: &Instruction) -> AstrapeInstruction {
+    AstrapeInstruction::unpack(&instruction.data).unwrap()
+}
+
+fn no_update(param: u8) -> ConfigUpdate {
+    ConfigUpdate {
+        param,
+        deposit_period: None,
+        pyth_price_max_age: None,
+        min_commission_rate: None,
+        max_commission_rate: None,
+        min_deposit_amount: None,
+        max_deposit_amount: None,
+        deposit_periods: None,
+        pyth_feed_id: None,
+        early_exit_policy: None,
+        pool_capacity: None,
+        withdrawal_sla: None,
+        late_payment_rate: None,
+    }
+}
+
+#[test]
+fn test_initialize_accounts() {
+    let keys = Keys::new();
+    let instruction = astrape_instruction::initialize(
+        &keys.admin,
+        &keys.interest_mint,
+        &keys.collateral_mint,
+        [7; 32],
+        60,
+        100,
+        300,
+        10,
+        100,
+        vec![DepositPeriod {
+            period: 100,
+            interest_rate: 50,
+            enabled: true,
+            capacity: None,
+        }],
+    );
+    assert_accounts(
+        &instruction,
+        &[
+            signer(keys.admin),
+            writable(config()),
+            writable(authority()),
+            writable(pool(&keys.interest_mint)),
+            writable(pool(&keys.collateral_mint)),
+            writable(pda(&[WITHDRAWAL_POOL_SEED])),
+            writable(pda(&[TREASURY_SEED])),
+            readonly(keys.interest_mint),
+            readonly(keys.collateral_mint),
+            readonly(system_program::id()),
+            readonly(spl_token::id()),
+            readonly(spl_associated_token_account::id()),
+            readonly(sysvar::rent::id()),
+            writable(pool_state()),
+            writable(pda(&[INVESTMENT_LEDGER_SEED])),
+            writable(withdrawal_queue()),
+            readonly(bpf_loader_upgradeable::get_program_data_address(
+                &astrape::id(),
+            )),
+        ],
+    );
+    assert!(matches!(
+        unpack(&instruction),
+        AstrapeInstruction::Initialize {
+            pyth_feed_id: [7, ..],
+            max_deposit_amount: 100,
+            ..
+        }
+    ));
+}
+
+#[test]
+fn test_admin_accounts() {
+    let keys = Keys::new();
+    let admin_interest = get_associated_token_address(&keys.admin, &keys.interest_mint);
+    let admin_collateral = get_associated_token_address(&keys.admin, &keys.collateral_mint);
+
+    assert_accounts(
+        &astrape_instruction::admin_update_config(&keys.admin, no_update(1)),
+        &[
+            signer(keys.admin),
+            writable(config()),
+            readonly(pool_state()),
+        ],
+    );
+    for instruction in [
+        astrape_instruction::propose_admin(&keys.admin, &keys.user),
+        astrape_instruction::admin_set_pause(&keys.admin, PauseFlags::default()),
+    ] {
+        assert_accounts(&instruction, &[signer(keys.admin), writable(config())]);
+    }
+    assert_accounts(
+        &astrape_instruction::accept_admin(&keys.user),
+        &[signer(keys.user), writable(config())],
+    );
+
+    let instruction =
+        astrape_instruction::admin_deposit_interest(&keys.admin, &keys.interest_mint, 5);
+    let interest_accounts = [
+        signer(keys.admin),
+        readonly(config()),
+        readonly(authority()),
+        writable(admin_interest),
+        writable(pool(&keys.interest_mint)),
+        readonly(system_program::id()),
+        readonly(spl_token::id()),
+        readonly(spl_associated_token_account::id()),
+    ];
+    assert_accounts(&instruction, &interest_accounts);
+    assert!(matches!(
+        unpack(&instruction),
+        AstrapeInstruction::AdminDepositInterest { amount: 5 }
+    ));
+    let instruction =
+        astrape_instruction::admin_withdraw_interest(&keys.admin, &keys.interest_mint, 5);
+    assert_accounts(&instruction, &interest_accounts);
+    assert!(matches!(
+        unpack(&instruction),
+        AstrapeInstruction::AdminWithdrawInterest { amount: 5 }
+    ));
+
+    assert_accounts(
+        &astrape_instruction::admin_withdraw_treasury(&keys.admin, &keys.interest_mint, 5),
+        &[
+            signer(keys.admin),
+            readonly(config()),
+            readonly(authority()),
+            writable(admin_interest),
+            writable(pda(&[TREASURY_SEED])),
+            readonly(spl_token::id()),
+        ],
+    );
+
+    let instruction = astrape_instruction::admin_withdraw_collateral_for_investment(
+        &keys.admin,
+        &keys.collateral_mint,
+        5,
+        2,
+    );
+    assert_accounts(
+        &instruction,
+        &[
+            signer(keys.admin),
+            readonly(config()),
+            readonly(authority()),
+            writable(admin_collateral),
+            writable(pool(&keys.collateral_mint)),
+            readonly(system_program::id()),
+            readonly(spl_token::id()),
+            readonly(spl_associated_token_account::id()),
+            writable(pda(&[INVESTMENT_LEDGER_SEED])),
+        ],
+    );
+    assert!(matches!(
+        unpack(&instruction),
+        AstrapeInstruction::AdminWithdrawCollateralForInvestment {
+            amount: 5,
+            strategy: 2
+        }
+    ));
+
+    assert_accounts(
+        &astrape_instruction::admin_return_collateral(&keys.admin, &keys.collateral_mint, 2, 5, 6),
+        &[
+            signer(keys.admin),
+            readonly(config()),
+            readonly(authority()),
+            writable(admin_collateral),
+            writable(pool(&keys.collateral_mint)),
+            writable(pda(&[INVESTMENT_LEDGER_SEED])),
+            readonly(spl_token::id()),
+        ],
+    );
+}
+
+#[test]
+fn test_admin_prepare_withdrawal_accounts() {
+    let keys = Keys::new();
+    let admin_collateral = get_associated_token_address(&keys.admin, &keys.collateral_mint);
+    let prepare_accounts = [
+        signer(keys.admin),
+        readonly(config()),
+        writable(admin_collateral),
+        writable(pda(&[WITHDRAWAL_POOL_SEED])),
+    ];
+
+    let instruction = astrape_instruction::admin_prepare_withdrawal(
+        &keys.admin,
+        &keys.collateral_mint,
+        &keys.holder,
+        &keys.user,
+        DEPOSIT_INDEX,
+    );
+    let mut expected = prepare_accounts.to_vec();
+    expected.extend([
+        readonly(keys.holder),
+        writable(keys.deposit()),
+        readonly(spl_token::id()),
+        writable(pool_state()),
+        writable(withdrawal_queue()),
+    ]);
+    assert_accounts(&instruction, &expected);
+    assert!(matches!(
+        unpack(&instruction),
+        AstrapeInstruction::AdminPrepareWithdrawal {
+            deposit_index: DEPOSIT_INDEX
+        }
+    ));
+
+    let instruction = astrape_instruction::admin_prepare_withdrawal_batch(
+        &keys.admin,
+        &keys.collateral_mint,
+        &[
+            (keys.holder, keys.user, DEPOSIT_INDEX),
+            (keys.user, keys.user, 0),
+        ],
+    );
+    let mut expected = prepare_accounts.to_vec();
+    expected.extend([
+        readonly(spl_token::id()),
+        writable(pool_state()),
+        writable(withdrawal_queue()),
+        readonly(keys.holder),
+        writable(keys.deposit()),
+        readonly(keys.user),
+        writable(deposit_of(&keys.user, 0)),
+    ]);
+    assert_accounts(&instruction, &expected);
+}
+
+#[test]
+fn test_deposit_collateral_accounts() {
+    let keys = Keys::new();
+    let instruction = astrape_instruction::deposit_collateral(
+        &keys.user,
+        &keys.interest_mint,
+        &keys.collateral_mint,
+        &keys.price_feed,
+        DEPOSIT_INDEX,
+        10,
+        100,
+        200,
+        true,
+    );
+    assert_accounts(
+        &instruction,
+        &[
+            signer(keys.user),
+            writable(config()),
+            readonly(authority()),
+            writable(get_associated_token_address(
+                &keys.user,
+                &keys.collateral_mint,
+            )),
+            writable(pda(&[USER_DEPOSIT_COUNTER_SEED, keys.user.as_ref()])),
+            writable(keys.deposit()),
+            writable(pool(&keys.collateral_mint)),
+            writable(get_associated_token_address(
+                &keys.user,
+                &keys.interest_mint,
+            )),
+            writable(pool(&keys.interest_mint)),
+            writable(pda(&[TREASURY_SEED])),
+            readonly(keys.price_feed),
+            readonly(system_program::id()),
+            readonly(spl_token::id()),
+            writable(receipt_mint(&keys.deposit())),
+            writable(receipt(&keys.user, &keys.deposit())),
+            readonly(spl_associated_token_account::id()),
+            writable(pool_state()),
+        ],
+    );
+    assert!(matches!(
+        unpack(&instruction),
+        AstrapeInstruction::DepositCollateral {
+            amount: 10,
+            deposit_period: 100,
+            commission_rate: 200,
+            rollover: true
+        }
+    ));
+}
+
+#[test]
+fn test_position_accounts() {
+    let keys = Keys::new();
+    let deposit = keys.deposit();
+    let holder_receipt = receipt(&keys.holder, &deposit);
+
+    let instruction = astrape_instruction::extend_deposit(
+        &keys.holder,
+        &keys.interest_mint,
+        &keys.collateral_mint,
+        &keys.price_feed,
+        &keys.user,
+        DEPOSIT_INDEX,
+        10,
+        100,
+    );
+    assert_accounts(
+        &instruction,
+        &[
+            signer(keys.holder),
+            writable(config()),
+            readonly(authority()),
+            writable(get_associated_token_address(
+                &keys.holder,
+                &keys.collateral_mint,
+            )),
+            writable(deposit),
+            writable(pool(&keys.collateral_mint)),
+            writable(get_associated_token_address(
+                &keys.holder,
+                &keys.interest_mint,
+            )),
+            writable(pool(&keys.interest_mint)),
+            writable(pda(&[TREASURY_SEED])),
+            readonly(keys.price_feed),
+            readonly(spl_token::id()),
+            writable(pool_state()),
+            readonly(holder_receipt),
+        ],
+    );
+    assert!(matches!(
+        unpack(&instruction),
+        AstrapeInstruction::ExtendDeposit {
+            deposit_index: DEPOSIT_INDEX,
+            amount: 10,
+            deposit_period: 100
+        }
+    ));
+
+    assert_accounts(
+        &astrape_instruction::set_rollover(&keys.holder, &keys.user, DEPOSIT_INDEX, true),
+        &[
+            signer(keys.holder),
+            writable(deposit),
+            readonly(holder_receipt),
+        ],
+    );
+
+    // The crank is not signed by the holder
+    assert_accounts(
+        &astrape_instruction::rollover(
+            &keys.holder,
+            &keys.interest_mint,
+            &keys.price_feed,
+            &keys.user,
+            DEPOSIT_INDEX,
+        ),
+        &[
+            readonly(keys.holder),
+            writable(config()),
+            readonly(authority()),
+            writable(deposit),
+            writable(get_associated_token_address(
+                &keys.holder,
+                &keys.interest_mint,
+            )),
+            writable(pool(&keys.interest_mint)),
+            writable(pda(&[TREASURY_SEED])),
+            readonly(keys.price_feed),
+            readonly(spl_token::id()),
+            writable(pool_state()),
+            readonly(holder_receipt),
+        ],
+    );
+
+    let new_owner = Pubkey::new_unique();
+    let instruction =
+        astrape_instruction::transfer_position(&keys.holder, &keys.user, DEPOSIT_INDEX, &new_owner);
+    assert_accounts(
+        &instruction,
+        &[
+            signer(keys.holder),
+            writable(deposit),
+            readonly(new_owner),
+            readonly(receipt_mint(&deposit)),
+            writable(holder_receipt),
+            writable(receipt(&new_owner, &deposit)),
+            readonly(system_program::id()),
+            readonly(spl_token::id()),
+            readonly(spl_associated_token_account::id()),
+        ],
+    );
+    assert!(matches!(
+        unpack(&instruction),
+        AstrapeInstruction::TransferPosition { new_owner: owner, .. } if owner == new_owner
+    ));
+
+    assert_accounts(
+        &astrape_instruction::close_deposit(&keys.holder, &keys.user, DEPOSIT_INDEX),
+        &[
+            signer(keys.holder),
+            writable(deposit),
+            writable(keys.user),
+            writable(holder_receipt),
+            readonly(spl_token::id()),
+            writable(pool_state()),
+        ],
+    );
+}
+
+#[test]
+fn test_withdrawal_request_accounts() {
+    let keys = Keys::new();
+    let deposit = keys.deposit();
+    let holder_receipt = receipt(&keys.holder, &deposit);
+    let queue_tail = Pubkey::new_unique();
+
+    let early_accounts = |tail: Pubkey| {
+        vec![
+            signer(keys.holder),
+            readonly(config()),
+            readonly(authority()),
+            writable(deposit),
+            writable(get_associated_token_address(
+                &keys.holder,
+                &keys.interest_mint,
+            )),
+            writable(pool(&keys.interest_mint)),
+            readonly(spl_token::id()),
+            writable(pool_state()),
+            readonly(holder_receipt),
+            writable(withdrawal_queue()),
+            writable(tail),
+        ]
+    };
+    assert_accounts(
+        &astrape_instruction::request_withdrawal_early(
+            &keys.holder,
+            &keys.interest_mint,
+            &keys.user,
+            DEPOSIT_INDEX,
+            Some(&queue_tail),
+        ),
+        &early_accounts(queue_tail),
+    );
+    // The queue stands in for the tail of an empty queue
+    assert_accounts(
+        &astrape_instruction::request_withdrawal_early(
+            &keys.holder,
+            &keys.interest_mint,
+            &keys.user,
+            DEPOSIT_INDEX,
+            None,
+        ),
+        &early_accounts(withdrawal_queue()),
+    );
+
+    let instruction = astrape_instruction::request_withdrawal(
+        &keys.holder,
+        &keys.user,
+        DEPOSIT_INDEX,
+        Some(&queue_tail),
+    );
+    assert_accounts(
+        &instruction,
+        &[
+            signer(keys.holder),
+            readonly(config()),
+            writable(deposit),
+            readonly(holder_receipt),
+            writable(pool_state()),
+            writable(withdrawal_queue()),
+            writable(queue_tail),
+        ],
+    );
+    assert!(matches!(
+        unpack(&instruction),
+        AstrapeInstruction::RequestWithdrawal {
+            deposit_index: DEPOSIT_INDEX
+        }
+    ));
+
+    let instruction = astrape_instruction::request_partial_withdrawal_early(
+        &keys.holder,
+        &keys.interest_mint,
+        &keys.collateral_mint,
+        &keys.user,
+        DEPOSIT_INDEX,
+        10,
+    );
+    assert_accounts(
+        &instruction,
+        &[
+            signer(keys.holder),
+            readonly(config()),
+            readonly(authority()),
+            writable(deposit),
+            writable(get_associated_token_address(
+                &keys.holder,
+                &keys.interest_mint,
+            )),
+            writable(pool(&keys.interest_mint)),
+            writable(get_associated_token_address(
+                &keys.holder,
+                &keys.collateral_mint,
+            )),
+            writable(pool(&keys.collateral_mint)),
+            readonly(spl_token::id()),
+            writable(pool_state()),
+            readonly(holder_receipt),
+        ],
+    );
+    assert!(matches!(
+        unpack(&instruction),
+        AstrapeInstruction::RequestPartialWithdrawalEarly {
+            deposit_index: DEPOSIT_INDEX,
+            amount: 10
+        }
+    ));
+}
+
+#[test]
+fn test_withdrawal_accounts() {
+    let keys = Keys::new();
+    let deposit = keys.deposit();
+    let holder_collateral = get_associated_token_address(&keys.holder, &keys.collateral_mint);
+
+    assert_accounts(
+        &astrape_instruction::withdraw_collateral(
+            &keys.holder,
+            &keys.collateral_mint,
+            &keys.user,
+            DEPOSIT_INDEX,
+        ),
+        &[
+            signer(keys.holder),
+            readonly(config()),
+            readonly(authority()),
+            writable(deposit),
+            writable(holder_collateral),
+            writable(pda(&[WITHDRAWAL_POOL_SEED])),
+            readonly(spl_token::id()),
+            writable(receipt_mint(&deposit)),
+            writable(receipt(&keys.holder, &deposit)),
+            writable(pool_state()),
+        ],
+    );
+
+    let overdue_accounts = |previous: Pubkey| {
+        vec![
+            signer(keys.holder),
+            readonly(config()),
+            readonly(authority()),
+            writable(deposit),
+            writable(holder_collateral),
+            writable(pool(&keys.collateral_mint)),
+            readonly(spl_token::id()),
+            writable(receipt_mint(&deposit)),
+            writable(receipt(&keys.holder, &deposit)),
+            writable(pool_state()),
+            writable(withdrawal_queue()),
+            writable(previous),
+        ]
+    };
+    let previous = Pubkey::new_unique();
+    assert_accounts(
+        &astrape_instruction::withdraw_overdue_collateral(
+            &keys.holder,
+            &keys.collateral_mint,
+            &keys.user,
+            DEPOSIT_INDEX,
+            Some(&previous),
+        ),
+        &overdue_accounts(previous),
+    );
+    // The oldest position stands in for its own previous position
+    assert_accounts(
+        &astrape_instruction::withdraw_overdue_collateral(
+            &keys.holder,
+            &keys.collateral_mint,
+            &keys.user,
+            DEPOSIT_INDEX,
+            None,
+        ),
+        &overdue_accounts(deposit),
+    );
+
+    assert_accounts(
+        &astrape_instruction::claim_late_payment(
+            &keys.holder,
+            &keys.interest_mint,
+            &keys.collateral_mint,
+            &keys.user,
+            DEPOSIT_INDEX,
+        ),
+        &[
+            signer(keys.holder),
+            readonly(config()),
+            readonly(authority()),
+            writable(deposit),
+            readonly(receipt(&keys.holder, &deposit)),
+            writable(get_associated_token_address(
+                &keys.holder,
+                &keys.interest_mint,
+            )),
+            writable(pool(&keys.interest_mint)),
+            readonly(pool(&keys.collateral_mint)),
+            readonly(spl_token::id()),
+        ],
+    );
+}
+
+#[test]
+fn test_multisig_accounts() {
+    let keys = Keys::new();
+    let multisig = pda(&[MULTISIG_SEED]);
+    let proposal = pda(&[MULTISIG_PROPOSAL_SEED, &4u64.to_le_bytes()]);
+
+    assert_accounts(
+        &astrape_instruction::admin_initialize_multisig(&keys.admin, vec![keys.admin], 1),
+        &[
+            signer(keys.admin),
+            writable(config()),
+            writable(multisig),
+            readonly(system_program::id()),
+        ],
+    );
+    assert_accounts(
+        &astrape_instruction::create_proposal(
+            &keys.admin,
+            4,
+            MultisigAction::UpdateConfig(Box::new(no_update(1))),
+        ),
+        &[
+            signer(keys.admin),
+            writable(multisig),
+            writable(proposal),
+            readonly(system_program::id()),
+        ],
+    );
+    assert_accounts(
+        &astrape_instruction::approve_proposal(&keys.admin, 4),
+        &[signer(keys.admin), readonly(multisig), writable(proposal)],
+    );
+
+    let execute_accounts = [
+        signer(keys.admin),
+        writable(multisig),
+        writable(proposal),
+        writable(config()),
+    ];
+    let execute = |action: &MultisigAction| {
+        astrape_instruction::execute_proposal(
+            &keys.admin,
+            4,
+            action,
+            &keys.interest_mint,
+            &keys.collateral_mint,
+        )
+    };
+    let destination = Pubkey::new_unique();
+
+    assert_accounts(
+        &execute(&MultisigAction::SetSigners {
+            signers: vec![keys.admin],
+            threshold: 1,
+        }),
+        &execute_accounts,
+    );
+    let mut expected = execute_accounts.to_vec();
+    expected.push(readonly(pool_state()));
+    assert_accounts(
+        &execute(&MultisigAction::UpdateConfig(Box::new(no_update(1)))),
+        &expected,
+    );
+    let mut expected = execute_accounts.to_vec();
+    expected.extend([
+        readonly(authority()),
+        writable(destination),
+        writable(pool(&keys.collateral_mint)),
+        readonly(spl_token::id()),
+        writable(pda(&[INVESTMENT_LEDGER_SEED])),
+    ]);
+    assert_accounts(
+        &execute(&MultisigAction::WithdrawCollateralForInvestment {
+            amount: 5,
+            strategy: 1,
+            destination,
+        }),
+        &expected,
+    );
+    let mut expected = execute_accounts.to_vec();
+    expected.extend([
+        readonly(authority()),
+        writable(destination),
+        writable(pool(&keys.interest_mint)),
+        readonly(spl_token::id()),
+    ]);
+    assert_accounts(
+        &execute(&MultisigAction::WithdrawInterest {
+            amount: 5,
+            destination,
+        }),
+        &expected,
+    );
+    let mut expected = execute_accounts.to_vec();
+    expected.extend([
+        readonly(authority()),
+        writable(destination),
+        writable(pda(&[TREASURY_SEED])),
+        readonly(spl_token::id()),
+    ]);
+    assert_accounts(
+        &execute(&MultisigAction::WithdrawTreasury {
+            amount: 5,
+            destination,
+        }),
+        &expected,
+    );
+    let mut expected = execute_accounts.to_vec();
+    expected.extend([
+        readonly(authority()),
+        writable(destination),
+        writable(pool(&keys.collateral_mint)),
+        readonly(spl_token::id()),
+        writable(pda(&[INVESTMENT_LEDGER_SEED])),
+    ]);
+    assert_accounts(
+        &execute(&MultisigAction::ReturnCollateral {
+            strategy: 1,
+            principal: 5,
+            amount: 6,
+            source: destination,
+        }),
+        &expected,
+    );
+}
@@ -1,26 +1,46 @@
 use {
     astrape::{
+        events::{
+            AdminChangedEvent, AdminFundsEvent, AdminProposedEvent, AstrapeEvent,
+            ConfigUpdatedEvent, ConfigValue, DepositClosedEvent, FundMovement, LatePaymentEvent,
+            MultisigUpdatedEvent, PauseUpdatedEvent, PositionTransferredEvent, RefundEvent,
+            RelockEvent, RolloverUpdatedEvent, WithdrawalCompletedEvent, WithdrawalPreparedEvent,
+            WithdrawalRequestedEvent,
+        },
+        instruction as astrape_instruction,
         instructions::AstrapeInstruction,
         processor::{
-            Processor, AUTHORITY_SEED, CONFIG_SEED, SLOTS_PER_MONTH, WITHDRAWAL_POOL_SEED,
+            Processor, AUTHORITY_SEED, BPS_DENOMINATOR, CONFIG_SEED, INVESTMENT_LEDGER_SEED,
+            MS_PER_SLOT, MULTISIG_PROPOSAL_SEED, MULTISIG_SEED, POOL_STATE_SEED, RATE_DENOMINATOR,
+            RECEIPT_MINT_SEED, TREASURY_SEED, USER_DEPOSIT_COUNTER_SEED, USER_DEPOSIT_SEED,
+            WITHDRAWAL_POOL_SEED, WITHDRAWAL_QUEUE_SEED,
+        },
+        state::{
+            AstrapeConfig, ConfigUpdate, DepositPeriod, EarlyExitPolicy, InvestmentLedger,
+            Multisig, MultisigAction, MultisigProposal, PauseFlags, PoolState, RefundStep,
+            UserDeposit, UserDepositCounter, UserDepositState, WithdrawalQueue,
         },
-        state::{AstrapeConfig, UserDeposit, UserDepositState},
     },
+    base64::{engine::general_purpose::STANDARD as BASE64, Engine},
     borsh::{BorshDeserialize, BorshSerialize},
-    clap::Parser,
+    pyth_solana_receiver_sdk::price_update::{
+        get_feed_id_from_hex, PriceFeedMessage, PriceUpdateV2, VerificationLevel,
+    },
     solana_program::{
+        account_info::AccountInfo,
+        bpf_loader_upgradeable::{self, UpgradeableLoaderState},
+        clock::Clock,
+        entrypoint::ProgramResult,
         instruction::{AccountMeta, Instruction},
         program_pack::Pack,
+        program_stubs::{set_syscall_stubs, SyscallStubs},
         pubkey::Pubkey,
         system_instruction,
-        sysvar::rent::Rent,
     },
     solana_program_test::*,
     solana_sdk::{
         account::Account,
         signature::{Keypair, Signer},
-        signer::EncodableKey,
-        sysvar::SysvarId,
         transaction::Transaction,
     },
     spl_associated_token_account::instruction as ata_instruction,
@@ -28,10 +48,125 @@ use {
         instruction as token_instruction,
         state::{Account as TokenAccount, Mint},
     },
+    std::{
+        cell::RefCell,
+        sync::{Once, OnceLock},
+    },
 };
 
 // Constants for testing
 const LAMPORTS_PER_SOL: u64 = 1_000_000_000;
+const BTC_USD_FEED_ID: &str = "0xe62df6c8b4a85fe1a67db44dc12de5db330f7ac66b72dc658afedf0f4a415b43";
+const PYTH_PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+// BTC/USD at $100,000, published with Pyth's usual exponent
+const MOCK_PRICE: i64 = 100_000 * 100_000_000;
+const MOCK_PRICE_EXPONENT: i32 = -8;
+// 30 days of slots
+const SLOTS_PER_MONTH: u64 = 30 * 24 * 60 * 60 * 1000 / MS_PER_SLOT;
+
+// An enabled tenor without a capacity cap
+fn tenor(period: u64, interest_rate: u64) -> DepositPeriod {
+    DepositPeriod {
+        period,
+        interest_rate,
+        enabled: true,
+        capacity: None,
+    }
+}
+
+fn pyth_price_feed_address() -> Pubkey {
+    Pubkey::find_program_address(&[b"price_feed"], &pyth_solana_receiver_sdk::ID).0
+}
+
+// The syscall stubs of `ProgramTest` print `sol_log_data` to stdout, as builtins have no
+// "Program data:" log. These forward it to the transaction logs like the SBF runtime does.
+struct EventLogStubs;
+
+static PROGRAM_TEST_STUBS: OnceLock<Box<dyn SyscallStubs>> = OnceLock::new();
+
+impl EventLogStubs {
+    // Waits for the stubs of `ProgramTest` while they are being swapped in by another test
+    fn inner(&self) -> &dyn SyscallStubs {
+        loop {
+            if let Some(stubs) = PROGRAM_TEST_STUBS.get() {
+                return stubs.as_ref();
+            }
+            std::hint::spin_loop();
+        }
+    }
+}
+
+impl SyscallStubs for EventLogStubs {
+    fn sol_log(&self, message: &str) {
+        self.inner().sol_log(message)
+    }
+    fn sol_log_compute_units(&self) {
+        self.inner().sol_log_compute_units()
+    }
+    fn sol_remaining_compute_units(&self) -> u64 {
+        self.inner().sol_remaining_compute_units()
+    }
+    fn sol_invoke_signed(
+        &self,
+        instruction: &Instruction,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+    ) -> ProgramResult {
+        self.inner()
+            .sol_invoke_signed(instruction, account_infos, signers_seeds)
+    }
+    fn sol_get_sysvar(
+        &self,
+        sysvar_id_addr: *const u8,
+        var_addr: *mut u8,
+        offset: u64,
+        length: u64,
+    ) -> u64 {
+        self.inner()
+            .sol_get_sysvar(sysvar_id_addr, var_addr, offset, length)
+    }
+    fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_clock_sysvar(var_addr)
+    }
+    fn sol_get_epoch_schedule_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_epoch_schedule_sysvar(var_addr)
+    }
+    fn sol_get_fees_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_fees_sysvar(var_addr)
+    }
+    fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_rent_sysvar(var_addr)
+    }
+    fn sol_get_epoch_rewards_sysvar(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_epoch_rewards_sysvar(var_addr)
+    }
+    fn sol_get_last_restart_slot(&self, var_addr: *mut u8) -> u64 {
+        self.inner().sol_get_last_restart_slot(var_addr)
+    }
+    fn sol_get_epoch_stake(&self, vote_address: *const u8) -> u64 {
+        self.inner().sol_get_epoch_stake(vote_address)
+    }
+    fn sol_get_return_data(&self) -> Option<(Pubkey, Vec<u8>)> {
+        self.inner().sol_get_return_data()
+    }
+    fn sol_set_return_data(&self, data: &[u8]) {
+        self.inner().sol_set_return_data(data)
+    }
+    fn sol_log_data(&self, fields: &[&[u8]]) {
+        let fields: Vec<String> = fields.iter().map(|field| BASE64.encode(field)).collect();
+        self.inner()
+            .sol_log(&format!("{}{}", EVENT_LOG_PREFIX, fields.join(" ")));
+    }
+    fn sol_get_processed_sibling_instruction(&self, index: usize) -> Option<Instruction> {
+        self.inner().sol_get_processed_sibling_instruction(index)
+    }
+    fn sol_get_stack_height(&self) -> u64 {
+        self.inner().sol_get_stack_height()
+    }
+}
+
+// `sol_log` prefixes "Program log: ", the forwarded events keep the rest of the SBF log line
+const EVENT_LOG_PREFIX: &str = "Program data: ";
 
 // Test helper struct
 struct TestHelper<'a> {
@@ -45,11 +180,17 @@ struct TestHelper<'a> {
     interest_pool_ata: Pubkey,
     collateral_pool_ata: Pubkey,
     withdrawal_pool_pda: Pubkey,
+    treasury_pda: Pubkey,
+    pool_state_pda: Pubkey,
+    investment_ledger_pda: Pubkey,
+    withdrawal_queue_pda: Pubkey,
     user_interest_ata: Pubkey,
     user_collateral_ata: Pubkey,
     admin_interest_ata: Pubkey,
     admin_collateral_ata: Pubkey,
-    user_deposit_account: Pubkey,
+    user_deposit_counter: Pubkey,
+    pyth_price_feed: Pubkey,
+    events: RefCell<Vec<AstrapeEvent>>, // Emitted by the transactions sent so far
 }
 
 impl<'a> TestHelper<'a> {
@@ -79,6 +220,12 @@ impl<'a> TestHelper<'a> {
         // Add the withdrawal pool ATA
         let (withdrawal_pool_pda, _) =
             Pubkey::find_program_address(&[WITHDRAWAL_POOL_SEED], &program_id);
+        let (treasury_pda, _) = Pubkey::find_program_address(&[TREASURY_SEED], &program_id);
+        let (pool_state_pda, _) = Pubkey::find_program_address(&[POOL_STATE_SEED], &program_id);
+        let (investment_ledger_pda, _) =
+            Pubkey::find_program_address(&[INVESTMENT_LEDGER_SEED], &program_id);
+        let (withdrawal_queue_pda, _) =
+            Pubkey::find_program_address(&[WITHDRAWAL_QUEUE_SEED], &program_id);
 
         let user_interest_ata = spl_associated_token_account::get_associated_token_address(
             &user.pubkey(),
@@ -100,9 +247,11 @@ impl<'a> TestHelper<'a> {
             &collateral_mint.pubkey(),
         );
 
-        // User deposit account (PDA derived from user pubkey)
-        let (user_deposit_account, _) =
-            Pubkey::find_program_address(&[user.pubkey().as_ref()], &program_id);
+        // User deposit counter (PDA derived from user pubkey)
+        let (user_deposit_counter, _) = Pubkey::find_program_address(
+            &[USER_DEPOSIT_COUNTER_SEED, user.pubkey().as_ref()],
+            &program_id,
+        );
 
         Self {
             admin,
@@ -115,99 +264,142 @@ impl<'a> TestHelper<'a> {
             interest_pool_ata,
             collateral_pool_ata,
             withdrawal_pool_pda,
+            treasury_pda,
+            pool_state_pda,
+            investment_ledger_pda,
+            withdrawal_queue_pda,
             user_interest_ata,
             user_collateral_ata,
             admin_interest_ata,
             admin_collateral_ata,
-            user_deposit_account,
+            user_deposit_counter,
+            pyth_price_feed: pyth_price_feed_address(),
+            events: RefCell::new(Vec::new()),
         }
     }
 
-    async fn initialize_program(
+    // Sends `instructions` signed by `signer` and keeps the events decoded from their
+    // "Program data:" logs. Must be called after `ProgramTest` started, which installs its
+    // own syscall stubs.
+    async fn process(
         &self,
         banks_client: &mut BanksClient,
+        instructions: &[Instruction],
+        signer: &Keypair,
     ) -> Result<(), BanksClientError> {
-        // Initialize the program with configuration
-        let initialize_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(self.admin.pubkey(), true), // Admin (payer & signer)
-                AccountMeta::new(self.config_pda, false),    // Config PDA
-                AccountMeta::new(self.authority_pda, false), // Authority PDA
-                AccountMeta::new(self.interest_pool_ata, false), // Interest pool ATA
-                AccountMeta::new(self.collateral_pool_ata, false), // Collateral pool ATA
-                AccountMeta::new(self.withdrawal_pool_pda, false), // Withdrawal pool account
-                AccountMeta::new_readonly(self.interest_mint.pubkey(), false), // Interest mint
-                AccountMeta::new_readonly(self.collateral_mint.pubkey(), false), // Collateral mint
-                AccountMeta::new_readonly(solana_program::system_program::id(), false), // System program
-                AccountMeta::new_readonly(spl_token::id(), false), // Token program
-                AccountMeta::new_readonly(spl_associated_token_account::id(), false), // ATA program
-                AccountMeta::new_readonly(Rent::id(), false),      // Rent sysvar
-            ],
-            data: AstrapeInstruction::Initialize {
-                interest_mint: self.interest_mint.pubkey(),
-                collateral_mint: self.collateral_mint.pubkey(),
-                base_interest_rate: 50, // 5% annual rate (in basis points)
-                price_factor: 100_000 / 10_u64.pow(8 - 6), // zBTC's decimal: 8 , USDC's decimal: 6
-                min_commission_rate: 100, // 10% commission
-                max_commission_rate: 300, // 30% commission
-                min_deposit_amount: 10_000_000, // 0.1 zBTC
-                max_deposit_amount: 100_000_000, // 1 zBTC
-                deposit_periods: vec![
-                    1 * SLOTS_PER_MONTH as u64,
-                    3 * SLOTS_PER_MONTH as u64,
-                    6 * SLOTS_PER_MONTH as u64,
-                ], // Different deposit periods in slots
-            }
-            .try_to_vec()
-            .unwrap(),
-        };
-
-        // Send the initialize instruction
-        let mut transaction =
-            Transaction::new_with_payer(&[initialize_instruction], Some(&self.admin.pubkey()));
+        static EVENT_LOG_STUBS: Once = Once::new();
+        EVENT_LOG_STUBS.call_once(|| {
+            let stubs = set_syscall_stubs(Box::new(EventLogStubs));
+            let _ = PROGRAM_TEST_STUBS.set(stubs);
+        });
 
+        let mut transaction = Transaction::new_with_payer(instructions, Some(&signer.pubkey()));
         transaction.sign(
-            &[&self.admin],
+            &[signer],
             banks_client.get_latest_blockhash().await.unwrap(),
         );
+        let result = banks_client
+            .process_transaction_with_metadata(transaction)
+            .await?;
+        result.result.map_err(BanksClientError::TransactionError)?;
+        let events = result
+            .metadata
+            .unwrap()
+            .log_messages
+            .iter()
+            .filter_map(|log| {
+                log.strip_prefix(EVENT_LOG_PREFIX).or_else(|| {
+                    log.strip_prefix("Program log: ")?
+                        .strip_prefix(EVENT_LOG_PREFIX)
+                })
+            })
+            .map(|data| AstrapeEvent::unpack(&BASE64.decode(data).unwrap()).unwrap())
+            .collect::<Vec<_>>();
+        self.events.borrow_mut().extend(events);
+        Ok(())
+    }
 
-        // Process the transaction and handle the result
-        banks_client.process_transaction(transaction).await
+    // Events emitted since the last call
+    fn take_events(&self) -> Vec<AstrapeEvent> {
+        self.events.take()
     }
 
-    async fn admin_deposit_interest(
+    // User deposit account (PDA derived from user pubkey and deposit index)
+    fn user_deposit_account(&self, deposit_index: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                USER_DEPOSIT_SEED,
+                self.user.pubkey().as_ref(),
+                &deposit_index.to_le_bytes(),
+            ],
+            &self.program_id,
+        )
+        .0
+    }
+
+    // Receipt mint of a position (PDA derived from its deposit account)
+    fn receipt_mint(&self, deposit_account: Pubkey) -> Pubkey {
+        Pubkey::find_program_address(
+            &[RECEIPT_MINT_SEED, deposit_account.as_ref()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    // User's receipt token account of a position
+    fn receipt_account(&self, deposit_account: Pubkey) -> Pubkey {
+        spl_associated_token_account::get_associated_token_address(
+            &self.user.pubkey(),
+            &self.receipt_mint(deposit_account),
+        )
+    }
+
+    async fn initialize_program(
         &self,
         banks_client: &mut BanksClient,
-        amount: u64,
     ) -> Result<(), BanksClientError> {
-        let deposit_interest_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(self.admin.pubkey(), true),
-                AccountMeta::new_readonly(self.config_pda, false),
-                AccountMeta::new_readonly(self.authority_pda, false),
-                AccountMeta::new(self.admin_interest_ata, false),
-                AccountMeta::new(self.interest_pool_ata, false),
-                AccountMeta::new_readonly(solana_program::system_program::id(), false),
-                AccountMeta::new_readonly(spl_token::id(), false),
-                AccountMeta::new_readonly(spl_associated_token_account::id(), false),
-            ],
-            data: AstrapeInstruction::AdminDepositInterest { amount }
-                .try_to_vec()
-                .unwrap(),
-        };
+        self.initialize_program_with_max_commission(banks_client, 300)
+            .await
+    }
 
-        let mut transaction = Transaction::new_with_payer(
-            &[deposit_interest_instruction],
-            Some(&self.admin.pubkey()),
+    async fn initialize_program_with_max_commission(
+        &self,
+        banks_client: &mut BanksClient,
+        max_commission_rate: u64,
+    ) -> Result<(), BanksClientError> {
+        // Initialize the program with configuration
+        let initialize_instruction = astrape_instruction::initialize(
+            &self.admin.pubkey(),
+            &self.interest_mint.pubkey(),
+            &self.collateral_mint.pubkey(),
+            get_feed_id_from_hex(BTC_USD_FEED_ID).unwrap(),
+            3600, // 1 hour
+            100,  // 10% commission
+            max_commission_rate,
+            10_000_000,  // 0.1 zBTC
+            100_000_000, // 1 zBTC
+            vec![
+                tenor(SLOTS_PER_MONTH, 50), // 5% annual rate
+                tenor(3 * SLOTS_PER_MONTH, 60),
+                tenor(6 * SLOTS_PER_MONTH, 70),
+            ], // Different deposit periods in slots
         );
+        self.process(banks_client, &[initialize_instruction], self.admin)
+            .await
+    }
 
-        transaction.sign(
-            &[&self.admin],
-            banks_client.get_latest_blockhash().await.unwrap(),
+    async fn admin_deposit_interest(
+        &self,
+        banks_client: &mut BanksClient,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let deposit_interest_instruction = astrape_instruction::admin_deposit_interest(
+            &self.admin.pubkey(),
+            &self.interest_mint.pubkey(),
+            amount,
         );
-        banks_client.process_transaction(transaction).await
+        self.process(banks_client, &[deposit_interest_instruction], self.admin)
+            .await
     }
 
     async fn deposit_collateral(
@@ -217,337 +409,798 @@ impl<'a> TestHelper<'a> {
         deposit_period: u64,
         commission_rate: u64,
     ) -> Result<(), BanksClientError> {
-        let deposit_collateral_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(self.user.pubkey(), true),
-                AccountMeta::new_readonly(self.config_pda, false),
-                AccountMeta::new_readonly(self.authority_pda, false),
-                AccountMeta::new(self.user_collateral_ata, false),
-                AccountMeta::new(self.user_deposit_account, false),
-                AccountMeta::new(self.collateral_pool_ata, false),
-                AccountMeta::new(self.user_interest_ata, false),
-                AccountMeta::new(self.interest_pool_ata, false),
-                AccountMeta::new_readonly(solana_program::system_program::id(), false),
-                AccountMeta::new_readonly(spl_token::id(), false),
-            ],
-            data: AstrapeInstruction::DepositCollateral {
-                amount,
-                deposit_period,
-                commission_rate,
-            }
-            .try_to_vec()
-            .unwrap(),
-        };
-
-        let mut transaction = Transaction::new_with_payer(
-            &[deposit_collateral_instruction],
-            Some(&self.user.pubkey()),
-        );
-
-        transaction.sign(
-            &[&self.user],
-            banks_client.get_latest_blockhash().await.unwrap(),
-        );
-        banks_client.process_transaction(transaction).await
+        self.deposit_collateral_with_rollover(
+            banks_client,
+            amount,
+            deposit_period,
+            commission_rate,
+            false,
+        )
+        .await
     }
 
-    async fn admin_withdraw_collateral_for_investment(
+    async fn deposit_collateral_with_rollover(
         &self,
         banks_client: &mut BanksClient,
+        amount: u64,
+        deposit_period: u64,
+        commission_rate: u64,
+        rollover: bool,
     ) -> Result<(), BanksClientError> {
-        let withdraw_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(self.admin.pubkey(), true), // Admin (payer & signer)
-                AccountMeta::new_readonly(self.config_pda, false), // Config PDA
-                AccountMeta::new_readonly(self.authority_pda, false), // Authority PDA
-                AccountMeta::new(self.admin_collateral_ata, false), // Admin collateral ATA
-                AccountMeta::new(self.collateral_pool_ata, false), // Collateral pool ATA
-                AccountMeta::new_readonly(solana_program::system_program::id(), false), // System program
-                AccountMeta::new_readonly(spl_token::id(), false), // Token program
-                AccountMeta::new_readonly(spl_associated_token_account::id(), false), // ATA program
-            ],
-            data: AstrapeInstruction::AdminWithdrawCollateralForInvestment
-                .try_to_vec()
-                .unwrap(),
-        };
-
-        let mut transaction =
-            Transaction::new_with_payer(&[withdraw_instruction], Some(&self.admin.pubkey()));
-
-        transaction.sign(
-            &[&self.admin],
-            banks_client.get_latest_blockhash().await.unwrap(),
+        let deposit_index = self.get_deposit_count(banks_client).await;
+        let deposit_collateral_instruction = astrape_instruction::deposit_collateral(
+            &self.user.pubkey(),
+            &self.interest_mint.pubkey(),
+            &self.collateral_mint.pubkey(),
+            &self.pyth_price_feed,
+            deposit_index,
+            amount,
+            deposit_period,
+            commission_rate,
+            rollover,
         );
-        banks_client.process_transaction(transaction).await
+        self.process(banks_client, &[deposit_collateral_instruction], self.user)
+            .await
     }
 
-    async fn request_withdrawal_early(
+    async fn extend_deposit(
         &self,
         banks_client: &mut BanksClient,
+        deposit_index: u64,
+        amount: u64,
+        deposit_period: u64,
     ) -> Result<(), BanksClientError> {
-        let request_withdrawal_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(self.user.pubkey(), true),
-                AccountMeta::new_readonly(self.config_pda, false),
-                AccountMeta::new_readonly(self.authority_pda, false),
-                AccountMeta::new(self.user_deposit_account, false),
-                AccountMeta::new(self.user_interest_ata, false),
-                AccountMeta::new(self.interest_pool_ata, false),
-                AccountMeta::new_readonly(spl_token::id(), false),
-            ],
-            data: AstrapeInstruction::RequestWithdrawalEarly
-                .try_to_vec()
-                .unwrap(),
-        };
+        self.extend_deposit_of(
+            banks_client,
+            self.user.pubkey(),
+            deposit_index,
+            amount,
+            deposit_period,
+        )
+        .await
+    }
 
-        let mut transaction = Transaction::new_with_payer(
-            &[request_withdrawal_instruction],
-            Some(&self.user.pubkey()),
+    // Extension of a position opened by `user`, whose receipt may have been transferred
+    async fn extend_deposit_of(
+        &self,
+        banks_client: &mut BanksClient,
+        user: Pubkey,
+        deposit_index: u64,
+        amount: u64,
+        deposit_period: u64,
+    ) -> Result<(), BanksClientError> {
+        let extend_deposit_instruction = astrape_instruction::extend_deposit(
+            &self.user.pubkey(),
+            &self.interest_mint.pubkey(),
+            &self.collateral_mint.pubkey(),
+            &self.pyth_price_feed,
+            &user,
+            deposit_index,
+            amount,
+            deposit_period,
         );
+        self.process(banks_client, &[extend_deposit_instruction], self.user)
+            .await
+    }
 
-        transaction.sign(
-            &[&self.user],
-            banks_client.get_latest_blockhash().await.unwrap(),
-        );
-        banks_client.process_transaction(transaction).await
+    async fn set_rollover(
+        &self,
+        banks_client: &mut BanksClient,
+        deposit_index: u64,
+        rollover: bool,
+    ) -> Result<(), BanksClientError> {
+        self.set_rollover_of(banks_client, self.user.pubkey(), deposit_index, rollover)
+            .await
     }
 
-    async fn request_withdrawal(
+    async fn set_rollover_of(
         &self,
         banks_client: &mut BanksClient,
+        user: Pubkey,
+        deposit_index: u64,
+        rollover: bool,
     ) -> Result<(), BanksClientError> {
-        let request_withdrawal_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(self.user.pubkey(), true),
-                AccountMeta::new(self.user_deposit_account, false),
-            ],
-            data: AstrapeInstruction::RequestWithdrawal
-                .try_to_vec()
-                .unwrap(),
-        };
+        let set_rollover_instruction =
+            astrape_instruction::set_rollover(&self.user.pubkey(), &user, deposit_index, rollover);
+        self.process(banks_client, &[set_rollover_instruction], self.user)
+            .await
+    }
 
-        let mut transaction = Transaction::new_with_payer(
-            &[request_withdrawal_instruction],
-            Some(&self.user.pubkey()),
+    // Sent by the admin, as anyone can crank a rollover
+    async fn rollover(
+        &self,
+        banks_client: &mut BanksClient,
+        deposit_index: u64,
+    ) -> Result<(), BanksClientError> {
+        let rollover_instruction = astrape_instruction::rollover(
+            &self.user.pubkey(),
+            &self.interest_mint.pubkey(),
+            &self.pyth_price_feed,
+            &self.user.pubkey(),
+            deposit_index,
         );
+        self.process(banks_client, &[rollover_instruction], self.admin)
+            .await
+    }
 
-        transaction.sign(
-            &[&self.user],
-            banks_client.get_latest_blockhash().await.unwrap(),
+    async fn transfer_position(
+        &self,
+        banks_client: &mut BanksClient,
+        deposit_index: u64,
+        new_owner: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let transfer_position_instruction = astrape_instruction::transfer_position(
+            &self.user.pubkey(),
+            &self.user.pubkey(),
+            deposit_index,
+            &new_owner,
         );
-        banks_client.process_transaction(transaction).await
+        self.process(banks_client, &[transfer_position_instruction], self.user)
+            .await
     }
 
-    async fn admin_prepare_withdrawal(
+    async fn admin_withdraw_collateral_for_investment(
         &self,
         banks_client: &mut BanksClient,
-        user_pubkey: Pubkey,
+        amount: u64,
+        strategy: u64,
     ) -> Result<(), BanksClientError> {
-        let prepare_withdrawal_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(self.admin.pubkey(), true),
-                AccountMeta::new_readonly(self.config_pda, false),
-                AccountMeta::new(self.admin_collateral_ata, false),
-                AccountMeta::new(self.withdrawal_pool_pda, false),
-                AccountMeta::new_readonly(user_pubkey, false),
-                AccountMeta::new(self.user_deposit_account, false),
-                AccountMeta::new_readonly(spl_token::id(), false),
-            ],
-            data: AstrapeInstruction::AdminPrepareWithdrawal
-                .try_to_vec()
-                .unwrap(),
-        };
-
-        let mut transaction = Transaction::new_with_payer(
-            &[prepare_withdrawal_instruction],
-            Some(&self.admin.pubkey()),
+        let withdraw_instruction = astrape_instruction::admin_withdraw_collateral_for_investment(
+            &self.admin.pubkey(),
+            &self.collateral_mint.pubkey(),
+            amount,
+            strategy,
         );
+        self.process(banks_client, &[withdraw_instruction], self.admin)
+            .await
+    }
 
-        transaction.sign(
-            &[&self.admin],
-            banks_client.get_latest_blockhash().await.unwrap(),
+    async fn admin_return_collateral(
+        &self,
+        banks_client: &mut BanksClient,
+        strategy: u64,
+        principal: u64,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let return_instruction = astrape_instruction::admin_return_collateral(
+            &self.admin.pubkey(),
+            &self.collateral_mint.pubkey(),
+            strategy,
+            principal,
+            amount,
         );
-        banks_client.process_transaction(transaction).await
+        self.process(banks_client, &[return_instruction], self.admin)
+            .await
     }
 
-    async fn withdraw_collateral(
+    async fn request_withdrawal_early(
         &self,
         banks_client: &mut BanksClient,
+        deposit_index: u64,
     ) -> Result<(), BanksClientError> {
-        let withdraw_collateral_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(self.user.pubkey(), true),
-                AccountMeta::new_readonly(self.config_pda, false),
-                AccountMeta::new_readonly(self.authority_pda, false),
-                AccountMeta::new(self.user_deposit_account, false),
-                AccountMeta::new(self.user_collateral_ata, false),
-                AccountMeta::new(self.withdrawal_pool_pda, false),
-                AccountMeta::new_readonly(spl_token::id(), false),
-            ],
-            data: AstrapeInstruction::WithdrawCollateral
-                .try_to_vec()
-                .unwrap(),
-        };
-
-        let mut transaction = Transaction::new_with_payer(
-            &[withdraw_collateral_instruction],
-            Some(&self.user.pubkey()),
-        );
+        self.request_withdrawal_early_of(banks_client, self.user.pubkey(), deposit_index)
+            .await
+    }
 
-        transaction.sign(
-            &[&self.user],
-            banks_client.get_latest_blockhash().await.unwrap(),
+    // Early withdrawal of a position opened by `user`, whose receipt may have been transferred
+    async fn request_withdrawal_early_of(
+        &self,
+        banks_client: &mut BanksClient,
+        user: Pubkey,
+        deposit_index: u64,
+    ) -> Result<(), BanksClientError> {
+        let queue_tail = self.read_withdrawal_queue(banks_client).await.tail;
+        let request_withdrawal_instruction = astrape_instruction::request_withdrawal_early(
+            &self.user.pubkey(),
+            &self.interest_mint.pubkey(),
+            &user,
+            deposit_index,
+            queue_tail.as_ref(),
         );
-        banks_client.process_transaction(transaction).await
+        self.process(banks_client, &[request_withdrawal_instruction], self.user)
+            .await
     }
 
-    async fn admin_update_config(
+    async fn request_withdrawal(
         &self,
         banks_client: &mut BanksClient,
+        deposit_index: u64,
     ) -> Result<(), BanksClientError> {
-        let update_config_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(self.admin.pubkey(), true),
-                AccountMeta::new(self.config_pda, false),
-            ],
-            data: AstrapeInstruction::AdminUpdateConfig {
-                param: 0,                     // Update base interest rate
-                base_interest_rate: Some(60), // Update to 6%
-                price_factor: None,
-                min_commission_rate: None,
-                max_commission_rate: None,
-                min_deposit_amount: None,
-                max_deposit_amount: None,
-                deposit_periods: None,
-            }
-            .try_to_vec()
-            .unwrap(),
-        };
-
-        let mut transaction =
-            Transaction::new_with_payer(&[update_config_instruction], Some(&self.admin.pubkey()));
-
-        transaction.sign(
-            &[&self.admin],
-            banks_client.get_latest_blockhash().await.unwrap(),
+        let queue_tail = self.read_withdrawal_queue(banks_client).await.tail;
+        let request_withdrawal_instruction = astrape_instruction::request_withdrawal(
+            &self.user.pubkey(),
+            &self.user.pubkey(),
+            deposit_index,
+            queue_tail.as_ref(),
         );
-        banks_client.process_transaction(transaction).await
+        self.process(banks_client, &[request_withdrawal_instruction], self.user)
+            .await
     }
 
-    async fn read_config(
+    async fn request_partial_withdrawal_early(
         &self,
         banks_client: &mut BanksClient,
-    ) -> Result<AstrapeConfig, Box<dyn std::error::Error>> {
-        let config_account = banks_client
-            .get_account(self.config_pda)
-            .await?
-            .ok_or("Config account not found")?;
-        Ok(AstrapeConfig::try_from_slice(&config_account.data)?)
+        deposit_index: u64,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        self.request_partial_withdrawal_early_of(
+            banks_client,
+            self.user.pubkey(),
+            deposit_index,
+            amount,
+        )
+        .await
     }
 
-    async fn get_token_balance(
+    async fn request_partial_withdrawal_early_of(
         &self,
         banks_client: &mut BanksClient,
-        token_account: &Pubkey,
-    ) -> u64 {
-        let account = banks_client
-            .get_account(*token_account)
+        user: Pubkey,
+        deposit_index: u64,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let request_withdrawal_instruction = astrape_instruction::request_partial_withdrawal_early(
+            &self.user.pubkey(),
+            &self.interest_mint.pubkey(),
+            &self.collateral_mint.pubkey(),
+            &user,
+            deposit_index,
+            amount,
+        );
+        self.process(banks_client, &[request_withdrawal_instruction], self.user)
             .await
-            .unwrap()
-            .unwrap();
-        let token_account = TokenAccount::unpack(&account.data).unwrap();
-        token_account.amount
     }
 
-    async fn get_user_deposit(
+    async fn admin_prepare_withdrawal(
         &self,
         banks_client: &mut BanksClient,
-    ) -> Result<UserDeposit, Box<dyn std::error::Error>> {
-        let deposit_account = banks_client
-            .get_account(self.user_deposit_account)
-            .await?
-            .ok_or("User deposit account not found")?;
-        Ok(UserDeposit::try_from_slice(&deposit_account.data)?)
-    }
-
-    async fn admin_withdraw_interest(&self, banks_client: &mut BanksClient, amount: u64) {
-        let withdraw_interest_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(self.admin.pubkey(), true),
-                AccountMeta::new_readonly(self.config_pda, false),
-                AccountMeta::new_readonly(self.authority_pda, false),
-                AccountMeta::new(self.admin_interest_ata, false),
-                AccountMeta::new(self.interest_pool_ata, false),
-                AccountMeta::new_readonly(solana_program::system_program::id(), false),
-                AccountMeta::new_readonly(spl_token::id(), false),
-                AccountMeta::new_readonly(spl_associated_token_account::id(), false),
-            ],
-            data: AstrapeInstruction::AdminWithdrawInterest { amount }
-                .try_to_vec()
-                .unwrap(),
-        };
-
-        let mut transaction = Transaction::new_with_payer(
-            &[withdraw_interest_instruction],
-            Some(&self.admin.pubkey()),
-        );
+        user_pubkey: Pubkey,
+        deposit_index: u64,
+    ) -> Result<(), BanksClientError> {
+        self.admin_prepare_withdrawal_of(banks_client, user_pubkey, user_pubkey, deposit_index)
+            .await
+    }
 
-        transaction.sign(
-            &[&self.admin],
-            banks_client.get_latest_blockhash().await.unwrap(),
+    // Prepares a position owned by `owner`, which may have been opened by another user
+    async fn admin_prepare_withdrawal_of(
+        &self,
+        banks_client: &mut BanksClient,
+        owner: Pubkey,
+        user: Pubkey,
+        deposit_index: u64,
+    ) -> Result<(), BanksClientError> {
+        let prepare_withdrawal_instruction = astrape_instruction::admin_prepare_withdrawal(
+            &self.admin.pubkey(),
+            &self.collateral_mint.pubkey(),
+            &owner,
+            &user,
+            deposit_index,
         );
-        banks_client.process_transaction(transaction).await.unwrap();
+        self.process(banks_client, &[prepare_withdrawal_instruction], self.admin)
+            .await
     }
 
-    // negative cases
-    async fn test_non_admin_operation(
+    // Prepares the (owner, user, deposit index) positions with a single instruction
+    async fn admin_prepare_withdrawal_batch(
         &self,
         banks_client: &mut BanksClient,
+        positions: &[(Pubkey, Pubkey, u64)],
     ) -> Result<(), BanksClientError> {
-        // Try to update config as non-admin user
-        let update_config_instruction = Instruction {
-            program_id: self.program_id,
-            accounts: vec![
-                AccountMeta::new(self.user.pubkey(), true), // User instead of admin
-                AccountMeta::new(self.config_pda, false),
-            ],
-            data: AstrapeInstruction::AdminUpdateConfig {
-                param: 0,
-                base_interest_rate: Some(80),
-                price_factor: None,
+        let prepare_withdrawal_instruction = astrape_instruction::admin_prepare_withdrawal_batch(
+            &self.admin.pubkey(),
+            &self.collateral_mint.pubkey(),
+            positions,
+        );
+        self.process(banks_client, &[prepare_withdrawal_instruction], self.admin)
+            .await
+    }
+
+    async fn withdraw_collateral(
+        &self,
+        banks_client: &mut BanksClient,
+        deposit_index: u64,
+    ) -> Result<(), BanksClientError> {
+        self.withdraw_collateral_of(banks_client, self.user.pubkey(), deposit_index)
+            .await
+    }
+
+    async fn withdraw_collateral_of(
+        &self,
+        banks_client: &mut BanksClient,
+        user: Pubkey,
+        deposit_index: u64,
+    ) -> Result<(), BanksClientError> {
+        let withdraw_collateral_instruction = astrape_instruction::withdraw_collateral(
+            &self.user.pubkey(),
+            &self.collateral_mint.pubkey(),
+            &user,
+            deposit_index,
+        );
+        self.process(banks_client, &[withdraw_collateral_instruction], self.user)
+            .await
+    }
+
+    async fn withdraw_overdue_collateral(
+        &self,
+        banks_client: &mut BanksClient,
+        deposit_index: u64,
+    ) -> Result<(), BanksClientError> {
+        let queue_previous = self
+            .queue_previous(banks_client, self.user_deposit_account(deposit_index))
+            .await;
+        let withdraw_instruction = astrape_instruction::withdraw_overdue_collateral(
+            &self.user.pubkey(),
+            &self.collateral_mint.pubkey(),
+            &self.user.pubkey(),
+            deposit_index,
+            queue_previous.as_ref(),
+        );
+        self.process(banks_client, &[withdraw_instruction], self.user)
+            .await
+    }
+
+    async fn claim_late_payment(
+        &self,
+        banks_client: &mut BanksClient,
+        deposit_index: u64,
+    ) -> Result<(), BanksClientError> {
+        let claim_instruction = astrape_instruction::claim_late_payment(
+            &self.user.pubkey(),
+            &self.interest_mint.pubkey(),
+            &self.collateral_mint.pubkey(),
+            &self.user.pubkey(),
+            deposit_index,
+        );
+        self.process(banks_client, &[claim_instruction], self.user)
+            .await
+    }
+
+    async fn close_deposit(
+        &self,
+        banks_client: &mut BanksClient,
+        deposit_index: u64,
+    ) -> Result<(), BanksClientError> {
+        self.close_deposit_of(banks_client, self.user.pubkey(), deposit_index)
+            .await
+    }
+
+    // Closes a position opened by `user` and redeemed by this user, whose rent goes back
+    // to `user`
+    async fn close_deposit_of(
+        &self,
+        banks_client: &mut BanksClient,
+        user: Pubkey,
+        deposit_index: u64,
+    ) -> Result<(), BanksClientError> {
+        let close_deposit_instruction =
+            astrape_instruction::close_deposit(&self.user.pubkey(), &user, deposit_index);
+        self.process(banks_client, &[close_deposit_instruction], self.user)
+            .await
+    }
+
+    async fn admin_update_config(
+        &self,
+        banks_client: &mut BanksClient,
+    ) -> Result<(), BanksClientError> {
+        self.admin_update_config_with(
+            banks_client,
+            ConfigUpdate {
+                param: 0, // Update the 1 month tenor rate to 6%
+                deposit_period: Some(tenor(SLOTS_PER_MONTH, 60)),
+                pyth_price_max_age: None,
                 min_commission_rate: None,
                 max_commission_rate: None,
                 min_deposit_amount: None,
                 max_deposit_amount: None,
                 deposit_periods: None,
-            }
-            .try_to_vec()
-            .unwrap(),
+                pyth_feed_id: None,
+                early_exit_policy: None,
+                pool_capacity: None,
+                withdrawal_sla: None,
+                late_payment_rate: None,
+            },
+        )
+        .await
+    }
+
+    async fn admin_update_config_with(
+        &self,
+        banks_client: &mut BanksClient,
+        update: ConfigUpdate,
+    ) -> Result<(), BanksClientError> {
+        let update_config_instruction =
+            astrape_instruction::admin_update_config(&self.admin.pubkey(), update);
+        self.process(banks_client, &[update_config_instruction], self.admin)
+            .await
+    }
+
+    async fn propose_admin(
+        &self,
+        banks_client: &mut BanksClient,
+        new_admin: Pubkey,
+    ) -> Result<(), BanksClientError> {
+        let propose_admin_instruction =
+            astrape_instruction::propose_admin(&self.admin.pubkey(), &new_admin);
+        self.process(banks_client, &[propose_admin_instruction], self.admin)
+            .await
+    }
+
+    async fn accept_admin(
+        &self,
+        banks_client: &mut BanksClient,
+        new_admin: &Keypair,
+    ) -> Result<(), BanksClientError> {
+        let accept_admin_instruction = astrape_instruction::accept_admin(&new_admin.pubkey());
+        self.process(banks_client, &[accept_admin_instruction], new_admin)
+            .await
+    }
+
+    async fn admin_set_pause(
+        &self,
+        banks_client: &mut BanksClient,
+        paused: PauseFlags,
+    ) -> Result<(), BanksClientError> {
+        let set_pause_instruction =
+            astrape_instruction::admin_set_pause(&self.admin.pubkey(), paused);
+        self.process(banks_client, &[set_pause_instruction], self.admin)
+            .await
+    }
+
+    fn multisig_pda(&self) -> Pubkey {
+        Pubkey::find_program_address(&[MULTISIG_SEED], &self.program_id).0
+    }
+
+    // Proposal account (PDA derived from the proposal index)
+    fn proposal_pda(&self, proposal_index: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[MULTISIG_PROPOSAL_SEED, &proposal_index.to_le_bytes()],
+            &self.program_id,
+        )
+        .0
+    }
+
+    async fn admin_initialize_multisig(
+        &self,
+        banks_client: &mut BanksClient,
+        signers: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<(), BanksClientError> {
+        let initialize_multisig_instruction = astrape_instruction::admin_initialize_multisig(
+            &self.admin.pubkey(),
+            signers,
+            threshold,
+        );
+        self.process(banks_client, &[initialize_multisig_instruction], self.admin)
+            .await
+    }
+
+    async fn create_proposal(
+        &self,
+        banks_client: &mut BanksClient,
+        signer: &Keypair,
+        action: MultisigAction,
+    ) -> Result<u64, BanksClientError> {
+        let proposal_index = self.read_multisig(banks_client).await.proposal_count;
+        let create_proposal_instruction =
+            astrape_instruction::create_proposal(&signer.pubkey(), proposal_index, action);
+        self.process(banks_client, &[create_proposal_instruction], signer)
+            .await?;
+        Ok(proposal_index)
+    }
+
+    async fn approve_proposal(
+        &self,
+        banks_client: &mut BanksClient,
+        signer: &Keypair,
+        proposal_index: u64,
+    ) -> Result<(), BanksClientError> {
+        let approve_proposal_instruction =
+            astrape_instruction::approve_proposal(&signer.pubkey(), proposal_index);
+        self.process(banks_client, &[approve_proposal_instruction], signer)
+            .await
+    }
+
+    async fn execute_proposal(
+        &self,
+        banks_client: &mut BanksClient,
+        signer: &Keypair,
+        proposal_index: u64,
+        action: &MultisigAction,
+    ) -> Result<(), BanksClientError> {
+        let execute_proposal_instruction = astrape_instruction::execute_proposal(
+            &signer.pubkey(),
+            proposal_index,
+            action,
+            &self.interest_mint.pubkey(),
+            &self.collateral_mint.pubkey(),
+        );
+        self.process(banks_client, &[execute_proposal_instruction], signer)
+            .await
+    }
+
+    // `proposer` proposes to return collateral from the admin's token account, and the
+    // admin approves and executes it
+    async fn return_collateral_through_multisig(
+        &self,
+        banks_client: &mut BanksClient,
+        proposer: &Keypair,
+        strategy: u64,
+        principal: u64,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let action = MultisigAction::ReturnCollateral {
+            strategy,
+            principal,
+            amount,
+            source: self.admin_collateral_ata,
         };
+        let proposal_index = self
+            .create_proposal(banks_client, proposer, action.clone())
+            .await?;
+        self.approve_proposal(banks_client, self.admin, proposal_index)
+            .await?;
+        self.execute_proposal(banks_client, self.admin, proposal_index, &action)
+            .await
+    }
 
-        let mut transaction =
-            Transaction::new_with_payer(&[update_config_instruction], Some(&self.user.pubkey()));
+    async fn read_pool_state(&self, banks_client: &mut BanksClient) -> PoolState {
+        let pool_state_account = banks_client
+            .get_account(self.pool_state_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        PoolState::unpack(&pool_state_account.data).unwrap()
+    }
 
-        transaction.sign(
-            &[&self.user],
-            banks_client.get_latest_blockhash().await.unwrap(),
+    async fn read_investment_ledger(&self, banks_client: &mut BanksClient) -> InvestmentLedger {
+        let investment_ledger_account = banks_client
+            .get_account(self.investment_ledger_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        InvestmentLedger::unpack(&investment_ledger_account.data).unwrap()
+    }
+
+    async fn read_withdrawal_queue(&self, banks_client: &mut BanksClient) -> WithdrawalQueue {
+        let withdrawal_queue_account = banks_client
+            .get_account(self.withdrawal_queue_pda)
+            .await
+            .unwrap()
+            .unwrap();
+        WithdrawalQueue::unpack(&withdrawal_queue_account.data).unwrap()
+    }
+
+    // Deposit accounts of the queued positions, oldest first
+    async fn read_queued_deposits(&self, banks_client: &mut BanksClient) -> Vec<Pubkey> {
+        let mut deposits = vec![];
+        let mut next = self.read_withdrawal_queue(banks_client).await.head;
+        while let Some(deposit_account) = next {
+            let account = banks_client
+                .get_account(deposit_account)
+                .await
+                .unwrap()
+                .unwrap();
+            next = UserDeposit::unpack(&account.data)
+                .unwrap()
+                .queued
+                .unwrap()
+                .next;
+            deposits.push(deposit_account);
+        }
+        deposits
+    }
+
+    // The position queued right before `deposit_account`, if any
+    async fn queue_previous(
+        &self,
+        banks_client: &mut BanksClient,
+        deposit_account: Pubkey,
+    ) -> Option<Pubkey> {
+        let deposits = self.read_queued_deposits(banks_client).await;
+        let index = deposits
+            .iter()
+            .position(|deposit| *deposit == deposit_account)?;
+        index.checked_sub(1).map(|previous| deposits[previous])
+    }
+
+    async fn read_multisig(&self, banks_client: &mut BanksClient) -> Multisig {
+        let multisig_account = banks_client
+            .get_account(self.multisig_pda())
+            .await
+            .unwrap()
+            .unwrap();
+        Multisig::unpack(&multisig_account.data).unwrap()
+    }
+
+    async fn read_proposal(
+        &self,
+        banks_client: &mut BanksClient,
+        proposal_index: u64,
+    ) -> MultisigProposal {
+        let proposal_account = banks_client
+            .get_account(self.proposal_pda(proposal_index))
+            .await
+            .unwrap()
+            .unwrap();
+        MultisigProposal::unpack(&proposal_account.data).unwrap()
+    }
+
+    async fn read_config(
+        &self,
+        banks_client: &mut BanksClient,
+    ) -> Result<AstrapeConfig, Box<dyn std::error::Error>> {
+        let config_account = banks_client
+            .get_account(self.config_pda)
+            .await?
+            .ok_or("Config account not found")?;
+        Ok(AstrapeConfig::unpack(&config_account.data)?)
+    }
+
+    async fn get_token_balance(
+        &self,
+        banks_client: &mut BanksClient,
+        token_account: &Pubkey,
+    ) -> u64 {
+        let account = banks_client
+            .get_account(*token_account)
+            .await
+            .unwrap()
+            .unwrap();
+        let token_account = TokenAccount::unpack(&account.data).unwrap();
+        token_account.amount
+    }
+
+    async fn get_user_deposit(
+        &self,
+        banks_client: &mut BanksClient,
+        deposit_index: u64,
+    ) -> Result<UserDeposit, Box<dyn std::error::Error>> {
+        let deposit_account = banks_client
+            .get_account(self.user_deposit_account(deposit_index))
+            .await?
+            .ok_or("User deposit account not found")?;
+        Ok(UserDeposit::unpack(&deposit_account.data)?)
+    }
+
+    async fn get_deposit_count(&self, banks_client: &mut BanksClient) -> u64 {
+        match banks_client
+            .get_account(self.user_deposit_counter)
+            .await
+            .unwrap()
+        {
+            Some(account) => {
+                UserDepositCounter::try_from_slice(&account.data)
+                    .unwrap()
+                    .deposit_count
+            }
+            None => 0,
+        }
+    }
+
+    async fn admin_withdraw_treasury(
+        &self,
+        banks_client: &mut BanksClient,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        self.admin_withdraw_treasury_to(banks_client, &self.admin_interest_ata, amount)
+            .await
+    }
+
+    async fn admin_withdraw_treasury_to(
+        &self,
+        banks_client: &mut BanksClient,
+        destination: &Pubkey,
+        amount: u64,
+    ) -> Result<(), BanksClientError> {
+        let mut withdraw_treasury_instruction = astrape_instruction::admin_withdraw_treasury(
+            &self.admin.pubkey(),
+            &self.interest_mint.pubkey(),
+            amount,
         );
+        // The builder pays out to the admin's interest token account
+        withdraw_treasury_instruction.accounts[3] = AccountMeta::new(*destination, false);
+        self.process(banks_client, &[withdraw_treasury_instruction], self.admin)
+            .await
+    }
 
-        banks_client.process_transaction(transaction).await
+    // negative cases
+    async fn test_non_admin_operation(
+        &self,
+        banks_client: &mut BanksClient,
+    ) -> Result<(), BanksClientError> {
+        // Try to update config as non-admin user
+        let update_config_instruction = astrape_instruction::admin_update_config(
+            &self.user.pubkey(), // User instead of admin
+            ConfigUpdate {
+                param: 0,
+                deposit_period: Some(tenor(SLOTS_PER_MONTH, 80)),
+                pyth_price_max_age: None,
+                min_commission_rate: None,
+                max_commission_rate: None,
+                min_deposit_amount: None,
+                max_deposit_amount: None,
+                deposit_periods: None,
+                pyth_feed_id: None,
+                early_exit_policy: None,
+                pool_capacity: None,
+                withdrawal_sla: None,
+                late_payment_rate: None,
+            },
+        );
+        self.process(banks_client, &[update_config_instruction], self.user)
+            .await
     }
 }
 
 struct TestSetup {}
 
 impl TestSetup {
+    // Mock a Pyth `PriceUpdateV2` account for the BTC/USD feed
+    fn add_price_feed(program_test: &mut ProgramTest, price: i64, exponent: i32) {
+        let publish_time = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs() as i64;
+        program_test.add_account(
+            pyth_price_feed_address(),
+            Self::price_feed_account(price, exponent, publish_time),
+        );
+    }
+
+    // Warping moves the clock far past the publish time of the mocked feed, so it is published again
+    async fn warp_to_slot(context: &mut ProgramTestContext, slot: u64) {
+        context.warp_to_slot(slot).unwrap();
+        let clock = context.banks_client.get_sysvar::<Clock>().await.unwrap();
+        context.set_account(
+            &pyth_price_feed_address(),
+            &Self::price_feed_account(MOCK_PRICE, MOCK_PRICE_EXPONENT, clock.unix_timestamp).into(),
+        );
+    }
+
+    fn price_feed_account(price: i64, exponent: i32, publish_time: i64) -> Account {
+        let price_update = PriceUpdateV2 {
+            write_authority: Pubkey::new_unique(),
+            verification_level: VerificationLevel::Full,
+            price_message: PriceFeedMessage {
+                feed_id: get_feed_id_from_hex(BTC_USD_FEED_ID).unwrap(),
+                price,
+                conf: 0,
+                exponent,
+                publish_time,
+                prev_publish_time: publish_time,
+                ema_price: price,
+                ema_conf: 0,
+            },
+            posted_slot: 0,
+        };
+        let mut data = PYTH_PRICE_UPDATE_DISCRIMINATOR.to_vec();
+        data.extend_from_slice(&price_update.try_to_vec().unwrap());
+
+        Account {
+            lamports: LAMPORTS_PER_SOL,
+            data,
+            owner: pyth_solana_receiver_sdk::ID,
+            ..Account::default()
+        }
+    }
+
+    // The program is loaded as a builtin, so its program data account is added by hand to make
+    // `upgrade_authority` the upgrade authority
+    fn add_program_data(program_test: &mut ProgramTest, upgrade_authority: &Pubkey) {
+        program_test.add_account(
+            bpf_loader_upgradeable::get_program_data_address(&astrape::id()),
+            Account::new_data(
+                LAMPORTS_PER_SOL,
+                &UpgradeableLoaderState::ProgramData {
+                    slot: 0,
+                    upgrade_authority_address: Some(*upgrade_authority),
+                },
+                &bpf_loader_upgradeable::id(),
+            )
+            .unwrap(),
+        );
+    }
+
     async fn setup(
         banks_client: &mut BanksClient,
         admin: &Keypair,
@@ -614,7 +1267,7 @@ impl TestSetup {
                     &collateral_mint.pubkey(),
                     &admin.pubkey(),
                     None,
-                    6,
+                    8,
                 )
                 .unwrap(),
             ],
@@ -757,15 +1410,9 @@ impl TestSetup {
     }
 }
 
-#[derive(Debug, clap::Parser)]
-struct TestArgs {
-    #[clap(short, long, env = "ADMIN_KEYPAIR")]
-    keypair: String,
-}
-
 #[tokio::test]
 async fn test_full_flow() {
-    env_logger::try_init();
+    let _ = env_logger::try_init();
     log::info!("=============================================");
     log::info!("STARTING TOKEN LOCK CONTRACT INTEGRATION TEST");
     log::info!("=============================================");
@@ -781,8 +1428,7 @@ async fn test_full_flow() {
         processor!(astrape::entrypoint::process_instruction),
     );
 
-    let args = TestArgs::parse();
-    let admin = Keypair::read_from_file(&args.keypair).unwrap();
+    let admin = Keypair::new();
 
     // Add account with some lamports to program_test to work with
     program_test.add_account(
@@ -803,6 +1449,19 @@ async fn test_full_flow() {
         },
     );
 
+    let new_admin = Keypair::new();
+
+    program_test.add_account(
+        new_admin.pubkey(),
+        Account {
+            lamports: LAMPORTS_PER_SOL * 100,
+            ..Account::default()
+        },
+    );
+
+    TestSetup::add_price_feed(&mut program_test, MOCK_PRICE, MOCK_PRICE_EXPONENT);
+    TestSetup::add_program_data(&mut program_test, &admin.pubkey());
+
     log::info!("Starting banks client...");
     let (mut banks_client, _payer, _recent_blockhash) = program_test.start().await;
 
@@ -908,13 +1567,18 @@ async fn test_full_flow() {
         match test_helper.read_config(&mut banks_client).await {
             Ok(config) => {
                 log::info!("Config data read successfully:");
-                log::info!("  Base interest rate: {}", config.base_interest_rate);
+                log::info!("  Deposit periods: {:?}", config.deposit_periods);
                 log::info!("  Min commission rate: {}", config.min_commission_rate);
                 log::info!("  Max commission rate: {}", config.max_commission_rate);
                 log::info!("  Interest mint: {}", config.interest_mint);
                 log::info!("  Collateral mint: {}", config.collateral_mint);
 
-                assert_eq!(config.base_interest_rate, 50);
+                assert_eq!(config.admin, admin.pubkey());
+                assert_eq!(config.pending_admin, None);
+                assert_eq!(
+                    config.deposit_period(SLOTS_PER_MONTH),
+                    Some(&tenor(SLOTS_PER_MONTH, 50))
+                );
                 assert_eq!(config.min_commission_rate, 100);
                 log::info!("✓ Configuration verified");
             }
@@ -980,7 +1644,7 @@ async fn test_full_flow() {
     log::info!("\n🔍 TESTING USER DEPOSIT COLLATERAL");
     let deposit_amount = 20_000_000; // 0.2 zBTC with 8 decimals
     let current_slot = banks_client.get_root_slot().await.unwrap();
-    let deposit_period = 1 * SLOTS_PER_MONTH as u64; // 1 month period
+    let deposit_period = SLOTS_PER_MONTH; // 1 month period
     let commission_rate = 200; // 20% commission
     log::info!("Current slot: {}", current_slot);
     log::info!("Deposit period: {}", deposit_period);
@@ -1032,27 +1696,66 @@ async fn test_full_flow() {
         pool_collateral_after / 1_000_000
     );
 
-    // Verify deposit state
-    log::info!("\nVerifying deposit state...");
-    if let Ok(user_deposit) = test_helper.get_user_deposit(&mut banks_client).await {
-        log::info!("User deposit state: {:?}", user_deposit.state);
-        log::info!("Deposit amount: {}", user_deposit.amount);
-        log::info!("Interest received: {}", user_deposit.interest_received);
+    // The user holds the receipt of the new position
+    let receipt_account = test_helper.receipt_account(test_helper.user_deposit_account(0));
+    assert_eq!(
+        test_helper
+            .get_token_balance(&mut banks_client, &receipt_account)
+            .await,
+        1
+    );
+
+    // The pool state counts the new position
+    let pool_state = test_helper.read_pool_state(&mut banks_client).await;
+    let position = test_helper
+        .get_user_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+    assert_eq!(pool_state.total_locked, deposit_amount);
+    assert_eq!(pool_state.withdrawal_obligations, 0);
+    assert_eq!(pool_state.active_deposits, 1);
+    assert_eq!(pool_state.total_interest_paid, position.interest_received);
+    let tenor_totals = pool_state.tenor_totals(deposit_period).unwrap();
+    assert_eq!(tenor_totals.count, 1);
+    assert_eq!(tenor_totals.amount, deposit_amount);
+    assert_eq!(
+        pool_state.state_totals(UserDepositState::Deposited),
+        tenor_totals
+    );
+
+    // Verify deposit state
+    log::info!("\nVerifying deposit state...");
+    if let Ok(user_deposit) = test_helper.get_user_deposit(&mut banks_client, 0).await {
+        log::info!("User deposit state: {:?}", user_deposit.state);
+        log::info!("Deposit amount: {}", user_deposit.amount);
+        log::info!("Interest received: {}", user_deposit.interest_received);
 
         // Calculate expected interest based on our parameters:
-        // 0.2 zBTC at $100k per BTC = $20,000 value
-        // Price factor = 1,000 (already accounts for decimal difference)
+        // 0.2 zBTC at $100k per BTC = $20,000 value = 20,000,000,000 USDC base units
         // Base interest rate = 5% = 0.05
-        // Period = 1 month = 1/12 year
+        // Period = 1 month = 30/365 year
         // Commission = 20% = 0.8 ratio without commission
-        // Expected interest = 20,000,000 (0.2 BTC in lamports) * 1,000 * (1 + 0.05) * (1/12) * 0.8
+        // Expected interest = 20,000,000,000 * 0.05 * (30/365) * 0.8 = 65,753,423 (rounded down)
+        let config = test_helper.read_config(&mut banks_client).await.unwrap();
+        let price = Processor::normalize_pyth_price(MOCK_PRICE, MOCK_PRICE_EXPONENT).unwrap();
         let expected_interest = Processor::calculate_interest_amount(
             deposit_amount,
+            price,
             commission_rate,
             deposit_period,
-            &test_helper.read_config(&mut banks_client).await.unwrap(),
-        );
+            &config,
+        )
+        .unwrap();
+        let expected_commission = Processor::calculate_commission_amount(
+            deposit_amount,
+            price,
+            commission_rate,
+            deposit_period,
+            &config,
+        )
+        .unwrap();
         log::info!("Expected interest: {}", expected_interest);
+        log::info!("Expected commission: {}", expected_commission);
         assert_eq!(user_deposit.interest_received, expected_interest);
         assert_eq!(user_deposit.amount, deposit_amount);
         assert_eq!(user_deposit.state, UserDepositState::Deposited);
@@ -1104,10 +1807,58 @@ async fn test_full_flow() {
         );
         assert_eq!(
             pool_interest_balance,
-            interest_deposit_amount - expected_interest,
+            interest_deposit_amount - expected_interest - expected_commission,
             "Pool should have less interest tokens"
         );
 
+        // The commission is collected in the treasury
+        assert_eq!(expected_commission, 16_438_356);
+        assert_eq!(
+            test_helper
+                .get_token_balance(&mut banks_client, &test_helper.treasury_pda)
+                .await,
+            expected_commission
+        );
+        assert_eq!(config.commission_revenue, expected_commission);
+
+        // The commission only goes to the interest token account of the admin
+        assert!(test_helper
+            .admin_withdraw_treasury_to(
+                &mut banks_client,
+                &test_helper.user_interest_ata,
+                expected_commission
+            )
+            .await
+            .is_err());
+
+        // The admin withdraws the commission, the interest pool is untouched
+        let admin_interest_before = test_helper
+            .get_token_balance(&mut banks_client, &test_helper.admin_interest_ata)
+            .await;
+        test_helper
+            .admin_withdraw_treasury(&mut banks_client, expected_commission)
+            .await
+            .unwrap();
+        assert_eq!(
+            test_helper
+                .get_token_balance(&mut banks_client, &test_helper.admin_interest_ata)
+                .await,
+            admin_interest_before + expected_commission
+        );
+        assert_eq!(
+            test_helper
+                .get_token_balance(&mut banks_client, &test_helper.treasury_pda)
+                .await,
+            0
+        );
+        assert_eq!(
+            test_helper
+                .get_token_balance(&mut banks_client, &test_helper.interest_pool_ata)
+                .await,
+            pool_interest_balance
+        );
+        log::info!("✓ Commission collected and withdrawn from the treasury");
+
         log::info!("✓ User deposit verified");
     } else {
         log::info!("Failed to read user deposit");
@@ -1133,11 +1884,40 @@ async fn test_full_flow() {
         pool_collateral_before / 1_000_000
     );
 
+    // Nothing beyond the pool balance can be withdrawn
+    assert!(test_helper
+        .admin_withdraw_collateral_for_investment(&mut banks_client, pool_collateral_before + 1, 1)
+        .await
+        .is_err());
+
+    // The collateral is split between two strategies
+    let first_strategy_amount = pool_collateral_before / 4;
+    test_helper
+        .admin_withdraw_collateral_for_investment(&mut banks_client, first_strategy_amount, 1)
+        .await
+        .unwrap();
     test_helper
-        .admin_withdraw_collateral_for_investment(&mut banks_client)
+        .admin_withdraw_collateral_for_investment(
+            &mut banks_client,
+            pool_collateral_before - first_strategy_amount,
+            2,
+        )
         .await
         .unwrap();
 
+    let investment_ledger = test_helper.read_investment_ledger(&mut banks_client).await;
+    assert_eq!(investment_ledger.total_deployed, pool_collateral_before);
+    assert_eq!(investment_ledger.deployed(1), first_strategy_amount);
+    assert_eq!(
+        investment_ledger.deployed(2),
+        pool_collateral_before - first_strategy_amount
+    );
+    assert_eq!(investment_ledger.entry_count, 2);
+    assert_eq!(investment_ledger.entries[0].strategy, 1);
+    assert_eq!(investment_ledger.entries[0].amount, first_strategy_amount);
+    assert!(investment_ledger.entries[1].slot >= investment_ledger.entries[0].slot);
+    log::info!("✓ Investment ledger records the withdrawals per strategy");
+
     // Check balances after
     let admin_collateral_after = test_helper
         .get_token_balance(&mut banks_client, &test_helper.admin_collateral_ata)
@@ -1166,14 +1946,14 @@ async fn test_full_flow() {
     // User requests early withdrawal
     log::info!("\nUser requesting early withdrawal...");
     test_helper
-        .request_withdrawal_early(&mut banks_client)
+        .request_withdrawal_early(&mut banks_client, 0)
         .await
         .unwrap();
     log::info!("✓ Early withdrawal requested");
 
     // Verify deposit state changed
     log::info!("\nVerifying deposit state after request...");
-    if let Ok(user_deposit) = test_helper.get_user_deposit(&mut banks_client).await {
+    if let Ok(user_deposit) = test_helper.get_user_deposit(&mut banks_client, 0).await {
         log::info!("Deposit state: {:?}", user_deposit.state);
         assert_eq!(user_deposit.state, UserDepositState::WithdrawRequested);
         log::info!("✓ State change verified");
@@ -1181,17 +1961,42 @@ async fn test_full_flow() {
         log::info!("Failed to read user deposit");
     }
 
+    // The position moved from the locked collateral to the withdrawal obligations
+    let pool_state = test_helper.read_pool_state(&mut banks_client).await;
+    assert_eq!(pool_state.total_locked, 0);
+    assert_eq!(pool_state.withdrawal_obligations, deposit_amount);
+    assert_eq!(pool_state.active_deposits, 1);
+    assert!(pool_state.by_tenor.is_empty());
+    assert_eq!(
+        pool_state
+            .state_totals(UserDepositState::WithdrawRequested)
+            .count,
+        1
+    );
+
+    // The request waits in the withdrawal queue
+    let withdrawal_queue = test_helper.read_withdrawal_queue(&mut banks_client).await;
+    assert_eq!(withdrawal_queue.length, 1);
+    assert_eq!(
+        test_helper.read_queued_deposits(&mut banks_client).await,
+        vec![test_helper.user_deposit_account(0)]
+    );
+    assert_eq!(withdrawal_queue.total_pending, deposit_amount);
+
     // Admin prepares for user withdrawal
     log::info!("\nAdmin preparing withdrawal...");
     test_helper
-        .admin_prepare_withdrawal(&mut banks_client, test_helper.user.pubkey())
+        .admin_prepare_withdrawal(&mut banks_client, test_helper.user.pubkey(), 0)
         .await
         .unwrap();
     log::info!("✓ Withdrawal prepared");
 
+    let withdrawal_queue = test_helper.read_withdrawal_queue(&mut banks_client).await;
+    assert_eq!(withdrawal_queue, WithdrawalQueue::default());
+
     // Verify deposit state changed again
     log::info!("\nVerifying deposit state after preparation...");
-    if let Ok(user_deposit) = test_helper.get_user_deposit(&mut banks_client).await {
+    if let Ok(user_deposit) = test_helper.get_user_deposit(&mut banks_client, 0).await {
         log::info!("Deposit state: {:?}", user_deposit.state);
         assert_eq!(user_deposit.state, UserDepositState::WithdrawReady);
         log::info!("✓ State change verified");
@@ -1212,7 +2017,7 @@ async fn test_full_flow() {
     );
 
     test_helper
-        .withdraw_collateral(&mut banks_client)
+        .withdraw_collateral(&mut banks_client, 0)
         .await
         .unwrap();
 
@@ -1227,10 +2032,17 @@ async fn test_full_flow() {
 
     // Verify deposit state is now completed
     log::info!("\nVerifying final deposit state...");
-    if let Ok(user_deposit) = test_helper.get_user_deposit(&mut banks_client).await {
+    if let Ok(user_deposit) = test_helper.get_user_deposit(&mut banks_client, 0).await {
         log::info!("Deposit state: {:?}", user_deposit.state);
         assert_eq!(user_deposit.state, UserDepositState::WithdrawCompleted);
         log::info!("✓ Withdrawal completed verified");
+        assert_eq!(
+            test_helper
+                .get_token_balance(&mut banks_client, &receipt_account)
+                .await,
+            0
+        );
+        log::info!("✓ Receipt burned");
     } else {
         log::info!("Failed to read user deposit");
     }
@@ -1272,15 +2084,109 @@ async fn test_full_flow() {
         "Withdrawal pool should be empty after withdrawal"
     );
 
+    // User closes the completed deposit
+    log::info!("\n🔍 TESTING CLOSE DEPOSIT");
+    let deposit_rent = banks_client
+        .get_account(test_helper.user_deposit_account(0))
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    let receipt_account = test_helper.receipt_account(test_helper.user_deposit_account(0));
+    let receipt_rent = banks_client
+        .get_account(receipt_account)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    let user_lamports_before = banks_client
+        .get_balance(test_helper.user.pubkey())
+        .await
+        .unwrap();
+
+    test_helper
+        .close_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+
+    let user_lamports_after = banks_client
+        .get_balance(test_helper.user.pubkey())
+        .await
+        .unwrap();
+    assert!(
+        banks_client
+            .get_account(test_helper.user_deposit_account(0))
+            .await
+            .unwrap()
+            .is_none(),
+        "Deposit account should be closed"
+    );
+    assert!(
+        banks_client
+            .get_account(receipt_account)
+            .await
+            .unwrap()
+            .is_none(),
+        "Emptied receipt account should be closed"
+    );
+    // The user pays the transaction fee out of the refunded rent
+    assert!(user_lamports_after > user_lamports_before);
+    assert!(user_lamports_after <= user_lamports_before + deposit_rent + receipt_rent);
+    log::info!("✓ Deposit closed and rent refunded");
+
+    // Nothing is left in the pool state once the position is closed
+    let pool_state = test_helper.read_pool_state(&mut banks_client).await;
+    assert_eq!(pool_state.withdrawal_obligations, 0);
+    assert_eq!(pool_state.active_deposits, 0);
+    assert_eq!(
+        pool_state
+            .state_totals(UserDepositState::WithdrawCompleted)
+            .count,
+        0
+    );
+    assert!(pool_state.total_interest_refunded > 0);
+
+    // Closing twice fails. A new blockhash keeps the retry from being deduplicated.
+    let blockhash = banks_client.get_latest_blockhash().await.unwrap();
+    banks_client
+        .get_new_latest_blockhash(&blockhash)
+        .await
+        .unwrap();
+    assert!(test_helper
+        .close_deposit(&mut banks_client, 0)
+        .await
+        .is_err());
+
+    // User can deposit again after closing
+    test_helper
+        .deposit_collateral(
+            &mut banks_client,
+            deposit_amount,
+            deposit_period,
+            commission_rate,
+        )
+        .await
+        .unwrap();
+    let user_deposit = test_helper
+        .get_user_deposit(&mut banks_client, 1)
+        .await
+        .unwrap();
+    assert_eq!(user_deposit.state, UserDepositState::Deposited);
+    assert_eq!(user_deposit.amount, deposit_amount);
+    log::info!("✓ User deposited again after closing");
+
     // Admin updates configuration
     log::info!("\n🔍 TESTING ADMIN UPDATE CONFIG");
     log::info!("Admin updating configuration...");
 
     // Get config before
     log::info!(
-        "Base interest rate before: {}",
+        "1 month interest rate before: {}",
         if let Ok(config_before) = test_helper.read_config(&mut banks_client).await {
-            config_before.base_interest_rate
+            config_before
+                .deposit_period(SLOTS_PER_MONTH)
+                .unwrap()
+                .interest_rate
         } else {
             log::info!("Failed to read config before update");
             0
@@ -1294,18 +2200,57 @@ async fn test_full_flow() {
 
     // Get config after
     if let Ok(config_after) = test_helper.read_config(&mut banks_client).await {
-        log::info!(
-            "Base interest rate after: {}",
-            config_after.base_interest_rate
-        );
+        let interest_rate = config_after
+            .deposit_period(SLOTS_PER_MONTH)
+            .unwrap()
+            .interest_rate;
+        log::info!("1 month interest rate after: {}", interest_rate);
 
-        // Verify configuration was updated
-        assert_eq!(config_after.base_interest_rate, 60); // Updated from 50 to 60
+        // Verify configuration was updated, the other tenors are kept
+        assert_eq!(interest_rate, 60); // Updated from 50 to 60
+        assert_eq!(config_after.deposit_periods.len(), 3);
         log::info!("✓ Configuration update verified");
     } else {
         log::info!("Failed to read config after update");
     }
 
+    // Admin hands over the role in two steps
+    log::info!("\n🔍 TESTING ADMIN TRANSFER");
+    test_helper
+        .propose_admin(&mut banks_client, new_admin.pubkey())
+        .await
+        .unwrap();
+    let config = test_helper.read_config(&mut banks_client).await.unwrap();
+    assert_eq!(config.admin, admin.pubkey());
+    assert_eq!(config.pending_admin, Some(new_admin.pubkey()));
+
+    // Only the proposed admin can accept
+    assert!(test_helper
+        .accept_admin(&mut banks_client, &user)
+        .await
+        .is_err());
+
+    test_helper
+        .accept_admin(&mut banks_client, &new_admin)
+        .await
+        .unwrap();
+    let config = test_helper.read_config(&mut banks_client).await.unwrap();
+    assert_eq!(config.admin, new_admin.pubkey());
+    assert_eq!(config.pending_admin, None);
+
+    // The previous admin lost its privileges, the new admin has them
+    assert!(test_helper
+        .propose_admin(&mut banks_client, admin.pubkey())
+        .await
+        .is_err());
+    let new_admin_helper =
+        TestHelper::new(&new_admin, &user, &collateral_mint, &interest_mint).await;
+    new_admin_helper
+        .admin_update_config(&mut banks_client)
+        .await
+        .unwrap();
+    log::info!("✓ Admin transfer verified");
+
     log::info!("\n=============================================");
     log::info!("ALL TESTS COMPLETED SUCCESSFULLY");
     log::info!("=============================================");
@@ -1314,7 +2259,7 @@ async fn test_full_flow() {
 #[tokio::test]
 async fn test_negative_cases() {
     // Set up the test environment similar to the main test
-    env_logger::try_init();
+    let _ = env_logger::try_init();
     log::info!("Starting negative test cases");
 
     let program_id = astrape::id();
@@ -1324,8 +2269,7 @@ async fn test_negative_cases() {
         processor!(astrape::entrypoint::process_instruction),
     );
 
-    let args = TestArgs::parse();
-    let admin = Keypair::read_from_file(&args.keypair).unwrap();
+    let admin = Keypair::new();
 
     // Add account with some lamports to program_test to work with
     program_test.add_account(
@@ -1361,6 +2305,9 @@ async fn test_negative_cases() {
         },
     );
 
+    TestSetup::add_price_feed(&mut program_test, MOCK_PRICE, MOCK_PRICE_EXPONENT);
+    TestSetup::add_program_data(&mut program_test, &admin.pubkey());
+
     log::info!("Starting banks client...");
     let (mut banks_client, _payer, _recent_blockhash) = program_test.start().await;
 
@@ -1381,12 +2328,37 @@ async fn test_negative_cases() {
     let test_helper2 = TestHelper::new(&admin, &user2, &collateral_mint, &interest_mint).await;
     let test_helper3 = TestHelper::new(&admin, &user3, &collateral_mint, &interest_mint).await;
 
-    test_helper1.initialize_program(&mut banks_client).await;
+    // Only the upgrade authority of the program can initialize it
+    let impostor_helper = TestHelper::new(&user2, &user1, &collateral_mint, &interest_mint).await;
+    assert!(impostor_helper
+        .initialize_program(&mut banks_client)
+        .await
+        .is_err());
+
+    // The commission can't exceed the whole interest
+    assert!(test_helper1
+        .initialize_program_with_max_commission(&mut banks_client, RATE_DENOMINATOR + 1)
+        .await
+        .is_err());
+
+    test_helper1
+        .initialize_program(&mut banks_client)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper1
+            .read_config(&mut banks_client)
+            .await
+            .unwrap()
+            .admin,
+        admin.pubkey()
+    );
 
     // Add some interest to the pool for tests
     test_helper1
         .admin_deposit_interest(&mut banks_client, 1_000_000_000_000)
-        .await;
+        .await
+        .unwrap();
 
     // Negative Test 1: Deposit amount below minimum
     log::info!("\n🔍 TEST CASE: Deposit below minimum amount");
@@ -1399,12 +2371,7 @@ async fn test_negative_cases() {
 
     let too_small_amount = min_deposit - 1;
     let result = test_helper1
-        .deposit_collateral(
-            &mut banks_client,
-            too_small_amount,
-            1 * SLOTS_PER_MONTH as u64,
-            200,
-        )
+        .deposit_collateral(&mut banks_client, too_small_amount, SLOTS_PER_MONTH, 200)
         .await;
     assert!(
         result.is_err(),
@@ -1423,12 +2390,7 @@ async fn test_negative_cases() {
 
     let too_large_amount = max_deposit + 1;
     let result = test_helper1
-        .deposit_collateral(
-            &mut banks_client,
-            too_large_amount,
-            1 * SLOTS_PER_MONTH as u64,
-            200,
-        )
+        .deposit_collateral(&mut banks_client, too_large_amount, SLOTS_PER_MONTH, 200)
         .await;
     assert!(
         result.is_err(),
@@ -1443,7 +2405,7 @@ async fn test_negative_cases() {
 
     // Make sure our invalid period isn't accidentally in the allowed list
     assert!(
-        !config.deposit_periods.contains(&invalid_period),
+        config.deposit_period(invalid_period).is_none(),
         "Test setup error: chosen invalid period is actually valid"
     );
 
@@ -1459,6 +2421,42 @@ async fn test_negative_cases() {
     );
     log::info!("✓ Transaction correctly failed with invalid deposit period");
 
+    // A disabled tenor keeps its rate but takes no new deposits
+    let tenor_update = |enabled| ConfigUpdate {
+        param: 0,
+        deposit_period: Some(DepositPeriod {
+            enabled,
+            ..tenor(6 * SLOTS_PER_MONTH, 70)
+        }),
+        pyth_price_max_age: None,
+        min_commission_rate: None,
+        max_commission_rate: None,
+        min_deposit_amount: None,
+        max_deposit_amount: None,
+        deposit_periods: None,
+        pyth_feed_id: None,
+        early_exit_policy: None,
+        pool_capacity: None,
+        withdrawal_sla: None,
+        late_payment_rate: None,
+    };
+    test_helper1
+        .admin_update_config_with(&mut banks_client, tenor_update(false))
+        .await
+        .unwrap();
+    let result = test_helper1
+        .deposit_collateral(&mut banks_client, 20_000_000, 6 * SLOTS_PER_MONTH, 200)
+        .await;
+    assert!(
+        result.is_err(),
+        "Transaction should fail with a disabled deposit period"
+    );
+    test_helper1
+        .admin_update_config_with(&mut banks_client, tenor_update(true))
+        .await
+        .unwrap();
+    log::info!("✓ Transaction correctly failed with disabled deposit period");
+
     // Negative Test 4: Commission rate out of range
     log::info!("\n🔍 TEST CASE: Commission rate out of range");
     let min_commission = config.min_commission_rate;
@@ -1476,7 +2474,7 @@ async fn test_negative_cases() {
         .deposit_collateral(
             &mut banks_client,
             20_000_000,
-            1 * SLOTS_PER_MONTH as u64,
+            SLOTS_PER_MONTH,
             too_low_commission,
         )
         .await;
@@ -1494,7 +2492,7 @@ async fn test_negative_cases() {
         .deposit_collateral(
             &mut banks_client,
             20_000_000,
-            1 * SLOTS_PER_MONTH as u64,
+            SLOTS_PER_MONTH,
             too_high_commission,
         )
         .await;
@@ -1504,27 +2502,75 @@ async fn test_negative_cases() {
     );
     log::info!("✓ Transaction correctly failed with commission rate too high");
 
+    // Config updates out of range
+    log::info!("\n🔍 TEST CASE: Config updates out of range");
+    let result = test_helper1
+        .admin_update_config_with(
+            &mut banks_client,
+            ConfigUpdate {
+                param: 0,
+                deposit_period: Some(tenor(SLOTS_PER_MONTH, RATE_DENOMINATOR + 1)),
+                pyth_price_max_age: None,
+                min_commission_rate: None,
+                max_commission_rate: None,
+                min_deposit_amount: None,
+                max_deposit_amount: None,
+                deposit_periods: None,
+                pyth_feed_id: None,
+                early_exit_policy: None,
+                pool_capacity: None,
+                withdrawal_sla: None,
+                late_payment_rate: None,
+            },
+        )
+        .await;
+    assert!(
+        result.is_err(),
+        "A tenor interest rate above 100% should be rejected"
+    );
+    let result = test_helper1
+        .admin_update_config_with(
+            &mut banks_client,
+            ConfigUpdate {
+                param: 3,
+                deposit_period: None,
+                pyth_price_max_age: None,
+                min_commission_rate: None,
+                max_commission_rate: Some(RATE_DENOMINATOR + 1),
+                min_deposit_amount: None,
+                max_deposit_amount: None,
+                deposit_periods: None,
+                pyth_feed_id: None,
+                early_exit_policy: None,
+                pool_capacity: None,
+                withdrawal_sla: None,
+                late_payment_rate: None,
+            },
+        )
+        .await;
+    assert!(
+        result.is_err(),
+        "A max commission rate above 100% should be rejected"
+    );
+    log::info!("✓ Config updates out of range were rejected");
+
     // Negative Test 5: Withdrawing without admin preparation
     log::info!("\n🔍 TEST CASE: Withdraw without admin preparation");
 
     // First make a valid deposit
     test_helper1
-        .deposit_collateral(
-            &mut banks_client,
-            20_000_000,
-            1 * SLOTS_PER_MONTH as u64,
-            200,
-        )
+        .deposit_collateral(&mut banks_client, 20_000_000, SLOTS_PER_MONTH, 200)
         .await
         .unwrap();
 
     // Request withdrawal (legitimate)
     test_helper1
-        .request_withdrawal_early(&mut banks_client)
-        .await;
+        .request_withdrawal_early(&mut banks_client, 0)
+        .await
+        .unwrap();
 
     // Try to withdraw without admin preparing it
-    let result = test_helper1.withdraw_collateral(&mut banks_client).await;
+    let result = test_helper1.withdraw_collateral(&mut banks_client, 0).await;
     assert!(
         result.is_err(),
         "Withdrawal should fail without admin preparation"
@@ -1542,12 +2588,12 @@ async fn test_negative_cases() {
     );
     log::info!("✓ Transaction correctly failed when non-admin attempted admin operation");
 
-    // Negative Test 7: Double deposit attempt (can't deposit twice to same account)
-    log::info!("\n🔍 TEST CASE: Double deposit attempt");
+    // Test 7: Multiple deposit positions for the same user
+    log::info!("\n🔍 TEST CASE: Multiple deposit positions");
 
     // First make a valid deposit
     let valid_amount = 20_000_000;
-    let valid_period = 1 * SLOTS_PER_MONTH as u64;
+    let valid_period = SLOTS_PER_MONTH;
     let valid_commission = 200;
 
     // First deposit should succeed
@@ -1562,23 +2608,81 @@ async fn test_negative_cases() {
     assert!(result.is_ok(), "First deposit should succeed");
     log::info!("✓ First deposit succeeded");
 
-    // Sleep for 1 second to ensure the second deposit has a different slot
-    tokio::time::sleep(std::time::Duration::from_secs(1)).await;
-
-    // Second deposit to same account should fail
+    // Second deposit opens a new position with a different lock period
     let result = test_helper2
         .deposit_collateral(
             &mut banks_client,
             valid_amount,
-            valid_period,
+            3 * SLOTS_PER_MONTH,
             valid_commission,
         )
         .await;
+    assert!(result.is_ok(), "Second deposit should open a new position");
+    assert_eq!(test_helper2.get_deposit_count(&mut banks_client).await, 2);
+
+    let first_deposit = test_helper2
+        .get_user_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+    let second_deposit = test_helper2
+        .get_user_deposit(&mut banks_client, 1)
+        .await
+        .unwrap();
+    assert_eq!(first_deposit.state, UserDepositState::Deposited);
+    assert_eq!(second_deposit.state, UserDepositState::Deposited);
+    assert_eq!(first_deposit.discriminator, UserDeposit::DISCRIMINATOR);
+    assert_eq!(second_deposit.depositor, test_helper2.user.pubkey());
+    assert_eq!(second_deposit.deposit_index, 1);
+    assert_eq!(
+        second_deposit.unlock_slot - second_deposit.deposit_slot,
+        3 * SLOTS_PER_MONTH
+    );
+    log::info!("✓ Second deposit opened a separate position");
+
+    // The deposit index has to be the one the position account is derived from
+    let queue_tail = test_helper2
+        .read_withdrawal_queue(&mut banks_client)
+        .await
+        .tail;
+    let mut mismatched_instruction = astrape_instruction::request_withdrawal_early(
+        &test_helper2.user.pubkey(),
+        &test_helper2.interest_mint.pubkey(),
+        &test_helper2.user.pubkey(),
+        1,
+        queue_tail.as_ref(),
+    );
+    mismatched_instruction.data = AstrapeInstruction::RequestWithdrawalEarly { deposit_index: 0 }
+        .pack()
+        .unwrap();
+    let result = test_helper2
+        .process(
+            &mut banks_client,
+            &[mismatched_instruction],
+            test_helper2.user,
+        )
+        .await;
     assert!(
         result.is_err(),
-        "Second deposit to same account should fail"
+        "Early withdrawal should fail with the index of another position"
     );
-    log::info!("✓ Transaction correctly failed on double deposit attempt");
+    log::info!("✓ Deposit index mismatch rejected");
+
+    // Operating on one position leaves the other untouched
+    test_helper2
+        .request_withdrawal_early(&mut banks_client, 1)
+        .await
+        .unwrap();
+    let first_deposit = test_helper2
+        .get_user_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+    let second_deposit = test_helper2
+        .get_user_deposit(&mut banks_client, 1)
+        .await
+        .unwrap();
+    assert_eq!(first_deposit.state, UserDepositState::Deposited);
+    assert_eq!(second_deposit.state, UserDepositState::WithdrawRequested);
+    log::info!("✓ Positions are managed independently");
 
     // Negative Test 8: Attempting to withdraw from a deposit that's not in withdraw ready state
     log::info!("\n🔍 TEST CASE: Withdraw from deposit not in ready state");
@@ -1595,14 +2699,2008 @@ async fn test_negative_cases() {
         .unwrap();
 
     // Try to withdraw immediately without requesting withdrawal first
-    let result = test_helper3.withdraw_collateral(&mut banks_client).await;
+    let result = test_helper3.withdraw_collateral(&mut banks_client, 0).await;
     assert!(
         result.is_err(),
         "Withdrawal should fail when deposit not in ready state"
     );
     log::info!("✓ Transaction correctly failed when withdrawing from deposit not in ready state");
 
-    log::info!("\n=============================================");
-    log::info!("ALL NEGATIVE TESTS COMPLETED SUCCESSFULLY");
-    log::info!("=============================================");
+    // Negative Test 9: Closing a deposit that is not completed
+    log::info!("\n🔍 TEST CASE: Close deposit not in completed state");
+    let result = test_helper3.close_deposit(&mut banks_client, 0).await;
+    assert!(
+        result.is_err(),
+        "Closing should fail when deposit not in completed state"
+    );
+    log::info!("✓ Transaction correctly failed when closing an active deposit");
+
+    // Negative Test 10: Paused instruction categories
+    log::info!("\n🔍 TEST CASE: Paused instruction categories");
+
+    // Only the admin can pause
+    let user_as_admin = TestHelper::new(&user3, &user3, &collateral_mint, &interest_mint).await;
+    let all_paused = PauseFlags {
+        deposits: true,
+        early_withdrawals: true,
+        withdrawal_requests: true,
+        collateral_withdrawals: true,
+    };
+    assert!(user_as_admin
+        .admin_set_pause(&mut banks_client, all_paused)
+        .await
+        .is_err());
+
+    test_helper1
+        .admin_set_pause(
+            &mut banks_client,
+            PauseFlags {
+                deposits: true,
+                early_withdrawals: true,
+                ..PauseFlags::default()
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper1
+            .read_config(&mut banks_client)
+            .await
+            .unwrap()
+            .paused,
+        PauseFlags {
+            deposits: true,
+            early_withdrawals: true,
+            ..PauseFlags::default()
+        }
+    );
+
+    let result = test_helper3
+        .deposit_collateral(
+            &mut banks_client,
+            valid_amount,
+            3 * SLOTS_PER_MONTH,
+            valid_commission,
+        )
+        .await;
+    assert!(result.is_err(), "Deposits should be paused");
+    let result = test_helper3
+        .request_withdrawal_early(&mut banks_client, 0)
+        .await;
+    assert!(result.is_err(), "Early withdrawals should be paused");
+
+    // Other categories keep working and resuming restores the paused ones
+    test_helper1
+        .admin_set_pause(&mut banks_client, PauseFlags::default())
+        .await
+        .unwrap();
+    test_helper3
+        .deposit_collateral(
+            &mut banks_client,
+            valid_amount,
+            valid_period,
+            valid_commission,
+        )
+        .await
+        .unwrap();
+    test_helper3
+        .request_withdrawal_early(&mut banks_client, 1)
+        .await
+        .unwrap();
+    log::info!("✓ Paused categories rejected and resumed");
+
+    // Negative Test 11: Deposit priced with another feed than the configured one
+    log::info!("\n🔍 TEST CASE: Price feed id mismatch");
+    let feed_id_update = |pyth_feed_id| ConfigUpdate {
+        param: 7,
+        deposit_period: None,
+        pyth_price_max_age: None,
+        min_commission_rate: None,
+        max_commission_rate: None,
+        min_deposit_amount: None,
+        max_deposit_amount: None,
+        deposit_periods: None,
+        pyth_feed_id: Some(pyth_feed_id),
+        early_exit_policy: None,
+        pool_capacity: None,
+        withdrawal_sla: None,
+        late_payment_rate: None,
+    };
+    let other_feed_id = [7; 32];
+    test_helper1
+        .admin_update_config_with(&mut banks_client, feed_id_update(other_feed_id))
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper1
+            .read_config(&mut banks_client)
+            .await
+            .unwrap()
+            .pyth_feed_id,
+        other_feed_id
+    );
+
+    let result = test_helper3
+        .deposit_collateral(
+            &mut banks_client,
+            2 * valid_amount,
+            6 * SLOTS_PER_MONTH,
+            valid_commission,
+        )
+        .await;
+    assert!(
+        result.is_err(),
+        "Deposit should fail when the price update is for another feed"
+    );
+
+    test_helper1
+        .admin_update_config_with(
+            &mut banks_client,
+            feed_id_update(get_feed_id_from_hex(BTC_USD_FEED_ID).unwrap()),
+        )
+        .await
+        .unwrap();
+    test_helper3
+        .deposit_collateral(
+            &mut banks_client,
+            valid_amount,
+            6 * SLOTS_PER_MONTH,
+            valid_commission,
+        )
+        .await
+        .unwrap();
+    log::info!("✓ Deposit only accepts the configured price feed");
+
+    // Negative Test 12: Partial early withdrawal bounds
+    log::info!("\n🔍 TEST CASE: Partial early withdrawal");
+    let result = test_helper3
+        .request_partial_withdrawal_early(&mut banks_client, 2, 15_000_000)
+        .await;
+    assert!(
+        result.is_err(),
+        "Partial withdrawal should fail when the remainder is below the minimum"
+    );
+    let result = test_helper3
+        .request_partial_withdrawal_early(&mut banks_client, 2, valid_amount)
+        .await;
+    assert!(
+        result.is_err(),
+        "Partial withdrawal of the whole position should fail"
+    );
+    let result = test_helper3
+        .request_partial_withdrawal_early(&mut banks_client, 1, 5_000_000)
+        .await;
+    assert!(
+        result.is_err(),
+        "Partial withdrawal should fail for a position already being withdrawn"
+    );
+
+    // The withdrawn part can be below the minimum deposit as long as the rest is not. It is
+    // paid out right away and the position shrinks in place.
+    let position = test_helper3
+        .get_user_deposit(&mut banks_client, 2)
+        .await
+        .unwrap();
+    let user_collateral_before = test_helper3
+        .get_token_balance(&mut banks_client, &test_helper3.user_collateral_ata)
+        .await;
+    let user_interest_before = test_helper3
+        .get_token_balance(&mut banks_client, &test_helper3.user_interest_ata)
+        .await;
+    let pool_state_before = test_helper3.read_pool_state(&mut banks_client).await;
+    test_helper3
+        .request_partial_withdrawal_early(&mut banks_client, 2, 5_000_000)
+        .await
+        .unwrap();
+    assert_eq!(test_helper3.get_deposit_count(&mut banks_client).await, 3);
+    let remaining = test_helper3
+        .get_user_deposit(&mut banks_client, 2)
+        .await
+        .unwrap();
+    let interest_share = position.interest_received * 5_000_000 / valid_amount;
+    assert_eq!(remaining.state, UserDepositState::Deposited);
+    assert_eq!(remaining.amount, valid_amount - 5_000_000);
+    assert_eq!(remaining.unlock_slot, position.unlock_slot);
+    assert_eq!(
+        remaining.interest_received,
+        position.interest_received - interest_share
+    );
+    assert_eq!(
+        test_helper3
+            .get_token_balance(&mut banks_client, &test_helper3.user_collateral_ata)
+            .await,
+        user_collateral_before + 5_000_000
+    );
+    let refund = user_interest_before
+        - test_helper3
+            .get_token_balance(&mut banks_client, &test_helper3.user_interest_ata)
+            .await;
+    assert!(refund > 0 && refund <= interest_share);
+
+    // The pool stops counting the withdrawn collateral and its refunded interest
+    let pool_state = test_helper3.read_pool_state(&mut banks_client).await;
+    assert_eq!(
+        pool_state.total_locked,
+        pool_state_before.total_locked - 5_000_000
+    );
+    assert_eq!(
+        pool_state.active_deposits,
+        pool_state_before.active_deposits
+    );
+    assert_eq!(
+        pool_state.total_interest_refunded,
+        pool_state_before.total_interest_refunded + refund
+    );
+    log::info!("✓ Partial early withdrawal shrank the position");
+
+    // Negative Test 13: Early exit refund policy
+    log::info!("\n🔍 TEST CASE: Early exit refund policy");
+    let policy_update = |policy| ConfigUpdate {
+        param: 8,
+        deposit_period: None,
+        pyth_price_max_age: None,
+        min_commission_rate: None,
+        max_commission_rate: None,
+        min_deposit_amount: None,
+        max_deposit_amount: None,
+        deposit_periods: None,
+        pyth_feed_id: None,
+        early_exit_policy: Some(policy),
+        pool_capacity: None,
+        withdrawal_sla: None,
+        late_payment_rate: None,
+    };
+    let result = test_helper1
+        .admin_update_config_with(
+            &mut banks_client,
+            policy_update(EarlyExitPolicy::FlatPenalty {
+                penalty_bps: BPS_DENOMINATOR + 1,
+            }),
+        )
+        .await;
+    assert!(result.is_err(), "Penalty above 100% should be rejected");
+
+    // Half of the interest is refunded before the lock ends
+    let half_refund = EarlyExitPolicy::StepSchedule(vec![RefundStep {
+        until_bps: BPS_DENOMINATOR,
+        refund_bps: BPS_DENOMINATOR / 2,
+    }]);
+    test_helper1
+        .admin_update_config_with(&mut banks_client, policy_update(half_refund.clone()))
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper1
+            .read_config(&mut banks_client)
+            .await
+            .unwrap()
+            .early_exit_policy,
+        half_refund
+    );
+
+    let user_interest_before = test_helper3
+        .get_token_balance(&mut banks_client, &test_helper3.user_interest_ata)
+        .await;
+    test_helper3
+        .request_withdrawal_early(&mut banks_client, 2)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper3
+            .get_token_balance(&mut banks_client, &test_helper3.user_interest_ata)
+            .await,
+        user_interest_before - remaining.interest_received / 2
+    );
+
+    test_helper1
+        .admin_update_config_with(
+            &mut banks_client,
+            policy_update(EarlyExitPolicy::LinearUnearned),
+        )
+        .await
+        .unwrap();
+    log::info!("✓ Early exit refund follows the configured policy");
+
+    // Negative Test 14: Extending an active deposit
+    log::info!("\n🔍 TEST CASE: Extend deposit");
+    let result = test_helper3
+        .extend_deposit(&mut banks_client, 0, 0, 2 * SLOTS_PER_MONTH)
+        .await;
+    assert!(
+        result.is_err(),
+        "Extension should fail for an unknown tenor"
+    );
+    let result = test_helper3
+        .extend_deposit(&mut banks_client, 0, 100_000_000, valid_period)
+        .await;
+    assert!(
+        result.is_err(),
+        "Extension should fail when the position exceeds the max amount"
+    );
+    let result = test_helper3
+        .extend_deposit(&mut banks_client, 1, 5_000_000, valid_period)
+        .await;
+    assert!(
+        result.is_err(),
+        "Extension should fail for a position already being withdrawn"
+    );
+
+    let position = test_helper3
+        .get_user_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+    let user_interest_before = test_helper3
+        .get_token_balance(&mut banks_client, &test_helper3.user_interest_ata)
+        .await;
+    test_helper3
+        .extend_deposit(&mut banks_client, 0, 5_000_000, 3 * SLOTS_PER_MONTH)
+        .await
+        .unwrap();
+    let extended = test_helper3
+        .get_user_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+    assert_eq!(extended.state, UserDepositState::Deposited);
+    assert_eq!(extended.amount, position.amount + 5_000_000);
+    assert!(extended.deposit_slot >= position.deposit_slot);
+    assert!(extended.unlock_slot >= position.unlock_slot + 2 * SLOTS_PER_MONTH);
+    // The lock restarts, and the interest still to be earned is the unearned part of the
+    // previous interest plus the interest paid for the extension
+    let interest_paid = test_helper3
+        .get_token_balance(&mut banks_client, &test_helper3.user_interest_ata)
+        .await
+        - user_interest_before;
+    assert!(interest_paid > 0);
+    assert!(extended.interest_received > interest_paid);
+    assert!(extended.interest_received <= position.interest_received + interest_paid);
+    log::info!("✓ Extension paid interest for the increment only");
+
+    // Negative Test 15: Rollover before maturity or without opting in
+    log::info!("\n🔍 TEST CASE: Rollover");
+    test_helper3
+        .deposit_collateral_with_rollover(
+            &mut banks_client,
+            valid_amount,
+            valid_period,
+            valid_commission,
+            true,
+        )
+        .await
+        .unwrap();
+    let rollover_index = test_helper3.get_deposit_count(&mut banks_client).await - 1;
+    let position = test_helper3
+        .get_user_deposit(&mut banks_client, rollover_index)
+        .await
+        .unwrap();
+    assert!(position.rollover);
+    assert_eq!(position.deposit_period, valid_period);
+
+    let result = test_helper3
+        .rollover(&mut banks_client, rollover_index)
+        .await;
+    assert!(result.is_err(), "Rollover should fail before maturity");
+    let result = test_helper3.rollover(&mut banks_client, 0).await;
+    assert!(
+        result.is_err(),
+        "Rollover should fail for a position which did not opt in"
+    );
+
+    test_helper3
+        .set_rollover(&mut banks_client, rollover_index, false)
+        .await
+        .unwrap();
+    assert!(
+        !test_helper3
+            .get_user_deposit(&mut banks_client, rollover_index)
+            .await
+            .unwrap()
+            .rollover
+    );
+    let result = test_helper3.set_rollover(&mut banks_client, 1, true).await;
+    assert!(
+        result.is_err(),
+        "Rollover cannot be set on a position already being withdrawn"
+    );
+    log::info!("✓ Rollover only applies to matured positions which opted in");
+
+    // Negative Test 16: Transferred positions follow their owner
+    log::info!("\n🔍 TEST CASE: Transfer position");
+    let position = test_helper2
+        .get_user_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+    assert_eq!(position.owner, user2.pubkey());
+    test_helper2
+        .transfer_position(&mut banks_client, 0, user3.pubkey())
+        .await
+        .unwrap();
+    let position = test_helper2
+        .get_user_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+    assert_eq!(position.owner, user3.pubkey());
+
+    // The receipt moved along with the position
+    let transferred_account = test_helper2.user_deposit_account(0);
+    assert_eq!(
+        test_helper2
+            .get_token_balance(
+                &mut banks_client,
+                &test_helper2.receipt_account(transferred_account)
+            )
+            .await,
+        0
+    );
+    assert_eq!(
+        test_helper3
+            .get_token_balance(
+                &mut banks_client,
+                &test_helper3.receipt_account(transferred_account)
+            )
+            .await,
+        1
+    );
+
+    // The original depositor has no rights left on the position
+    let result = test_helper2
+        .request_withdrawal_early(&mut banks_client, 0)
+        .await;
+    assert!(
+        result.is_err(),
+        "Previous owner should not withdraw a transferred position"
+    );
+    let result = test_helper2
+        .transfer_position(&mut banks_client, 0, user2.pubkey())
+        .await;
+    assert!(
+        result.is_err(),
+        "Previous owner should not transfer the position back"
+    );
+
+    test_helper3
+        .request_withdrawal_early_of(&mut banks_client, test_helper2.user.pubkey(), 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper2
+            .get_user_deposit(&mut banks_client, 0)
+            .await
+            .unwrap()
+            .state,
+        UserDepositState::WithdrawRequested
+    );
+    log::info!("✓ Only the new owner can act on a transferred position");
+
+    // Negative Test 17: The receipt authorizes the position wherever it goes
+    log::info!("\n🔍 TEST CASE: Receipt holder");
+    test_helper2
+        .deposit_collateral(
+            &mut banks_client,
+            valid_amount,
+            valid_period,
+            valid_commission,
+        )
+        .await
+        .unwrap();
+    let sold_index = test_helper2.get_deposit_count(&mut banks_client).await - 1;
+    let sold_account = test_helper2.user_deposit_account(sold_index);
+    let receipt_mint = test_helper2.receipt_mint(sold_account);
+
+    // user2 sells the receipt to user3 with a plain token transfer
+    let transfer_receipt_ix = [
+        ata_instruction::create_associated_token_account_idempotent(
+            &user2.pubkey(),
+            &user3.pubkey(),
+            &receipt_mint,
+            &spl_token::id(),
+        ),
+        token_instruction::transfer_checked(
+            &spl_token::id(),
+            &test_helper2.receipt_account(sold_account),
+            &receipt_mint,
+            &test_helper3.receipt_account(sold_account),
+            &user2.pubkey(),
+            &[],
+            1,
+            0,
+        )
+        .unwrap(),
+    ];
+    let mut transaction = Transaction::new_with_payer(&transfer_receipt_ix, Some(&user2.pubkey()));
+    transaction.sign(
+        &[&user2],
+        banks_client.get_latest_blockhash().await.unwrap(),
+    );
+    banks_client.process_transaction(transaction).await.unwrap();
+
+    // The seller is still the recorded owner, but without the receipt it can't act
+    assert!(test_helper2
+        .request_withdrawal_early(&mut banks_client, sold_index)
+        .await
+        .is_err());
+    assert!(test_helper2
+        .request_partial_withdrawal_early(&mut banks_client, sold_index, 5_000_000)
+        .await
+        .is_err());
+    assert!(test_helper2
+        .extend_deposit(&mut banks_client, sold_index, 5_000_000, valid_period)
+        .await
+        .is_err());
+    assert!(test_helper2
+        .set_rollover(&mut banks_client, sold_index, true)
+        .await
+        .is_err());
+    assert!(test_helper2
+        .transfer_position(&mut banks_client, sold_index, user2.pubkey())
+        .await
+        .is_err());
+
+    // The buyer acts on the position and becomes its owner
+    test_helper3
+        .set_rollover_of(&mut banks_client, user2.pubkey(), sold_index, true)
+        .await
+        .unwrap();
+    let position = test_helper2
+        .get_user_deposit(&mut banks_client, sold_index)
+        .await
+        .unwrap();
+    assert!(position.rollover);
+    assert_eq!(position.owner, user3.pubkey());
+    test_helper3
+        .extend_deposit_of(
+            &mut banks_client,
+            user2.pubkey(),
+            sold_index,
+            5_000_000,
+            valid_period,
+        )
+        .await
+        .unwrap();
+    test_helper3
+        .request_partial_withdrawal_early_of(
+            &mut banks_client,
+            user2.pubkey(),
+            sold_index,
+            5_000_000,
+        )
+        .await
+        .unwrap();
+    // The partial exit shrinks the position in place, so the receipt stays with it
+    let position = test_helper2
+        .get_user_deposit(&mut banks_client, sold_index)
+        .await
+        .unwrap();
+    assert_eq!(position.amount, valid_amount);
+    assert_eq!(position.state, UserDepositState::Deposited);
+    assert_eq!(
+        test_helper2.get_deposit_count(&mut banks_client).await,
+        sold_index + 1
+    );
+    assert_eq!(
+        test_helper3
+            .get_token_balance(
+                &mut banks_client,
+                &test_helper3.receipt_account(sold_account)
+            )
+            .await,
+        1
+    );
+
+    // The buyer exits early, the position joins the withdrawal queue
+    test_helper3
+        .request_withdrawal_early_of(&mut banks_client, user2.pubkey(), sold_index)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper3
+            .read_withdrawal_queue(&mut banks_client)
+            .await
+            .tail,
+        Some(sold_account)
+    );
+    log::info!("✓ Only the receipt holder can act on a position");
+
+    // Negative Test 18: Pool and tenor capacity caps
+    log::info!("\n🔍 TEST CASE: Deposit capacity caps");
+    let capacity_update = |pool_capacity| ConfigUpdate {
+        param: 9,
+        deposit_period: None,
+        pyth_price_max_age: None,
+        min_commission_rate: None,
+        max_commission_rate: None,
+        min_deposit_amount: None,
+        max_deposit_amount: None,
+        deposit_periods: None,
+        pyth_feed_id: None,
+        early_exit_policy: None,
+        pool_capacity: Some(pool_capacity),
+        withdrawal_sla: None,
+        late_payment_rate: None,
+    };
+    let pool_state = test_helper3.read_pool_state(&mut banks_client).await;
+    let result = test_helper1
+        .admin_update_config_with(
+            &mut banks_client,
+            capacity_update(Some(pool_state.total_locked - 1)),
+        )
+        .await;
+    assert!(
+        result.is_err(),
+        "Pool capacity below the locked collateral should be rejected"
+    );
+    test_helper1
+        .admin_update_config_with(
+            &mut banks_client,
+            capacity_update(Some(pool_state.total_locked + 30_000_000)),
+        )
+        .await
+        .unwrap();
+    let result = test_helper3
+        .deposit_collateral(&mut banks_client, 40_000_000, valid_period, 200)
+        .await;
+    assert!(
+        result.is_err(),
+        "Deposit should fail above the pool capacity"
+    );
+    test_helper3
+        .deposit_collateral(&mut banks_client, 30_000_000, valid_period, 200)
+        .await
+        .unwrap();
+    let result = test_helper3
+        .extend_deposit(&mut banks_client, 0, 10_000_000, 3 * SLOTS_PER_MONTH)
+        .await;
+    assert!(
+        result.is_err(),
+        "Extension should fail once the pool is full"
+    );
+    test_helper1
+        .admin_update_config_with(&mut banks_client, capacity_update(None))
+        .await
+        .unwrap();
+
+    // A tenor cap only limits deposits into that tenor
+    let six_months = 6 * SLOTS_PER_MONTH;
+    test_helper3
+        .deposit_collateral(&mut banks_client, 20_000_000, six_months, 200)
+        .await
+        .unwrap();
+    let tenor_locked = test_helper3
+        .read_pool_state(&mut banks_client)
+        .await
+        .tenor_totals(six_months)
+        .map_or(0, |totals| totals.amount);
+    let tenor_capacity_update = |capacity| ConfigUpdate {
+        param: 0,
+        deposit_period: Some(DepositPeriod {
+            capacity,
+            ..tenor(six_months, 70)
+        }),
+        pyth_price_max_age: None,
+        min_commission_rate: None,
+        max_commission_rate: None,
+        min_deposit_amount: None,
+        max_deposit_amount: None,
+        deposit_periods: None,
+        pyth_feed_id: None,
+        early_exit_policy: None,
+        pool_capacity: None,
+        withdrawal_sla: None,
+        late_payment_rate: None,
+    };
+    let result = test_helper1
+        .admin_update_config_with(
+            &mut banks_client,
+            tenor_capacity_update(Some(tenor_locked - 1)),
+        )
+        .await;
+    assert!(
+        result.is_err(),
+        "Tenor capacity below its locked collateral should be rejected"
+    );
+    test_helper1
+        .admin_update_config_with(
+            &mut banks_client,
+            tenor_capacity_update(Some(tenor_locked + 10_000_000)),
+        )
+        .await
+        .unwrap();
+    let result = test_helper3
+        .deposit_collateral(&mut banks_client, 20_000_000, six_months, 200)
+        .await;
+    assert!(
+        result.is_err(),
+        "Deposit should fail above the tenor capacity"
+    );
+    test_helper3
+        .deposit_collateral(&mut banks_client, 20_000_000, valid_period, 200)
+        .await
+        .unwrap();
+    test_helper1
+        .admin_update_config_with(&mut banks_client, tenor_capacity_update(None))
+        .await
+        .unwrap();
+    test_helper3
+        .deposit_collateral(&mut banks_client, 20_000_000, six_months, 200)
+        .await
+        .unwrap();
+    log::info!("✓ Deposits are capped by the pool and tenor capacity");
+
+    // Negative Test 19: Withdrawals are prepared in the order they were requested
+    log::info!("\n🔍 TEST CASE: Withdrawal queue order");
+    let withdrawal_queue = test_helper1.read_withdrawal_queue(&mut banks_client).await;
+    let queued_deposits = test_helper1.read_queued_deposits(&mut banks_client).await;
+    assert_eq!(queued_deposits[0], test_helper1.user_deposit_account(0));
+    assert_eq!(queued_deposits.len() as u64, withdrawal_queue.length);
+    assert_eq!(withdrawal_queue.tail, queued_deposits.last().copied());
+    let result = test_helper1
+        .admin_prepare_withdrawal(&mut banks_client, test_helper2.user.pubkey(), 1)
+        .await;
+    assert!(
+        result.is_err(),
+        "Only the oldest withdrawal request can be prepared"
+    );
+    assert_eq!(
+        test_helper1.read_withdrawal_queue(&mut banks_client).await,
+        withdrawal_queue
+    );
+    log::info!("✓ Withdrawal queue is served in order");
+
+    // Negative Test 20: Batch preparation of the whole withdrawal queue
+    log::info!("\n🔍 TEST CASE: Batch withdrawal preparation");
+    let mut positions = Vec::new();
+    let mut queued_amount = 0;
+    for queued_deposit in &queued_deposits {
+        let deposit_account = banks_client
+            .get_account(*queued_deposit)
+            .await
+            .unwrap()
+            .unwrap();
+        let deposit = UserDeposit::unpack(&deposit_account.data).unwrap();
+        queued_amount += deposit.amount;
+        positions.push((deposit.owner, deposit.depositor, deposit.deposit_index));
+    }
+    assert!(positions.len() > 2);
+    assert_eq!(withdrawal_queue.total_pending, queued_amount);
+    let total_pending = withdrawal_queue.total_pending;
+
+    // The admin funds the batch with collateral taken out of the pool
+    test_helper1
+        .admin_withdraw_collateral_for_investment(&mut banks_client, total_pending, 1)
+        .await
+        .unwrap();
+    let withdrawal_pool_before = test_helper1
+        .get_token_balance(&mut banks_client, &test_helper1.withdrawal_pool_pda)
+        .await;
+
+    // Positions out of queue order, repeated or missing are rejected
+    let mut swapped = positions.clone();
+    swapped.swap(0, 1);
+    assert!(test_helper1
+        .admin_prepare_withdrawal_batch(&mut banks_client, &swapped)
+        .await
+        .is_err());
+    assert!(test_helper1
+        .admin_prepare_withdrawal_batch(&mut banks_client, &[positions[0], positions[0]])
+        .await
+        .is_err());
+    assert!(test_helper1
+        .admin_prepare_withdrawal_batch(&mut banks_client, &[])
+        .await
+        .is_err());
+
+    test_helper1
+        .admin_prepare_withdrawal_batch(&mut banks_client, &positions)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper1
+            .get_token_balance(&mut banks_client, &test_helper1.withdrawal_pool_pda)
+            .await,
+        withdrawal_pool_before + total_pending
+    );
+    for queued_deposit in &queued_deposits {
+        let deposit_account = banks_client
+            .get_account(*queued_deposit)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            UserDeposit::unpack(&deposit_account.data).unwrap().state,
+            UserDepositState::WithdrawReady
+        );
+    }
+    assert_eq!(
+        test_helper1.read_withdrawal_queue(&mut banks_client).await,
+        WithdrawalQueue::default()
+    );
+    log::info!("✓ Withdrawal queue prepared in one batch");
+
+    // Negative Test 21: A transferred position is redeemed by the receipt holder
+    log::info!("\n🔍 TEST CASE: Redeeming a transferred position");
+    // The buyer redeems the collateral, which burns the receipt, and closes the position
+    let buyer_collateral_before = test_helper3
+        .get_token_balance(&mut banks_client, &test_helper3.user_collateral_ata)
+        .await;
+    test_helper3
+        .withdraw_collateral_of(&mut banks_client, user2.pubkey(), sold_index)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper3
+            .get_token_balance(&mut banks_client, &test_helper3.user_collateral_ata)
+            .await,
+        buyer_collateral_before + valid_amount
+    );
+    assert_eq!(
+        test_helper3
+            .get_token_balance(
+                &mut banks_client,
+                &test_helper3.receipt_account(sold_account)
+            )
+            .await,
+        0
+    );
+    assert!(test_helper2
+        .close_deposit(&mut banks_client, sold_index)
+        .await
+        .is_err());
+    test_helper3
+        .close_deposit_of(&mut banks_client, user2.pubkey(), sold_index)
+        .await
+        .unwrap();
+    assert!(banks_client
+        .get_account(sold_account)
+        .await
+        .unwrap()
+        .is_none());
+    log::info!("✓ Transferred position redeemed by the receipt holder");
+
+    log::info!("\n=============================================");
+    log::info!("ALL NEGATIVE TESTS COMPLETED SUCCESSFULLY");
+    log::info!("=============================================");
+}
+
+#[tokio::test]
+async fn test_multisig() {
+    let _ = env_logger::try_init();
+    log::info!("Starting multisig test cases");
+
+    let program_id = astrape::id();
+    let mut program_test = ProgramTest::new(
+        "astrape",
+        program_id,
+        processor!(astrape::entrypoint::process_instruction),
+    );
+
+    let admin = Keypair::new();
+    let signer2 = Keypair::new();
+    let signer3 = Keypair::new();
+    let user = Keypair::new();
+    program_test.add_account(
+        admin.pubkey(),
+        Account {
+            lamports: LAMPORTS_PER_SOL * 1000,
+            ..Account::default()
+        },
+    );
+    for account in [&signer2, &signer3, &user] {
+        program_test.add_account(
+            account.pubkey(),
+            Account {
+                lamports: LAMPORTS_PER_SOL * 100,
+                ..Account::default()
+            },
+        );
+    }
+
+    TestSetup::add_price_feed(&mut program_test, MOCK_PRICE, MOCK_PRICE_EXPONENT);
+    TestSetup::add_program_data(&mut program_test, &admin.pubkey());
+
+    let (mut banks_client, _payer, _recent_blockhash) = program_test.start().await;
+
+    let collateral_mint = Keypair::new();
+    let interest_mint = Keypair::new();
+
+    TestSetup::setup(
+        &mut banks_client,
+        &admin,
+        &interest_mint,
+        &collateral_mint,
+        &[&user],
+    )
+    .await;
+
+    let test_helper = TestHelper::new(&admin, &user, &collateral_mint, &interest_mint).await;
+    test_helper
+        .initialize_program(&mut banks_client)
+        .await
+        .unwrap();
+    test_helper
+        .admin_deposit_interest(&mut banks_client, 1_000_000_000_000)
+        .await
+        .unwrap();
+
+    let deposit_amount = 20_000_000;
+    test_helper
+        .deposit_collateral(&mut banks_client, deposit_amount, SLOTS_PER_MONTH, 200)
+        .await
+        .unwrap();
+
+    // Only the admin can set up the multisig
+    log::info!("\n🔍 TEST CASE: Initialize 2-of-3 multisig");
+    let signers = vec![admin.pubkey(), signer2.pubkey(), signer3.pubkey()];
+    let user_helper = TestHelper::new(&user, &user, &collateral_mint, &interest_mint).await;
+    assert!(user_helper
+        .admin_initialize_multisig(&mut banks_client, signers.clone(), 2)
+        .await
+        .is_err());
+    // Threshold cannot exceed the number of signers
+    assert!(test_helper
+        .admin_initialize_multisig(&mut banks_client, signers.clone(), 4)
+        .await
+        .is_err());
+
+    test_helper
+        .admin_initialize_multisig(&mut banks_client, signers.clone(), 2)
+        .await
+        .unwrap();
+    let multisig = test_helper.read_multisig(&mut banks_client).await;
+    assert_eq!(multisig.signers, signers);
+    assert_eq!(multisig.threshold, 2);
+    assert!(
+        test_helper
+            .read_config(&mut banks_client)
+            .await
+            .unwrap()
+            .multisig_enabled
+    );
+    log::info!("✓ Multisig initialized");
+
+    // The admin alone can no longer move funds or change the config
+    log::info!("\n🔍 TEST CASE: Single admin actions are rejected");
+    assert!(test_helper
+        .admin_withdraw_collateral_for_investment(&mut banks_client, deposit_amount, 1)
+        .await
+        .is_err());
+    assert!(test_helper
+        .admin_update_config(&mut banks_client)
+        .await
+        .is_err());
+    assert!(test_helper
+        .admin_withdraw_treasury(&mut banks_client, 1)
+        .await
+        .is_err());
+    log::info!("✓ Single admin actions rejected");
+
+    // Withdraw collateral for investment through a proposal
+    log::info!("\n🔍 TEST CASE: Withdraw collateral through the multisig");
+    let withdraw_action = MultisigAction::WithdrawCollateralForInvestment {
+        amount: deposit_amount,
+        strategy: 1,
+        destination: test_helper.admin_collateral_ata,
+    };
+
+    // Non-signers cannot propose
+    assert!(test_helper
+        .create_proposal(&mut banks_client, &user, withdraw_action.clone(),)
+        .await
+        .is_err());
+
+    let proposal_index = test_helper
+        .create_proposal(&mut banks_client, &admin, withdraw_action.clone())
+        .await
+        .unwrap();
+    let proposal = test_helper
+        .read_proposal(&mut banks_client, proposal_index)
+        .await;
+    assert_eq!(proposal.approvals, vec![admin.pubkey()]);
+    assert!(!proposal.executed);
+
+    // One approval is below the threshold
+    assert!(test_helper
+        .execute_proposal(&mut banks_client, &admin, proposal_index, &withdraw_action,)
+        .await
+        .is_err());
+
+    // Approvals must come from distinct signers
+    assert!(test_helper
+        .approve_proposal(&mut banks_client, &admin, proposal_index)
+        .await
+        .is_err());
+    assert!(test_helper
+        .approve_proposal(&mut banks_client, &user, proposal_index)
+        .await
+        .is_err());
+    test_helper
+        .approve_proposal(&mut banks_client, &signer2, proposal_index)
+        .await
+        .unwrap();
+
+    test_helper
+        .execute_proposal(
+            &mut banks_client,
+            &signer3,
+            proposal_index,
+            &withdraw_action,
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper
+            .get_token_balance(&mut banks_client, &test_helper.admin_collateral_ata)
+            .await,
+        deposit_amount
+    );
+    assert_eq!(
+        test_helper
+            .get_token_balance(&mut banks_client, &test_helper.collateral_pool_ata)
+            .await,
+        0
+    );
+    assert!(
+        test_helper
+            .read_proposal(&mut banks_client, proposal_index)
+            .await
+            .executed
+    );
+
+    // A proposal can only be executed once
+    assert!(test_helper
+        .execute_proposal(
+            &mut banks_client,
+            &signer2,
+            proposal_index,
+            &withdraw_action,
+        )
+        .await
+        .is_err());
+    log::info!("✓ Collateral withdrawn through the multisig");
+
+    // Update config through a proposal
+    log::info!("\n🔍 TEST CASE: Update config through the multisig");
+    let proposal_index = test_helper
+        .create_proposal(
+            &mut banks_client,
+            &signer2,
+            MultisigAction::UpdateConfig(Box::new(ConfigUpdate {
+                param: 0,
+                deposit_period: Some(tenor(SLOTS_PER_MONTH, 70)),
+                pyth_price_max_age: None,
+                min_commission_rate: None,
+                max_commission_rate: None,
+                min_deposit_amount: None,
+                max_deposit_amount: None,
+                deposit_periods: None,
+                pyth_feed_id: None,
+                early_exit_policy: None,
+                pool_capacity: None,
+                withdrawal_sla: None,
+                late_payment_rate: None,
+            })),
+        )
+        .await
+        .unwrap();
+    assert_eq!(proposal_index, 1);
+    test_helper
+        .approve_proposal(&mut banks_client, &signer3, proposal_index)
+        .await
+        .unwrap();
+    test_helper
+        .execute_proposal(
+            &mut banks_client,
+            &admin,
+            proposal_index,
+            &MultisigAction::UpdateConfig(Box::new(ConfigUpdate {
+                param: 0,
+                deposit_period: Some(tenor(SLOTS_PER_MONTH, 70)),
+                pyth_price_max_age: None,
+                min_commission_rate: None,
+                max_commission_rate: None,
+                min_deposit_amount: None,
+                max_deposit_amount: None,
+                deposit_periods: None,
+                pyth_feed_id: None,
+                early_exit_policy: None,
+                pool_capacity: None,
+                withdrawal_sla: None,
+                late_payment_rate: None,
+            })),
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper
+            .read_config(&mut banks_client)
+            .await
+            .unwrap()
+            .deposit_period(SLOTS_PER_MONTH)
+            .map(|tenor| tenor.interest_rate),
+        Some(70)
+    );
+    log::info!("✓ Config updated through the multisig");
+
+    // Withdraw the collected commission through a proposal
+    log::info!("\n🔍 TEST CASE: Withdraw treasury through the multisig");
+    let commission = test_helper
+        .get_token_balance(&mut banks_client, &test_helper.treasury_pda)
+        .await;
+    assert!(commission > 0);
+    let admin_interest_before = test_helper
+        .get_token_balance(&mut banks_client, &test_helper.admin_interest_ata)
+        .await;
+    let proposal_index = test_helper
+        .create_proposal(
+            &mut banks_client,
+            &signer3,
+            MultisigAction::WithdrawTreasury {
+                amount: commission,
+                destination: test_helper.admin_interest_ata,
+            },
+        )
+        .await
+        .unwrap();
+    test_helper
+        .approve_proposal(&mut banks_client, &admin, proposal_index)
+        .await
+        .unwrap();
+    test_helper
+        .execute_proposal(
+            &mut banks_client,
+            &signer2,
+            proposal_index,
+            &MultisigAction::WithdrawTreasury {
+                amount: commission,
+                destination: test_helper.admin_interest_ata,
+            },
+        )
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper
+            .get_token_balance(&mut banks_client, &test_helper.admin_interest_ata)
+            .await,
+        admin_interest_before + commission
+    );
+    assert_eq!(
+        test_helper
+            .get_token_balance(&mut banks_client, &test_helper.treasury_pda)
+            .await,
+        0
+    );
+    log::info!("✓ Treasury withdrawn through the multisig");
+
+    // Returning collateral goes through the multisig as well
+    log::info!("\n🔍 TEST CASE: Return invested collateral");
+    let half = deposit_amount / 2;
+    let difference = 1_000;
+    assert!(test_helper
+        .admin_return_collateral(&mut banks_client, 1, half, half)
+        .await
+        .is_err());
+
+    // Only collateral deployed in a known strategy can be settled
+    for (strategy, principal, amount) in [(2, half, half), (1, deposit_amount + 1, half), (1, 0, 0)]
+    {
+        assert!(test_helper
+            .return_collateral_through_multisig(
+                &mut banks_client,
+                &signer2,
+                strategy,
+                principal,
+                amount
+            )
+            .await
+            .is_err());
+    }
+
+    // First half comes back at a loss, second half with a yield
+    test_helper
+        .return_collateral_through_multisig(&mut banks_client, &signer2, 1, half, half - difference)
+        .await
+        .unwrap();
+    let investment_ledger = test_helper.read_investment_ledger(&mut banks_client).await;
+    assert_eq!(investment_ledger.deployed(1), deposit_amount - half);
+    assert_eq!(investment_ledger.total_deployed, deposit_amount - half);
+    assert_eq!(investment_ledger.realized_loss, difference);
+    assert_eq!(investment_ledger.realized_yield, 0);
+
+    test_helper
+        .return_collateral_through_multisig(
+            &mut banks_client,
+            &signer3,
+            1,
+            deposit_amount - half,
+            deposit_amount - half + difference,
+        )
+        .await
+        .unwrap();
+    let investment_ledger = test_helper.read_investment_ledger(&mut banks_client).await;
+    assert_eq!(investment_ledger.total_deployed, 0);
+    assert!(investment_ledger.strategies.is_empty());
+    assert_eq!(investment_ledger.realized_yield, difference);
+    assert_eq!(investment_ledger.realized_loss, difference);
+    assert_eq!(investment_ledger.entry_count, 3);
+    let last_entry = investment_ledger.entries.last().unwrap();
+    assert_eq!(last_entry.amount, deposit_amount - half);
+    assert_eq!(
+        last_entry.returned,
+        Some(deposit_amount - half + difference)
+    );
+    assert_eq!(
+        test_helper
+            .get_token_balance(&mut banks_client, &test_helper.collateral_pool_ata)
+            .await,
+        deposit_amount
+    );
+    assert_eq!(
+        test_helper
+            .get_token_balance(&mut banks_client, &test_helper.admin_collateral_ata)
+            .await,
+        0
+    );
+    log::info!("✓ Invested collateral returned to the pool");
+
+    log::info!("\n=============================================");
+    log::info!("ALL MULTISIG TESTS COMPLETED SUCCESSFULLY");
+    log::info!("=============================================");
+}
+
+#[tokio::test]
+async fn test_matured_positions() {
+    let _ = env_logger::try_init();
+    log::info!("Starting matured position test cases");
+
+    let program_id = astrape::id();
+    let mut program_test = ProgramTest::new(
+        "astrape",
+        program_id,
+        processor!(astrape::entrypoint::process_instruction),
+    );
+
+    let admin = Keypair::new();
+    let user = Keypair::new();
+    program_test.add_account(
+        admin.pubkey(),
+        Account {
+            lamports: LAMPORTS_PER_SOL * 1000,
+            ..Account::default()
+        },
+    );
+    program_test.add_account(
+        user.pubkey(),
+        Account {
+            lamports: LAMPORTS_PER_SOL * 100,
+            ..Account::default()
+        },
+    );
+
+    TestSetup::add_price_feed(&mut program_test, MOCK_PRICE, MOCK_PRICE_EXPONENT);
+    TestSetup::add_program_data(&mut program_test, &admin.pubkey());
+
+    let mut context = program_test.start_with_context().await;
+    let mut banks_client = context.banks_client.clone();
+
+    let collateral_mint = Keypair::new();
+    let interest_mint = Keypair::new();
+
+    TestSetup::setup(
+        &mut banks_client,
+        &admin,
+        &interest_mint,
+        &collateral_mint,
+        &[&user],
+    )
+    .await;
+
+    let test_helper = TestHelper::new(&admin, &user, &collateral_mint, &interest_mint).await;
+    test_helper
+        .initialize_program(&mut banks_client)
+        .await
+        .unwrap();
+    test_helper
+        .admin_deposit_interest(&mut banks_client, 1_000_000_000_000)
+        .await
+        .unwrap();
+
+    let capacity_update = |pool_capacity| ConfigUpdate {
+        param: 9,
+        deposit_period: None,
+        pyth_price_max_age: None,
+        min_commission_rate: None,
+        max_commission_rate: None,
+        min_deposit_amount: None,
+        max_deposit_amount: None,
+        deposit_periods: None,
+        pyth_feed_id: None,
+        early_exit_policy: None,
+        pool_capacity: Some(pool_capacity),
+        withdrawal_sla: None,
+        late_payment_rate: None,
+    };
+
+    // A rollover locks the collateral for a new term, so it is checked against the capacity
+    // like a deposit, after freeing the collateral of its previous term
+    log::info!("\n🔍 TEST CASE: Rollover in a full pool");
+    let deposit_amount = 20_000_000;
+    test_helper
+        .deposit_collateral_with_rollover(
+            &mut banks_client,
+            deposit_amount,
+            SLOTS_PER_MONTH,
+            200,
+            true,
+        )
+        .await
+        .unwrap();
+    let position = test_helper
+        .get_user_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+    TestSetup::warp_to_slot(&mut context, position.unlock_slot + 1).await;
+
+    test_helper
+        .admin_update_config_with(&mut banks_client, capacity_update(Some(deposit_amount)))
+        .await
+        .unwrap();
+    assert!(test_helper
+        .deposit_collateral(&mut banks_client, deposit_amount, SLOTS_PER_MONTH, 200)
+        .await
+        .is_err());
+    log::info!("✓ Deposit into the full pool failed");
+
+    test_helper.rollover(&mut banks_client, 0).await.unwrap();
+    let rolled = test_helper
+        .get_user_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+    assert!(rolled.deposit_slot > position.unlock_slot);
+    assert_eq!(rolled.unlock_slot, rolled.deposit_slot + SLOTS_PER_MONTH);
+    let pool_state = test_helper.read_pool_state(&mut banks_client).await;
+    assert_eq!(pool_state.total_locked, deposit_amount);
+    log::info!("✓ Rollover within the pool capacity succeeded");
+
+    // A matured position joins the withdrawal queue like an early exit
+    log::info!("\n🔍 TEST CASE: Withdrawal request at maturity");
+    assert!(test_helper
+        .request_withdrawal(&mut banks_client, 0)
+        .await
+        .is_err());
+    TestSetup::warp_to_slot(&mut context, rolled.unlock_slot + 1).await;
+    test_helper
+        .request_withdrawal(&mut banks_client, 0)
+        .await
+        .unwrap();
+    let withdrawal_queue = test_helper.read_withdrawal_queue(&mut banks_client).await;
+    assert_eq!(withdrawal_queue.length, 1);
+    assert_eq!(
+        withdrawal_queue.head,
+        Some(test_helper.user_deposit_account(0))
+    );
+    assert_eq!(withdrawal_queue.tail, withdrawal_queue.head);
+    assert_eq!(withdrawal_queue.total_pending, deposit_amount);
+    let pool_state = test_helper.read_pool_state(&mut banks_client).await;
+    assert_eq!(pool_state.withdrawal_obligations, deposit_amount);
+    log::info!("✓ Matured position queued for withdrawal");
+}
+
+#[tokio::test]
+async fn test_withdrawal_sla() {
+    let _ = env_logger::try_init();
+    log::info!("Starting withdrawal SLA test cases");
+
+    let program_id = astrape::id();
+    let mut program_test = ProgramTest::new(
+        "astrape",
+        program_id,
+        processor!(astrape::entrypoint::process_instruction),
+    );
+
+    let admin = Keypair::new();
+    let user = Keypair::new();
+    program_test.add_account(
+        admin.pubkey(),
+        Account {
+            lamports: LAMPORTS_PER_SOL * 1000,
+            ..Account::default()
+        },
+    );
+    program_test.add_account(
+        user.pubkey(),
+        Account {
+            lamports: LAMPORTS_PER_SOL * 100,
+            ..Account::default()
+        },
+    );
+
+    TestSetup::add_price_feed(&mut program_test, MOCK_PRICE, MOCK_PRICE_EXPONENT);
+    TestSetup::add_program_data(&mut program_test, &admin.pubkey());
+
+    let mut context = program_test.start_with_context().await;
+    let mut banks_client = context.banks_client.clone();
+
+    let collateral_mint = Keypair::new();
+    let interest_mint = Keypair::new();
+
+    TestSetup::setup(
+        &mut banks_client,
+        &admin,
+        &interest_mint,
+        &collateral_mint,
+        &[&user],
+    )
+    .await;
+
+    let test_helper = TestHelper::new(&admin, &user, &collateral_mint, &interest_mint).await;
+    test_helper
+        .initialize_program(&mut banks_client)
+        .await
+        .unwrap();
+    test_helper
+        .admin_deposit_interest(&mut banks_client, 1_000_000_000_000)
+        .await
+        .unwrap();
+
+    let sla_update = |param, withdrawal_sla, late_payment_rate| ConfigUpdate {
+        param,
+        deposit_period: None,
+        pyth_price_max_age: None,
+        min_commission_rate: None,
+        max_commission_rate: None,
+        min_deposit_amount: None,
+        max_deposit_amount: None,
+        deposit_periods: None,
+        pyth_feed_id: None,
+        early_exit_policy: None,
+        pool_capacity: None,
+        withdrawal_sla,
+        late_payment_rate,
+    };
+
+    let deposit_amount = 20_000_000;
+    let (unpaid_index, late_index) = (0, 1);
+    for _ in 0..2 {
+        test_helper
+            .deposit_collateral(&mut banks_client, deposit_amount, SLOTS_PER_MONTH, 200)
+            .await
+            .unwrap();
+    }
+    test_helper
+        .request_withdrawal_early(&mut banks_client, unpaid_index)
+        .await
+        .unwrap();
+    test_helper
+        .request_withdrawal_early(&mut banks_client, late_index)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.read_queued_deposits(&mut banks_client).await,
+        vec![
+            test_helper.user_deposit_account(unpaid_index),
+            test_helper.user_deposit_account(late_index)
+        ]
+    );
+
+    // Without a withdrawal SLA the admin has no deadline
+    log::info!("\n🔍 TEST CASE: Withdrawal SLA configuration");
+    assert!(test_helper
+        .withdraw_overdue_collateral(&mut banks_client, late_index)
+        .await
+        .is_err());
+
+    // A deadline of zero slots and a rate above 100% are rejected
+    assert!(test_helper
+        .admin_update_config_with(&mut banks_client, sla_update(10, Some(Some(0)), None))
+        .await
+        .is_err());
+    assert!(test_helper
+        .admin_update_config_with(
+            &mut banks_client,
+            sla_update(11, None, Some(RATE_DENOMINATOR + 1))
+        )
+        .await
+        .is_err());
+    let (withdrawal_sla, late_payment_rate) = (1_000, 500);
+    test_helper
+        .admin_update_config_with(
+            &mut banks_client,
+            sla_update(10, Some(Some(withdrawal_sla)), None),
+        )
+        .await
+        .unwrap();
+    test_helper
+        .admin_update_config_with(
+            &mut banks_client,
+            sla_update(11, None, Some(late_payment_rate)),
+        )
+        .await
+        .unwrap();
+    let config = test_helper.read_config(&mut banks_client).await.unwrap();
+    assert_eq!(config.withdrawal_sla, Some(withdrawal_sla));
+    assert_eq!(config.late_payment_rate, late_payment_rate);
+    log::info!("✓ Withdrawal SLA configured");
+
+    // Before the deadline the admin still has time to prepare the withdrawal
+    log::info!("\n🔍 TEST CASE: Overdue withdrawal from the collateral pool");
+    assert!(test_helper
+        .withdraw_overdue_collateral(&mut banks_client, late_index)
+        .await
+        .is_err());
+    let late_node = test_helper
+        .get_user_deposit(&mut banks_client, late_index)
+        .await
+        .unwrap()
+        .queued
+        .unwrap();
+    TestSetup::warp_to_slot(&mut context, late_node.slot + withdrawal_sla).await;
+
+    // While the collateral pool can pay, the user withdraws from it directly
+    assert!(test_helper
+        .claim_late_payment(&mut banks_client, late_index)
+        .await
+        .is_err());
+    let user_collateral_before = test_helper
+        .get_token_balance(&mut banks_client, &test_helper.user_collateral_ata)
+        .await;
+    test_helper.take_events();
+    test_helper
+        .withdraw_overdue_collateral(&mut banks_client, late_index)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::WithdrawalCompleted(
+            WithdrawalCompletedEvent {
+                deposit: test_helper.user_deposit_account(late_index),
+                recipient: test_helper.user.pubkey(),
+                amount: deposit_amount,
+                overdue: true,
+            }
+        )]
+    );
+    let late_deposit = test_helper
+        .get_user_deposit(&mut banks_client, late_index)
+        .await
+        .unwrap();
+    assert_eq!(late_deposit.state, UserDepositState::WithdrawCompleted);
+    assert_eq!(
+        test_helper
+            .get_token_balance(&mut banks_client, &test_helper.user_collateral_ata)
+            .await,
+        user_collateral_before + deposit_amount
+    );
+    // The receipt is burned, the position is redeemed
+    assert_eq!(
+        test_helper
+            .get_token_balance(
+                &mut banks_client,
+                &test_helper.receipt_account(test_helper.user_deposit_account(late_index))
+            )
+            .await,
+        0
+    );
+
+    // The overdue position leaves the queue behind the older one, which is linked past it
+    assert_eq!(
+        test_helper.read_queued_deposits(&mut banks_client).await,
+        vec![test_helper.user_deposit_account(unpaid_index)]
+    );
+    let withdrawal_queue = test_helper.read_withdrawal_queue(&mut banks_client).await;
+    assert_eq!(withdrawal_queue.length, 1);
+    assert_eq!(withdrawal_queue.total_pending, deposit_amount);
+    assert_eq!(
+        withdrawal_queue.tail,
+        Some(test_helper.user_deposit_account(unpaid_index))
+    );
+    log::info!("✓ Overdue collateral withdrawn from the collateral pool");
+
+    // Without liquidity the user is compensated in interest tokens instead
+    log::info!("\n🔍 TEST CASE: Late payment compensation");
+    let pool_balance = test_helper
+        .get_token_balance(&mut banks_client, &test_helper.collateral_pool_ata)
+        .await;
+    test_helper
+        .admin_withdraw_collateral_for_investment(&mut banks_client, pool_balance, 2)
+        .await
+        .unwrap();
+    assert!(test_helper
+        .withdraw_overdue_collateral(&mut banks_client, unpaid_index)
+        .await
+        .is_err());
+    let unpaid_deposit = test_helper
+        .get_user_deposit(&mut banks_client, unpaid_index)
+        .await
+        .unwrap();
+    let user_interest_before = test_helper
+        .get_token_balance(&mut banks_client, &test_helper.user_interest_ata)
+        .await;
+    test_helper.take_events();
+    test_helper
+        .claim_late_payment(&mut banks_client, unpaid_index)
+        .await
+        .unwrap();
+    let compensation = unpaid_deposit.interest_received * late_payment_rate / RATE_DENOMINATOR;
+    assert_eq!(
+        test_helper
+            .get_token_balance(&mut banks_client, &test_helper.user_interest_ata)
+            .await,
+        user_interest_before + compensation
+    );
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::LatePayment(LatePaymentEvent {
+            deposit: test_helper.user_deposit_account(unpaid_index),
+            recipient: test_helper.user.pubkey(),
+            compensation,
+        })]
+    );
+    let compensated = test_helper
+        .get_user_deposit(&mut banks_client, unpaid_index)
+        .await
+        .unwrap();
+    assert_eq!(compensated.state, UserDepositState::WithdrawRequested);
+    assert!(compensated.queued.unwrap().slot > unpaid_deposit.queued.unwrap().slot);
+    assert_eq!(
+        test_helper.read_withdrawal_queue(&mut banks_client).await,
+        withdrawal_queue
+    );
+
+    // The deadline restarted, so the next compensation waits for the next missed deadline
+    assert!(test_helper
+        .claim_late_payment(&mut banks_client, unpaid_index)
+        .await
+        .is_err());
+    log::info!("✓ Late payment compensated and the deadline restarted");
+}
+
+#[tokio::test]
+async fn test_events() {
+    let _ = env_logger::try_init();
+    log::info!("Starting event test cases");
+
+    let program_id = astrape::id();
+    let mut program_test = ProgramTest::new(
+        "astrape",
+        program_id,
+        processor!(astrape::entrypoint::process_instruction),
+    );
+
+    let admin = Keypair::new();
+    let user = Keypair::new();
+    let new_admin = Keypair::new();
+    for account in [&admin, &user, &new_admin] {
+        program_test.add_account(
+            account.pubkey(),
+            Account {
+                lamports: LAMPORTS_PER_SOL * 100,
+                ..Account::default()
+            },
+        );
+    }
+
+    TestSetup::add_price_feed(&mut program_test, MOCK_PRICE, MOCK_PRICE_EXPONENT);
+    TestSetup::add_program_data(&mut program_test, &admin.pubkey());
+
+    let (mut banks_client, _payer, _recent_blockhash) = program_test.start().await;
+
+    let collateral_mint = Keypair::new();
+    let interest_mint = Keypair::new();
+
+    TestSetup::setup(
+        &mut banks_client,
+        &admin,
+        &interest_mint,
+        &collateral_mint,
+        &[&user],
+    )
+    .await;
+
+    let test_helper = TestHelper::new(&admin, &user, &collateral_mint, &interest_mint).await;
+    test_helper
+        .initialize_program(&mut banks_client)
+        .await
+        .unwrap();
+    assert!(test_helper.take_events().is_empty());
+    test_helper
+        .admin_deposit_interest(&mut banks_client, 1_000_000_000_000)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::AdminFunds(AdminFundsEvent {
+            movement: FundMovement::InterestDeposited,
+            amount: 1_000_000_000_000,
+        })]
+    );
+
+    let user_key = user.pubkey();
+    let deposit_amount = 20_000_000;
+
+    // Deposit and position changes
+    log::info!("\n🔍 TEST CASE: Position events");
+    for deposit_index in [0, 1] {
+        test_helper
+            .deposit_collateral(&mut banks_client, deposit_amount, SLOTS_PER_MONTH, 200)
+            .await
+            .unwrap();
+        assert!(matches!(
+            &test_helper.take_events()[..],
+            [AstrapeEvent::Deposit(event)]
+                if event.deposit == test_helper.user_deposit_account(deposit_index)
+                    && event.owner == user_key
+                    && event.amount == deposit_amount
+                    && event.deposit_period == SLOTS_PER_MONTH
+        ));
+    }
+    let deposit = test_helper.user_deposit_account(0);
+
+    test_helper
+        .set_rollover(&mut banks_client, 0, true)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::RolloverUpdated(RolloverUpdatedEvent {
+            deposit,
+            rollover: true,
+        })]
+    );
+
+    test_helper
+        .extend_deposit(&mut banks_client, 0, deposit_amount, SLOTS_PER_MONTH)
+        .await
+        .unwrap();
+    let extended = test_helper
+        .get_user_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+    assert!(matches!(
+        &test_helper.take_events()[..],
+        [AstrapeEvent::Relock(RelockEvent {
+            deposit: event_deposit,
+            amount,
+            unlock_slot,
+            rollover: false,
+            ..
+        })] if *event_deposit == deposit
+            && *amount == 2 * deposit_amount
+            && *unlock_slot == extended.unlock_slot
+    ));
+
+    let buyer = Pubkey::new_unique();
+    test_helper
+        .transfer_position(&mut banks_client, 1, buyer)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::PositionTransferred(
+            PositionTransferredEvent {
+                deposit: test_helper.user_deposit_account(1),
+                from: user_key,
+                to: buyer,
+            }
+        )]
+    );
+    log::info!("✓ Deposit, rollover, extension and transfer events emitted");
+
+    // The withdrawal flow of a position, up to closing its account
+    log::info!("\n🔍 TEST CASE: Withdrawal events");
+    let user_interest_before = test_helper
+        .get_token_balance(&mut banks_client, &test_helper.user_interest_ata)
+        .await;
+    test_helper
+        .request_partial_withdrawal_early(&mut banks_client, 0, deposit_amount)
+        .await
+        .unwrap();
+    let user_interest_after = test_helper
+        .get_token_balance(&mut banks_client, &test_helper.user_interest_ata)
+        .await;
+    // The partial withdrawal is paid out right away, so it never joins the queue
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::Refund(RefundEvent {
+            deposit,
+            amount: deposit_amount,
+            refund: user_interest_before - user_interest_after,
+        })]
+    );
+
+    test_helper
+        .request_withdrawal_early(&mut banks_client, 0)
+        .await
+        .unwrap();
+    let refund = user_interest_after
+        - test_helper
+            .get_token_balance(&mut banks_client, &test_helper.user_interest_ata)
+            .await;
+    assert_eq!(
+        test_helper.take_events(),
+        vec![
+            AstrapeEvent::WithdrawalRequested(WithdrawalRequestedEvent {
+                deposit,
+                amount: deposit_amount,
+                early: true,
+                total_pending: deposit_amount,
+            }),
+            AstrapeEvent::Refund(RefundEvent {
+                deposit,
+                amount: deposit_amount,
+                refund,
+            }),
+        ]
+    );
+
+    test_helper
+        .admin_withdraw_collateral_for_investment(&mut banks_client, 2 * deposit_amount, 3)
+        .await
+        .unwrap();
+    // Half of it comes back, the admin pays the withdrawal with the other half
+    test_helper
+        .admin_return_collateral(&mut banks_client, 3, deposit_amount, deposit_amount)
+        .await
+        .unwrap();
+    test_helper
+        .admin_prepare_withdrawal(&mut banks_client, user_key, 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![
+            AstrapeEvent::AdminFunds(AdminFundsEvent {
+                movement: FundMovement::CollateralInvested { strategy: 3 },
+                amount: 2 * deposit_amount,
+            }),
+            AstrapeEvent::AdminFunds(AdminFundsEvent {
+                movement: FundMovement::CollateralReturned {
+                    strategy: 3,
+                    principal: deposit_amount,
+                },
+                amount: deposit_amount,
+            }),
+            AstrapeEvent::WithdrawalPrepared(WithdrawalPreparedEvent {
+                deposit,
+                amount: deposit_amount,
+            }),
+        ]
+    );
+
+    test_helper
+        .withdraw_collateral(&mut banks_client, 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::WithdrawalCompleted(
+            WithdrawalCompletedEvent {
+                deposit,
+                recipient: user_key,
+                amount: deposit_amount,
+                overdue: false,
+            }
+        )]
+    );
+
+    let rent = banks_client
+        .get_account(deposit)
+        .await
+        .unwrap()
+        .unwrap()
+        .lamports;
+    test_helper
+        .close_deposit(&mut banks_client, 0)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::DepositClosed(DepositClosedEvent {
+            deposit,
+            recipient: user_key,
+            refund: rent,
+        })]
+    );
+    log::info!("✓ Withdrawal and close events emitted");
+
+    // Config updates carry the new value, and updates without a value change nothing
+    log::info!("\n🔍 TEST CASE: Admin events");
+    let late_payment_update = |late_payment_rate| ConfigUpdate {
+        param: 11,
+        deposit_period: None,
+        pyth_price_max_age: None,
+        min_commission_rate: None,
+        max_commission_rate: None,
+        min_deposit_amount: None,
+        max_deposit_amount: None,
+        deposit_periods: None,
+        pyth_feed_id: None,
+        early_exit_policy: None,
+        pool_capacity: None,
+        withdrawal_sla: None,
+        late_payment_rate,
+    };
+    test_helper
+        .admin_update_config_with(&mut banks_client, late_payment_update(Some(5)))
+        .await
+        .unwrap();
+    test_helper
+        .admin_update_config_with(&mut banks_client, late_payment_update(None))
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::ConfigUpdated(ConfigUpdatedEvent {
+            param: 11,
+            value: ConfigValue::LatePaymentRate(5),
+        })]
+    );
+
+    let paused = PauseFlags {
+        deposits: true,
+        ..PauseFlags::default()
+    };
+    test_helper
+        .admin_set_pause(&mut banks_client, paused)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::PauseUpdated(PauseUpdatedEvent { paused })]
+    );
+
+    let admin_key = admin.pubkey();
+    let signers = vec![admin_key, Pubkey::new_unique()];
+    test_helper
+        .admin_initialize_multisig(&mut banks_client, signers.clone(), 1)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::MultisigUpdated(MultisigUpdatedEvent {
+            signers,
+            threshold: 1,
+        })]
+    );
+
+    let signers = vec![admin_key, Pubkey::new_unique(), Pubkey::new_unique()];
+    let set_signers = MultisigAction::SetSigners {
+        signers: signers.clone(),
+        threshold: 2,
+    };
+    let proposal_index = test_helper
+        .create_proposal(&mut banks_client, &admin, set_signers.clone())
+        .await
+        .unwrap();
+    test_helper
+        .execute_proposal(&mut banks_client, &admin, proposal_index, &set_signers)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![AstrapeEvent::MultisigUpdated(MultisigUpdatedEvent {
+            signers,
+            threshold: 2,
+        })]
+    );
+
+    let new_admin_key = new_admin.pubkey();
+    test_helper
+        .propose_admin(&mut banks_client, new_admin_key)
+        .await
+        .unwrap();
+    test_helper
+        .accept_admin(&mut banks_client, &new_admin)
+        .await
+        .unwrap();
+    assert_eq!(
+        test_helper.take_events(),
+        vec![
+            AstrapeEvent::AdminProposed(AdminProposedEvent {
+                admin: admin_key,
+                pending_admin: new_admin_key,
+            }),
+            AstrapeEvent::AdminChanged(AdminChangedEvent {
+                admin: new_admin_key,
+            }),
+        ]
+    );
+    log::info!("✓ Config, pause, multisig and admin events emitted");
 }
@@ -0,0 +1,725 @@
+mod common;
+
+use {
+    astrape::{
+        errors::AstrapeError,
+        processor::{
+            Processor, BPS_DENOMINATOR, MS_PER_SLOT, MS_PER_YEAR, PRICE_DECIMALS, RATE_DENOMINATOR,
+        },
+        state::{EarlyExitPolicy, RefundStep, UserDeposit, UserDepositState},
+    },
+    common::{config_with, position, tenor, ONE_MONTH},
+    solana_program::pubkey::Pubkey,
+};
+
+const PRICE_SCALE: u64 = 10_u64.pow(PRICE_DECIMALS);
+
+// Interest at `rate` with both mints having the same decimals, so prices are in base units
+fn interest_at(
+    amount: u64,
+    price: u64,
+    rate: u64,
+    commission_rate: u64,
+    period: u64,
+) -> Result<u64, AstrapeError> {
+    Processor::calculate_interest_for_rate(
+        amount,
+        price,
+        rate,
+        commission_rate,
+        period,
+        &config_with(vec![], 8, 8),
+    )
+}
+
+// Exact rational value of the interest, rounding down the collateral value first
+fn reference_interest(
+    amount: u64,
+    price: u64,
+    interest_rate: u64,
+    commission_rate: u64,
+    deposit_period: u64,
+) -> u128 {
+    let collateral_value = amount as u128 * price as u128 / PRICE_SCALE as u128;
+    let numerator = collateral_value
+        * interest_rate as u128
+        * (RATE_DENOMINATOR - commission_rate) as u128
+        * deposit_period as u128
+        * MS_PER_SLOT as u128;
+    let denominator = RATE_DENOMINATOR as u128 * RATE_DENOMINATOR as u128 * MS_PER_YEAR as u128;
+    numerator / denominator
+}
+
+#[test]
+fn test_interest_known_values() {
+    // (amount, price, interest rate, commission rate, period, expected interest)
+    let cases = [
+        // 0.2 zBTC, 5%, 20% commission, 1 month
+        (
+            20_000_000,
+            1_000 * PRICE_SCALE,
+            50,
+            200,
+            ONE_MONTH,
+            65_753_423,
+        ),
+        // 1 zBTC, 5%, no commission, 12 months of 30 days
+        (
+            100_000_000,
+            1_000 * PRICE_SCALE,
+            50,
+            0,
+            12 * ONE_MONTH,
+            4_931_506_773,
+        ),
+        // 1 zBTC at a realistic price, 8%, 15% commission, 3 months
+        (
+            100_000_000,
+            6_500_000 * PRICE_SCALE,
+            80,
+            150,
+            3 * ONE_MONTH,
+            10_898_629_968_797,
+        ),
+        // 0.00001 zBTC, 5%, no commission, 1 month: 4109.58.. is rounded down
+        (1_000, 1_000 * PRICE_SCALE, 50, 0, ONE_MONTH, 4_109),
+        // 0.2 zBTC at a fractional price of 0.5: the collateral value is 10_000_000
+        (20_000_000, PRICE_SCALE / 2, 50, 0, 12 * ONE_MONTH, 493_150),
+        // The collateral value 0.99999999 is rounded down to 0 before applying the rate
+        (1, PRICE_SCALE - 1, RATE_DENOMINATOR, 0, 12 * ONE_MONTH, 0),
+    ];
+
+    for (amount, price, rate, commission_rate, period, expected) in cases {
+        assert_eq!(
+            interest_at(amount, price, rate, commission_rate, period),
+            Ok(expected),
+            "amount={amount}, price={price}, rate={rate}, commission={commission_rate}, period={period}"
+        );
+    }
+}
+
+#[test]
+fn test_interest_uses_tenor_rate() {
+    let config = config_with(
+        vec![
+            tenor(ONE_MONTH, 50, true),
+            tenor(3 * ONE_MONTH, 80, true),
+            tenor(6 * ONE_MONTH, 120, false),
+        ],
+        6,
+        8,
+    );
+    let price = 100_000 * PRICE_SCALE;
+
+    for (period, rate) in [
+        (ONE_MONTH, 50),
+        (3 * ONE_MONTH, 80),
+        // Disabled tenors take no new deposits but keep their rate
+        (6 * ONE_MONTH, 120),
+    ] {
+        assert_eq!(
+            Processor::calculate_interest_amount(20_000_000, price, 200, period, &config),
+            Processor::calculate_interest_for_rate(20_000_000, price, rate, 200, period, &config),
+            "period={period}"
+        );
+    }
+    assert_eq!(
+        Processor::calculate_interest_amount(20_000_000, price, 200, 3 * ONE_MONTH, &config),
+        Ok(315_616_433)
+    );
+
+    // Periods outside the tenor table have no rate
+    assert_eq!(
+        Processor::calculate_interest_amount(20_000_000, price, 200, 2 * ONE_MONTH, &config),
+        Err(AstrapeError::InvalidLockPeriod(2 * ONE_MONTH))
+    );
+}
+
+#[test]
+fn test_interest_mint_decimals() {
+    // (interest decimals, collateral decimals, amount, price, expected interest)
+    let cases = [
+        // 0.2 zBTC (8 decimals) at 100,000 USDC (6 decimals): 20,000 USDC at 5% for
+        // 1 month with 20% commission
+        (6, 8, 20_000_000, 100_000 * PRICE_SCALE, 65_753_423),
+        // The same position valued in a 9 decimals interest token
+        (9, 8, 20_000_000, 100_000 * PRICE_SCALE, 65_753_423_642),
+        // 20,000 units of a 6 decimals collateral at 1.0, paid in 9 decimals
+        (9, 6, 20_000_000_000, PRICE_SCALE, 65_753_423_642),
+        // 20,000 units of a 6 decimals collateral at 1.0, paid in 2 decimals
+        (2, 6, 20_000_000_000, PRICE_SCALE, 6_575),
+        // Value below one base unit of the interest mint is worth nothing
+        (0, 8, 99_999_999, PRICE_SCALE, 0),
+    ];
+
+    for (interest_decimals, collateral_decimals, amount, price, expected) in cases {
+        let config = config_with(
+            vec![tenor(ONE_MONTH, 50, true)],
+            interest_decimals,
+            collateral_decimals,
+        );
+        assert_eq!(
+            Processor::calculate_interest_amount(amount, price, 200, ONE_MONTH, &config),
+            Ok(expected),
+            "interest decimals={interest_decimals}, collateral decimals={collateral_decimals}"
+        );
+    }
+}
+
+#[test]
+fn test_interest_zero_factors() {
+    let price = 1_000 * PRICE_SCALE;
+    assert_eq!(interest_at(0, price, 50, 200, ONE_MONTH), Ok(0));
+    assert_eq!(interest_at(20_000_000, 0, 50, 200, ONE_MONTH), Ok(0));
+    assert_eq!(interest_at(20_000_000, price, 50, 200, 0), Ok(0));
+    assert_eq!(interest_at(20_000_000, price, 0, 200, ONE_MONTH), Ok(0));
+    // The whole interest goes to commission
+    assert_eq!(
+        interest_at(20_000_000, price, 50, RATE_DENOMINATOR, ONE_MONTH),
+        Ok(0)
+    );
+}
+
+#[test]
+fn test_interest_rounds_down() {
+    // 1 * 1 * 5% * 1 month is far below one unit
+    assert_eq!(interest_at(1, PRICE_SCALE, 50, 0, ONE_MONTH), Ok(0));
+
+    // 1_000 * 1_000 * 100% = 1_000_000 units per year, so one unit takes 31_536 ms.
+    // 72 slots earn just over one unit, 71 slots just under it
+    let price = 1_000 * PRICE_SCALE;
+    let slots_for_one_unit = 31_536 / MS_PER_SLOT + 1; // 72 slots = 31_680 ms
+    assert_eq!(
+        interest_at(1_000, price, RATE_DENOMINATOR, 0, slots_for_one_unit),
+        Ok(1)
+    );
+    assert_eq!(
+        interest_at(1_000, price, RATE_DENOMINATOR, 0, slots_for_one_unit - 1),
+        Ok(0)
+    );
+}
+
+#[test]
+fn test_interest_amount_times_price_above_u64() {
+    // amount * price = 10^20 does not fit in u64 but the interest does
+    assert_eq!(
+        interest_at(
+            1_000_000_000_000,
+            100_000_000 * PRICE_SCALE,
+            50,
+            0,
+            ONE_MONTH
+        ),
+        Ok(410_958_897_767_630_644)
+    );
+}
+
+#[test]
+fn test_interest_overflow() {
+    // The product of the rates and period does not fit in u128
+    assert_eq!(
+        interest_at(u64::MAX, u64::MAX, 50, 0, ONE_MONTH),
+        Err(AstrapeError::ArithmeticOverflow)
+    );
+
+    // The product fits in u128 but the interest does not fit in u64
+    assert_eq!(
+        interest_at(u64::MAX, PRICE_SCALE, RATE_DENOMINATOR, 0, 120 * ONE_MONTH),
+        Err(AstrapeError::ArithmeticOverflow)
+    );
+}
+
+#[test]
+fn test_interest_commission_above_denominator() {
+    assert_eq!(
+        interest_at(
+            20_000_000,
+            1_000 * PRICE_SCALE,
+            50,
+            RATE_DENOMINATOR + 1,
+            ONE_MONTH
+        ),
+        Err(AstrapeError::CommissionRateOutOfBounds(
+            RATE_DENOMINATOR + 1
+        ))
+    );
+}
+
+#[test]
+fn test_interest_matches_reference() {
+    let amounts = [1, 999, 1_000_000, 20_000_000, 123_456_789, 10_000_000_000];
+    let prices = [
+        1,
+        PRICE_SCALE * 7 / 10,
+        1_000 * PRICE_SCALE,
+        6_543_210 * PRICE_SCALE + 12_345_678,
+    ];
+    let rates = [0, 1, 50, 125, RATE_DENOMINATOR];
+    let commission_rates = [0, 1, 200, 999, RATE_DENOMINATOR];
+    let periods = [
+        0,
+        1,
+        71,
+        ONE_MONTH,
+        3 * ONE_MONTH,
+        6 * ONE_MONTH,
+        12 * ONE_MONTH,
+    ];
+
+    for amount in amounts {
+        for price in prices {
+            for rate in rates {
+                for commission_rate in commission_rates {
+                    for period in periods {
+                        let interest =
+                            interest_at(amount, price, rate, commission_rate, period).unwrap();
+                        assert_eq!(
+                            interest as u128,
+                            reference_interest(amount, price, rate, commission_rate, period),
+                            "amount={amount}, price={price}, rate={rate}, commission={commission_rate}, period={period}"
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_interest_is_monotonic() {
+    let price = 1_000 * PRICE_SCALE;
+    let mut previous = 0;
+    for period in (0..=12 * ONE_MONTH).step_by(ONE_MONTH as usize / 10) {
+        let interest = interest_at(20_000_000, price, 50, 200, period).unwrap();
+        assert!(interest >= previous, "period={period}");
+        previous = interest;
+    }
+
+    // A higher commission never pays more interest
+    let mut previous = u64::MAX;
+    for commission_rate in 0..=RATE_DENOMINATOR {
+        let interest = interest_at(20_000_000, price, 50, commission_rate, ONE_MONTH).unwrap();
+        assert!(interest <= previous, "commission={commission_rate}");
+        previous = interest;
+    }
+}
+
+#[test]
+fn test_commission_known_values() {
+    let config = config_with(
+        vec![tenor(ONE_MONTH, 50, true), tenor(3 * ONE_MONTH, 80, true)],
+        8,
+        8,
+    );
+
+    // (amount, price, commission rate, period, expected commission)
+    let cases = [
+        // 0.2 zBTC, 5%, 20% commission, 1 month: 82_191_779 of which the user gets 65_753_423
+        (20_000_000, 1_000 * PRICE_SCALE, 200, ONE_MONTH, 16_438_356),
+        // 1 zBTC at a realistic price, 8%, 15% commission, 3 months
+        (
+            100_000_000,
+            6_500_000 * PRICE_SCALE,
+            150,
+            3 * ONE_MONTH,
+            1_923_287_641_553,
+        ),
+        // No commission
+        (20_000_000, 1_000 * PRICE_SCALE, 0, ONE_MONTH, 0),
+        // The whole interest goes to commission
+        (
+            20_000_000,
+            1_000 * PRICE_SCALE,
+            RATE_DENOMINATOR,
+            ONE_MONTH,
+            82_191_779,
+        ),
+    ];
+
+    for (amount, price, commission_rate, period, expected) in cases {
+        assert_eq!(
+            Processor::calculate_commission_amount(amount, price, commission_rate, period, &config),
+            Ok(expected),
+            "amount={amount}, price={price}, commission={commission_rate}, period={period}"
+        );
+    }
+
+    assert_eq!(
+        Processor::calculate_commission_amount(
+            20_000_000,
+            1_000 * PRICE_SCALE,
+            200,
+            2 * ONE_MONTH,
+            &config
+        ),
+        Err(AstrapeError::InvalidLockPeriod(2 * ONE_MONTH))
+    );
+}
+
+#[test]
+fn test_commission_and_interest_add_up() {
+    let config = config_with(vec![tenor(ONE_MONTH, 50, true)], 6, 8);
+    for amount in [1, 999, 20_000_000, 123_456_789] {
+        for price in [1, PRICE_SCALE - 1, 100_000 * PRICE_SCALE + 12_345] {
+            let gross_interest =
+                Processor::calculate_interest_amount(amount, price, 0, ONE_MONTH, &config).unwrap();
+            for commission_rate in (0..=RATE_DENOMINATOR).step_by(37) {
+                let interest = Processor::calculate_interest_amount(
+                    amount,
+                    price,
+                    commission_rate,
+                    ONE_MONTH,
+                    &config,
+                )
+                .unwrap();
+                let commission = Processor::calculate_commission_amount(
+                    amount,
+                    price,
+                    commission_rate,
+                    ONE_MONTH,
+                    &config,
+                )
+                .unwrap();
+                assert_eq!(
+                    interest + commission,
+                    gross_interest,
+                    "amount={amount}, price={price}, commission={commission_rate}"
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn test_normalize_pyth_price() {
+    // (price, exponent, expected price with PRICE_DECIMALS decimal places)
+    let cases = [
+        // BTC/USD as published by Pyth
+        (10_000_000_000_000, -8, 100_000 * PRICE_SCALE),
+        (9_876_543_210_987, -8, 9_876_543_210_987),
+        (100_000, 0, 100_000 * PRICE_SCALE),
+        (5, 2, 500 * PRICE_SCALE),
+        // Extra precision is rounded down
+        (123_456_789, -10, 1_234_567),
+        (99, -10, 0),
+        (i64::MAX, -30, 0),
+    ];
+
+    for (price, exponent, expected) in cases {
+        assert_eq!(
+            Processor::normalize_pyth_price(price, exponent),
+            Ok(expected),
+            "price={price}, exponent={exponent}"
+        );
+    }
+}
+
+#[test]
+fn test_normalize_pyth_price_invalid() {
+    assert_eq!(
+        Processor::normalize_pyth_price(0, -8),
+        Err(AstrapeError::GetPriceError)
+    );
+    assert_eq!(
+        Processor::normalize_pyth_price(-10_000_000_000_000, -8),
+        Err(AstrapeError::GetPriceError)
+    );
+    assert_eq!(
+        Processor::normalize_pyth_price(i64::MAX, 0),
+        Err(AstrapeError::ArithmeticOverflow)
+    );
+    assert_eq!(
+        Processor::normalize_pyth_price(1, 12),
+        Err(AstrapeError::ArithmeticOverflow)
+    );
+}
+
+#[test]
+fn test_split_deposit() {
+    let depositor = Pubkey::new_unique();
+    let deposit = UserDeposit {
+        discriminator: UserDeposit::DISCRIMINATOR,
+        depositor,
+        deposit_index: 0,
+        owner: depositor,
+        amount: 30_000_000,
+        deposit_slot: 100,
+        unlock_slot: 100 + 3 * ONE_MONTH,
+        interest_received: 1_000_000_001,
+        state: UserDepositState::Deposited,
+        commission_rate: 200,
+        deposit_period: 3 * ONE_MONTH,
+        rollover: false,
+        queued: None,
+    };
+
+    let mut remaining = deposit.clone();
+    let split = Processor::split_deposit(&mut remaining, 10_000_000, 10_000_000).unwrap();
+    assert_eq!(split.amount, 10_000_000);
+    assert_eq!(split.interest_received, 333_333_333);
+    assert_eq!(remaining.amount, 20_000_000);
+    assert_eq!(remaining.interest_received, 666_666_668);
+    for position in [&split, &remaining] {
+        assert_eq!(position.deposit_slot, deposit.deposit_slot);
+        assert_eq!(position.unlock_slot, deposit.unlock_slot);
+        assert_eq!(position.commission_rate, deposit.commission_rate);
+        assert_eq!(position.owner, deposit.owner);
+    }
+
+    for amount in [0, 30_000_000, 30_000_001] {
+        let mut remaining = deposit.clone();
+        assert_eq!(
+            Processor::split_deposit(&mut remaining, amount, 10_000_000).unwrap_err(),
+            AstrapeError::InvalidInput
+        );
+        assert_eq!(remaining.amount, deposit.amount);
+        assert_eq!(remaining.interest_received, deposit.interest_received);
+    }
+
+    // Only the remaining position has to meet the minimum
+    let mut remaining = deposit.clone();
+    let split = Processor::split_deposit(&mut remaining, 1_000_000, 10_000_000).unwrap();
+    assert_eq!(split.amount, 1_000_000);
+    assert_eq!(remaining.amount, 29_000_000);
+
+    let mut remaining = deposit.clone();
+    assert_eq!(
+        Processor::split_deposit(&mut remaining, 25_000_000, 10_000_000).unwrap_err(),
+        AstrapeError::DepositAmountOutOfBounds(5_000_000)
+    );
+    assert_eq!(remaining.amount, deposit.amount);
+}
+
+#[test]
+fn test_late_payment() {
+    let deposit = position(0, ONE_MONTH, 1_000_001);
+    assert_eq!(Processor::calculate_late_payment(&deposit, 0), Ok(0));
+    // Rounded down in favor of the pool
+    assert_eq!(
+        Processor::calculate_late_payment(&deposit, 500),
+        Ok(500_000)
+    );
+    assert_eq!(
+        Processor::calculate_late_payment(&deposit, RATE_DENOMINATOR),
+        Ok(1_000_001)
+    );
+    assert_eq!(
+        Processor::calculate_late_payment(&position(0, ONE_MONTH, u64::MAX), u64::MAX),
+        Err(AstrapeError::ArithmeticOverflow)
+    );
+}
+
+fn refund_at(policy: &EarlyExitPolicy, slot: u64) -> u64 {
+    Processor::calculate_interest_to_return(&position(1_000, 10_000, 1_000_000), policy, slot)
+        .unwrap()
+}
+
+#[test]
+fn test_refund_linear_unearned() {
+    let policy = EarlyExitPolicy::LinearUnearned;
+    assert_eq!(refund_at(&policy, 1_000), 1_000_000);
+    assert_eq!(refund_at(&policy, 1_001), 999_900);
+    assert_eq!(refund_at(&policy, 3_500), 750_000);
+    assert_eq!(refund_at(&policy, 11_000), 0);
+    assert_eq!(refund_at(&policy, 20_000), 0);
+}
+
+#[test]
+fn test_refund_flat_penalty() {
+    let policy = EarlyExitPolicy::FlatPenalty { penalty_bps: 500 };
+    assert_eq!(refund_at(&policy, 3_500), 800_000);
+    assert_eq!(refund_at(&policy, 11_000), 50_000);
+    // The refund is capped at the interest received
+    assert_eq!(refund_at(&policy, 1_000), 1_000_000);
+
+    let no_penalty = EarlyExitPolicy::FlatPenalty { penalty_bps: 0 };
+    assert_eq!(
+        refund_at(&no_penalty, 3_500),
+        refund_at(&EarlyExitPolicy::LinearUnearned, 3_500)
+    );
+}
+
+#[test]
+fn test_refund_step_schedule() {
+    let policy = EarlyExitPolicy::StepSchedule(vec![
+        RefundStep {
+            until_bps: 2_500,
+            refund_bps: BPS_DENOMINATOR,
+        },
+        RefundStep {
+            until_bps: 5_000,
+            refund_bps: 6_000,
+        },
+        RefundStep {
+            until_bps: BPS_DENOMINATOR,
+            refund_bps: 2_000,
+        },
+    ]);
+    assert_eq!(refund_at(&policy, 1_000), 1_000_000);
+    assert_eq!(refund_at(&policy, 3_499), 1_000_000);
+    assert_eq!(refund_at(&policy, 3_500), 600_000);
+    assert_eq!(refund_at(&policy, 6_000), 200_000);
+    assert_eq!(refund_at(&policy, 10_999), 200_000);
+    assert_eq!(refund_at(&policy, 11_000), 0);
+}
+
+#[test]
+fn test_refund_invalid_lock() {
+    assert_eq!(
+        Processor::calculate_interest_to_return(
+            &position(1_000, 0, 1_000_000),
+            &EarlyExitPolicy::LinearUnearned,
+            1_000
+        ),
+        Err(AstrapeError::DivisionByZero)
+    );
+}
+
+fn extension_at(
+    deposit: &UserDeposit,
+    amount: u64,
+    unlock_slot: u64,
+    current_slot: u64,
+) -> Result<(u64, u64), AstrapeError> {
+    Processor::calculate_extension_interest(
+        deposit,
+        amount,
+        unlock_slot,
+        current_slot,
+        100_000 * PRICE_SCALE,
+        60,
+        &config_with(vec![], 8, 8),
+    )
+}
+
+#[test]
+fn test_extension_interest() {
+    let price = 100_000 * PRICE_SCALE;
+    let deposit = position(1_000, ONE_MONTH, 0);
+    let unlock_slot = deposit.unlock_slot;
+    let current_slot = 1_000 + ONE_MONTH / 2;
+
+    // A top-up earns from now until the current unlock slot
+    let (interest, _) = extension_at(&deposit, 10_000_000, unlock_slot, current_slot).unwrap();
+    assert_eq!(
+        interest,
+        interest_at(10_000_000, price, 60, 200, unlock_slot - current_slot).unwrap()
+    );
+
+    // A longer lock pays the existing collateral for the added slots only
+    let (interest, _) =
+        extension_at(&deposit, 0, current_slot + 3 * ONE_MONTH, current_slot).unwrap();
+    assert_eq!(
+        interest,
+        interest_at(
+            deposit.amount,
+            price,
+            60,
+            200,
+            current_slot + 3 * ONE_MONTH - unlock_slot
+        )
+        .unwrap()
+    );
+
+    // Both at once add up
+    let (interest, commission) = extension_at(
+        &deposit,
+        10_000_000,
+        current_slot + 3 * ONE_MONTH,
+        current_slot,
+    )
+    .unwrap();
+    assert_eq!(
+        interest,
+        interest_at(10_000_000, price, 60, 200, 3 * ONE_MONTH).unwrap()
+            + interest_at(
+                deposit.amount,
+                price,
+                60,
+                200,
+                current_slot + 3 * ONE_MONTH - unlock_slot
+            )
+            .unwrap()
+    );
+    let (gross_interest, no_commission) = extension_at(
+        &UserDeposit {
+            commission_rate: 0,
+            ..deposit.clone()
+        },
+        10_000_000,
+        current_slot + 3 * ONE_MONTH,
+        current_slot,
+    )
+    .unwrap();
+    assert_eq!(no_commission, 0);
+    assert_eq!(interest + commission, gross_interest);
+}
+
+#[test]
+fn test_extension_interest_after_unlock() {
+    let deposit = position(1_000, ONE_MONTH, 0);
+    let current_slot = deposit.unlock_slot + ONE_MONTH;
+
+    // Slots between the unlock and now earn nothing
+    let (interest, _) = extension_at(&deposit, 0, current_slot + ONE_MONTH, current_slot).unwrap();
+    assert_eq!(
+        interest,
+        interest_at(deposit.amount, 100_000 * PRICE_SCALE, 60, 200, ONE_MONTH).unwrap()
+    );
+
+    assert_eq!(
+        extension_at(&deposit, 0, current_slot - 1, current_slot),
+        Err(AstrapeError::InvalidLockPeriod(current_slot - 1))
+    );
+}
+
+#[test]
+fn test_extend_then_early_exit() {
+    // A minimum deposit close to maturity is topped up and left right away
+    let mut deposit = position(1_000, ONE_MONTH, 1_000_000);
+    deposit.amount = 10_000_000;
+    let current_slot = deposit.unlock_slot - ONE_MONTH / 10;
+    let unearned = Processor::calculate_interest_to_return(
+        &deposit,
+        &EarlyExitPolicy::LinearUnearned,
+        current_slot,
+    )
+    .unwrap();
+    // A tenth of the interest, rounded down
+    assert_eq!(unearned, 99_999);
+
+    let unlock_slot = current_slot + 2 * ONE_MONTH;
+    let (interest, _) = extension_at(&deposit, 90_000_000, unlock_slot, current_slot).unwrap();
+    Processor::extend_position(
+        &mut deposit,
+        90_000_000,
+        2 * ONE_MONTH,
+        unlock_slot,
+        interest,
+        current_slot,
+    )
+    .unwrap();
+    assert_eq!(deposit.amount, 100_000_000);
+    assert_eq!(deposit.deposit_slot, current_slot);
+    assert_eq!(deposit.unlock_slot, unlock_slot);
+    assert_eq!(deposit.interest_received, unearned + interest);
+
+    // The whole interest of the top-up is refunded, only the earned interest is kept
+    assert_eq!(
+        Processor::calculate_interest_to_return(
+            &deposit,
+            &EarlyExitPolicy::LinearUnearned,
+            current_slot
+        ),
+        Ok(unearned + interest)
+    );
+    // Halfway through the new lock half of it is refunded
+    assert_eq!(
+        Processor::calculate_interest_to_return(
+            &deposit,
+            &EarlyExitPolicy::LinearUnearned,
+            current_slot + 2 * ONE_MONTH / 2
+        ),
+        Ok((unearned + interest) / 2)
+    );
+}
@@ -0,0 +1,72 @@
+use astrape::{
+    errors::AstrapeError,
+    state::{InvestmentLedger, MAX_LEDGER_ENTRIES, MAX_STRATEGIES},
+};
+
+#[test]
+fn test_investment_ledger() {
+    let mut ledger = InvestmentLedger::default();
+    for slot in 0..MAX_LEDGER_ENTRIES as u64 + 2 {
+        ledger.record_withdrawal(slot % 2, 1_000, slot).unwrap();
+    }
+    assert_eq!(ledger.entry_count, MAX_LEDGER_ENTRIES as u64 + 2);
+    assert_eq!(
+        ledger.total_deployed,
+        (MAX_LEDGER_ENTRIES as u64 + 2) * 1_000
+    );
+    assert_eq!(ledger.deployed(0), ledger.deployed(1));
+    assert_eq!(ledger.deployed(2), 0);
+
+    // Only the latest entries are kept, oldest first
+    assert_eq!(ledger.entries.len(), MAX_LEDGER_ENTRIES);
+    assert_eq!(ledger.entries[0].slot, 2);
+    assert_eq!(
+        ledger.entries.last().unwrap().slot,
+        MAX_LEDGER_ENTRIES as u64 + 1
+    );
+
+    // The number of strategies with deployed collateral is bounded
+    for strategy in 2..MAX_STRATEGIES as u64 {
+        ledger.record_withdrawal(strategy, 1_000, 0).unwrap();
+    }
+    assert_eq!(
+        ledger.record_withdrawal(MAX_STRATEGIES as u64, 1_000, 0),
+        Err(AstrapeError::InvalidInput)
+    );
+}
+
+#[test]
+fn test_investment_ledger_return() {
+    let mut ledger = InvestmentLedger::default();
+    ledger.record_withdrawal(1, 10_000, 0).unwrap();
+    ledger.record_withdrawal(2, 5_000, 0).unwrap();
+
+    // Unknown strategies and more than the deployed principal cannot be settled
+    assert_eq!(
+        ledger.record_return(3, 1_000, 1_000, 1),
+        Err(AstrapeError::InvalidInput)
+    );
+    assert_eq!(
+        ledger.record_return(1, 10_001, 10_001, 1),
+        Err(AstrapeError::InsufficientBalance(10_000))
+    );
+
+    // Partial settlement with a yield
+    ledger.record_return(1, 4_000, 4_500, 1).unwrap();
+    assert_eq!(ledger.deployed(1), 6_000);
+    assert_eq!(ledger.total_deployed, 11_000);
+    assert_eq!(ledger.realized_yield, 500);
+    assert_eq!(ledger.realized_loss, 0);
+
+    // Full settlement with a loss frees the strategy
+    ledger.record_return(2, 5_000, 3_000, 2).unwrap();
+    assert_eq!(ledger.deployed(2), 0);
+    assert_eq!(ledger.strategies.len(), 1);
+    assert_eq!(ledger.total_deployed, 6_000);
+    assert_eq!(ledger.realized_loss, 2_000);
+
+    assert_eq!(ledger.entry_count, 4);
+    assert_eq!(ledger.entries[0].returned, None);
+    assert_eq!(ledger.entries[3].amount, 5_000);
+    assert_eq!(ledger.entries[3].returned, Some(3_000));
+}
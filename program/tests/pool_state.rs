@@ -0,0 +1,121 @@
+mod common;
+
+use {
+    astrape::{
+        errors::AstrapeError,
+        processor::Processor,
+        state::{DepositPeriod, PoolState, UserDepositState, MAX_POOL_TENORS},
+    },
+    common::{config_with, position, tenor, ONE_MONTH},
+};
+
+#[test]
+fn test_pool_state_totals() {
+    let mut pool_state = PoolState::default();
+    let mut one_month = position(1_000, ONE_MONTH, 100);
+    let three_months = position(1_000, 3 * ONE_MONTH, 300);
+    pool_state.add_position(&one_month).unwrap();
+    pool_state.add_position(&three_months).unwrap();
+    assert_eq!(pool_state.total_locked, 40_000_000);
+    assert_eq!(pool_state.active_deposits, 2);
+    assert_eq!(pool_state.by_tenor.len(), 2);
+    assert_eq!(
+        pool_state
+            .state_totals(UserDepositState::Deposited)
+            .interest_received,
+        400
+    );
+
+    // A state change moves the position between the totals
+    pool_state.remove_position(&one_month).unwrap();
+    one_month.state = UserDepositState::WithdrawRequested;
+    pool_state.add_position(&one_month).unwrap();
+    assert_eq!(pool_state.total_locked, 20_000_000);
+    assert_eq!(pool_state.withdrawal_obligations, 20_000_000);
+    assert_eq!(pool_state.active_deposits, 2);
+    assert_eq!(pool_state.tenor_totals(ONE_MONTH), None);
+    assert_eq!(
+        pool_state.tenor_totals(3 * ONE_MONTH).unwrap().amount,
+        20_000_000
+    );
+
+    // Completed positions are no longer active
+    pool_state.remove_position(&one_month).unwrap();
+    one_month.state = UserDepositState::WithdrawCompleted;
+    pool_state.add_position(&one_month).unwrap();
+    assert_eq!(pool_state.withdrawal_obligations, 0);
+    assert_eq!(pool_state.active_deposits, 1);
+    assert_eq!(
+        pool_state
+            .state_totals(UserDepositState::WithdrawCompleted)
+            .count,
+        1
+    );
+
+    // A position can't be removed twice
+    pool_state.remove_position(&three_months).unwrap();
+    assert_eq!(
+        pool_state.remove_position(&three_months),
+        Err(AstrapeError::ArithmeticOverflow)
+    );
+}
+
+#[test]
+fn test_pool_state_tenor_limit() {
+    let mut pool_state = PoolState::default();
+    for tenor in 1..=MAX_POOL_TENORS as u64 {
+        pool_state
+            .add_position(&position(1_000, tenor * ONE_MONTH, 0))
+            .unwrap();
+    }
+    let period = (MAX_POOL_TENORS as u64 + 1) * ONE_MONTH;
+    assert_eq!(
+        pool_state.add_position(&position(1_000, period, 0)),
+        Err(AstrapeError::InvalidLockPeriod(period))
+    );
+}
+
+#[test]
+fn test_remaining_capacity() {
+    let mut config = config_with(vec![tenor(ONE_MONTH, 50, true)], 6, 8);
+    let mut pool_state = PoolState::default();
+    pool_state
+        .add_position(&position(1_000, ONE_MONTH, 0))
+        .unwrap();
+    pool_state
+        .add_position(&position(1_000, 3 * ONE_MONTH, 0))
+        .unwrap();
+
+    let uncapped = tenor(ONE_MONTH, 50, true);
+    assert_eq!(
+        Processor::remaining_capacity(&config, &uncapped, &pool_state),
+        None
+    );
+
+    let capped = DepositPeriod {
+        capacity: Some(50_000_000),
+        ..uncapped
+    };
+    assert_eq!(
+        Processor::remaining_capacity(&config, &capped, &pool_state),
+        Some(30_000_000)
+    );
+
+    // The lower of the pool and the tenor capacity applies
+    config.pool_capacity = Some(60_000_000);
+    assert_eq!(
+        Processor::remaining_capacity(&config, &capped, &pool_state),
+        Some(20_000_000)
+    );
+    assert_eq!(
+        Processor::remaining_capacity(&config, &uncapped, &pool_state),
+        Some(20_000_000)
+    );
+
+    // A full pool leaves no room
+    config.pool_capacity = Some(40_000_000);
+    assert_eq!(
+        Processor::remaining_capacity(&config, &capped, &pool_state),
+        Some(0)
+    );
+}
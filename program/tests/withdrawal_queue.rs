@@ -0,0 +1,132 @@
+mod common;
+
+use {
+    astrape::{
+        errors::AstrapeError,
+        state::{QueuedWithdrawal, UserDeposit, WithdrawalQueue},
+    },
+    common::{position, ONE_MONTH},
+    solana_program::pubkey::Pubkey,
+};
+
+// Mutable borrows of two different positions
+fn pair(deposits: &mut [UserDeposit], a: usize, b: usize) -> (&mut UserDeposit, &mut UserDeposit) {
+    assert!(a < b);
+    let (left, right) = deposits.split_at_mut(b);
+    (&mut left[a], &mut right[0])
+}
+
+#[test]
+fn test_withdrawal_queue() {
+    let mut queue = WithdrawalQueue::default();
+    let keys: Vec<Pubkey> = (0..4).map(|_| Pubkey::new_unique()).collect();
+    let mut deposits: Vec<UserDeposit> = (0..4)
+        .map(|index| UserDeposit {
+            amount: 1_000 * (index + 1),
+            ..position(0, ONE_MONTH, 0)
+        })
+        .collect();
+    queue.push(&keys[0], &mut deposits[0], None, 0).unwrap();
+    for index in 1..4 {
+        let (tail, deposit) = pair(&mut deposits, index - 1, index);
+        queue
+            .push(
+                &keys[index],
+                deposit,
+                Some((&keys[index - 1], tail)),
+                index as u64,
+            )
+            .unwrap();
+    }
+    assert_eq!(queue.head, Some(keys[0]));
+    assert_eq!(queue.tail, Some(keys[3]));
+    assert_eq!(queue.length, 4);
+    assert_eq!(queue.total_pending, 10_000);
+    assert_eq!(deposits[0].queued.unwrap().next, Some(keys[1]));
+    assert_eq!(
+        deposits[3].queued,
+        Some(QueuedWithdrawal {
+            slot: 3,
+            next: None
+        })
+    );
+
+    // A position is queued once, behind the newest position
+    let (tail, deposit) = pair(&mut deposits, 1, 3);
+    assert_eq!(
+        queue.push(&keys[1], tail, Some((&keys[3], deposit)), 4),
+        Err(AstrapeError::WithdrawalQueueMismatch)
+    );
+    let mut other = position(0, ONE_MONTH, 0);
+    let other_key = Pubkey::new_unique();
+    assert_eq!(
+        queue.push(&other_key, &mut other, None, 4),
+        Err(AstrapeError::WithdrawalQueueMismatch)
+    );
+    assert_eq!(
+        queue.push(
+            &other_key,
+            &mut other,
+            Some((&keys[2], &mut deposits[2])),
+            4
+        ),
+        Err(AstrapeError::WithdrawalQueueMismatch)
+    );
+    assert_eq!(other.queued, None);
+    assert_eq!(queue.length, 4);
+
+    // Positions are prepared first in, first out
+    assert_eq!(
+        queue.pop(&keys[1], &mut deposits[1]),
+        Err(AstrapeError::NotNextInWithdrawalQueue)
+    );
+    queue.pop(&keys[0], &mut deposits[0]).unwrap();
+    assert_eq!(deposits[0].queued, None);
+    assert_eq!(queue.head, Some(keys[1]));
+    assert_eq!(queue.total_pending, 9_000);
+
+    // A position can leave the queue from anywhere, unlinked from the previous one
+    let (previous, deposit) = pair(&mut deposits, 1, 2);
+    queue
+        .remove(&keys[2], deposit, Some((&keys[1], previous)))
+        .unwrap();
+    assert_eq!(deposits[1].queued.unwrap().next, Some(keys[3]));
+    assert_eq!(queue.length, 2);
+    assert_eq!(queue.total_pending, 6_000);
+
+    assert_eq!(
+        queue.remove(&keys[3], &mut deposits[3], None),
+        Err(AstrapeError::WithdrawalQueueMismatch)
+    );
+    let (previous, deposit) = pair(&mut deposits, 2, 3);
+    assert_eq!(
+        queue.remove(&keys[3], deposit, Some((&keys[2], previous))),
+        Err(AstrapeError::WithdrawalQueueMismatch)
+    );
+    let (previous, deposit) = pair(&mut deposits, 1, 3);
+    queue
+        .remove(&keys[3], deposit, Some((&keys[1], previous)))
+        .unwrap();
+    assert_eq!(queue.tail, Some(keys[1]));
+    assert_eq!(deposits[1].queued.unwrap().next, None);
+
+    queue.pop(&keys[1], &mut deposits[1]).unwrap();
+    assert_eq!(queue, WithdrawalQueue::default());
+    assert_eq!(
+        queue.pop(&keys[0], &mut deposits[0]),
+        Err(AstrapeError::NotNextInWithdrawalQueue)
+    );
+
+    // The length of the queue isn't bounded by the queue account
+    let mut tail = (Pubkey::new_unique(), position(0, ONE_MONTH, 0));
+    queue.push(&tail.0, &mut tail.1, None, 0).unwrap();
+    for _ in 0..1_000 {
+        let mut next = (Pubkey::new_unique(), position(0, ONE_MONTH, 0));
+        queue
+            .push(&next.0, &mut next.1, Some((&tail.0, &mut tail.1)), 0)
+            .unwrap();
+        tail = next;
+    }
+    assert_eq!(queue.length, 1_001);
+    assert_eq!(queue.total_pending, 1_001 * 20_000_000);
+}